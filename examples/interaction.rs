@@ -1,4 +1,4 @@
-fn main() {
+fn main() -> Result<(), vviz::VvizError> {
     use clap::Parser;
     let args = vviz::app::Args::parse();
 
@@ -17,15 +17,15 @@ fn main() {
 
     let manipulation = Manipulation::Position;
 
-    vviz::app::spawn(args.mode, move |mut manager: vviz::manager::Manager| {
-        let w3d = manager.add_widget3("w3d".to_string());
+    vviz::app::spawn_with_args(args, move |mut manager: vviz::manager::Manager| {
+        let w3d = manager.add_widget3("w3d");
 
-        w3d.place_entity("cube".to_string(), vviz::entities::colored_cube(1.0));
+        w3d.place_entity("cube", vviz::entities::colored_cube(1.0));
         let mut scene_pose_entity = nalgebra::Isometry3::<f32>::identity();
 
-        let mut ui_delta = manager.add_ranged_value("delta".to_string(), 0.0, (-1.0, 1.0));
-        let mut ui_dim = manager.add_ranged_value("dimension".to_string(), 0, (0, 2));
-        let mut ui_manipulation = manager.add_enum("manipulation".to_string(), manipulation);
+        let mut ui_delta = manager.add_ranged_value("delta", 0.0, (-1.0, 1.0));
+        let mut ui_dim = manager.add_ranged_value("dimension", 0, (0, 2));
+        let mut ui_manipulation = manager.add_enum("manipulation", manipulation);
 
         loop {
             if ui_delta.get_new_value().is_some()
@@ -45,9 +45,9 @@ fn main() {
                             nalgebra::UnitQuaternion::<f32>::from_scaled_axis(scaled_axis);
                     }
                 }
-                w3d.update_scene_pose_entity("cube".to_string(), scene_pose_entity)
+                w3d.update_scene_pose_entity("cube", scene_pose_entity)
             }
             manager.sync_with_gui();
         }
-    });
+    })
 }
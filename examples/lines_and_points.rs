@@ -1,17 +1,17 @@
-fn main() {
+fn main() -> Result<(), vviz::VvizError> {
     use clap::Parser;
     let args = vviz::app::Args::parse();
 
-    vviz::app::spawn(args.mode, |mut manager: vviz::manager::Manager| {
-        let w3d = manager.add_widget3("w3d".to_string());
+    vviz::app::spawn_with_args(args, |mut manager: vviz::manager::Manager| {
+        let w3d = manager.add_widget3("w3d");
         w3d.place_entity_at(
-            "axis".to_string(),
+            "axis",
             vviz::entities::Axis3::from_scale(1.0).into(),
             vviz::math::rot_x(0.7),
         );
 
         w3d.place_entity_at(
-            "points".to_string(),
+            "points",
             vviz::entities::ColoredPoints3::from_arrays_and_color(
                 vec![
                     [0.50, 0.50, 0.5],
@@ -32,5 +32,5 @@ fn main() {
         loop {
             manager.sync_with_gui();
         }
-    });
+    })
 }
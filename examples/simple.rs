@@ -1,16 +1,16 @@
-fn main() {
+fn main() -> Result<(), vviz::VvizError> {
     use clap::Parser;
     let args = vviz::app::Args::parse();
 
-    vviz::app::spawn(args.mode, |mut manager: vviz::manager::Manager| {
-        let w3d = manager.add_widget3("w3d".to_string());
+    vviz::app::spawn_with_args(args, |mut manager: vviz::manager::Manager| {
+        let w3d = manager.add_widget3("w3d");
         w3d.place_entity_at(
-            "cube".to_string(),
+            "cube",
             vviz::entities::colored_cube(1.0),
             vviz::math::rot_x(0.7),
         );
         loop {
             manager.sync_with_gui();
         }
-    });
+    })
 }
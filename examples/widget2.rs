@@ -1,13 +1,13 @@
-fn main() {
+fn main() -> Result<(), vviz::VvizError> {
     use clap::Parser;
     let args = vviz::app::Args::parse();
 
-    vviz::app::spawn(args.mode, |mut manager: vviz::manager::Manager| {
+    vviz::app::spawn_with_args(args, |mut manager: vviz::manager::Manager| {
         let image: image::DynamicImage = vviz::utilities::load_image_from_url(
             "https://rustacean.net/assets/rustacean-orig-noshadow.png",
         )
         .unwrap();
-        manager.add_widget2("img".to_string(), image.into_rgba8());
+        manager.add_widget2("img", image.into_rgba8());
         manager.sync_with_gui();
-    });
+    })
 }
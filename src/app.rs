@@ -3,12 +3,15 @@
 use clap::Parser;
 
 use super::common;
+use super::error::VvizError;
 use super::gui;
 use super::manager;
 
 struct App {
     to_gui_loop_receiver: Option<std::sync::mpsc::Receiver<common::ToGuiLoopMessage>>,
     from_gui_loop_sender: Option<std::sync::mpsc::Sender<common::FromGuiLoopMessage>>,
+    style: Option<egui::Style>,
+    max_frame_rate: Option<f32>,
 }
 
 impl App {
@@ -16,38 +19,69 @@ impl App {
         App {
             to_gui_loop_receiver: None,
             from_gui_loop_sender: None,
+            style: None,
+            max_frame_rate: None,
         }
     }
 
-    fn spawn(mut self, f: impl FnOnce(manager::Manager) + Send + 'static) {
+    fn spawn(mut self, f: impl FnOnce(manager::Manager) + Send + 'static) -> Result<(), VvizError> {
         let (to_gui_loop_sender, to_gui_loop_receiver) = std::sync::mpsc::channel();
         self.to_gui_loop_receiver = Some(to_gui_loop_receiver);
 
         let (from_gui_loop_sender, from_gui_loop_receiver) = std::sync::mpsc::channel();
         self.from_gui_loop_sender = Some(from_gui_loop_sender);
 
+        let panic_sender = to_gui_loop_sender.clone();
         std::thread::spawn(move || {
             let manager = manager::Manager::new_local(to_gui_loop_sender, from_gui_loop_receiver);
-            f(manager);
+            // The default panic hook has already printed the panic to stderr at this point. We
+            // additionally forward it to the gui, so it does not look like a hung window.
+            if let Err(payload) =
+                std::panic::catch_unwind(std::panic::AssertUnwindSafe(move || f(manager)))
+            {
+                let message = panic_message(payload.as_ref());
+                // If the gui is gone already, there is nobody left to inform.
+                let _ = panic_sender.send(common::ToGuiLoopMessage::AppThreadPanic(
+                    common::AppThreadPanic { message },
+                ));
+            }
         });
-        self.block_on_gui_loop();
+        self.block_on_gui_loop()
     }
 
-    fn block_on_gui_loop(self) {
+    fn block_on_gui_loop(self) -> Result<(), VvizError> {
         let conf = miniquad::conf::Conf {
             high_dpi: true,
             ..Default::default()
         };
-        miniquad::start(conf, |mut ctx| {
-            miniquad::UserData::owning(
-                gui::GuiLoop::new(
+        // Miniquad panics if the window cannot be created. As above, the panic was printed already.
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(move || {
+            miniquad::start(conf, move |mut ctx| {
+                let mut gui_loop = gui::GuiLoop::new(
                     &mut ctx,
                     self.to_gui_loop_receiver.unwrap(),
                     self.from_gui_loop_sender.unwrap(),
-                ),
-                ctx,
-            )
-        });
+                );
+                if let Some(style) = self.style {
+                    gui_loop = gui_loop.with_style(style);
+                }
+                if let Some(max_frame_rate) = self.max_frame_rate {
+                    gui_loop = gui_loop.with_max_frame_rate(max_frame_rate);
+                }
+                miniquad::UserData::owning(gui_loop, ctx)
+            })
+        }))
+        .map_err(|payload| VvizError::Gui(panic_message(payload.as_ref())))
+    }
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic payload".to_string()
     }
 }
 
@@ -67,14 +101,26 @@ pub struct Args {
     /// visualization mode
     #[clap(short, long, arg_enum)]
     pub mode: VVizMode,
+    /// address the websocket server binds to in remote mode
+    #[clap(long, default_value = manager::RemoteConfig::DEFAULT_ADDRESS)]
+    pub address: String,
+    /// shared secret token the remote client must send in remote mode
+    #[clap(long)]
+    pub token: Option<String>,
+    /// maximal number of frames the gui draws per second, e.g. to save power
+    #[clap(long)]
+    pub max_frame_rate: Option<f32>,
 }
 
 /// This spawns the application thread - which one whishes to visually/interactively debug.
 ///
+/// In local mode, it blocks until the gui window is closed, and fails if the window cannot be
+/// created. In remote mode, it fails if the websocket server cannot be started.
+///
 /// Example
 /// ``` no_run
 /// vviz::app::spawn(vviz::app::VVizMode::Local, |mut manager: vviz::manager::Manager| {
-///     let mut ui_a_button = manager.add_button("a button".to_string());
+///     let mut ui_a_button = manager.add_button("a button");
 ///
 ///     // Some initial application logic...
 ///
@@ -87,17 +133,97 @@ pub struct Args {
 ///
 ///         manager.sync_with_gui();
 ///     }
-/// });
+/// })
+/// .unwrap();
 /// ```
-pub fn spawn(mode: VVizMode, f: impl FnOnce(manager::Manager) + Send + 'static) {
+pub fn spawn(
+    mode: VVizMode,
+    f: impl FnOnce(manager::Manager) + Send + 'static,
+) -> Result<(), VvizError> {
     match mode {
         VVizMode::Local => {
             let vviz = App::new();
-            vviz.spawn(f);
+            vviz.spawn(f)
+        }
+        VVizMode::Remote => spawn_remote(manager::RemoteConfig::default(), f),
+    }
+}
+
+/// Same as [spawn], but configured by `args`: the gui draws at most `max_frame_rate` frames per
+/// second, see [manager::Manager::set_max_frame_rate]. In remote mode, the websocket server binds
+/// to `address` and requires `token`, if any. Set a token when binding to a non-local address.
+///
+/// Example
+/// ``` no_run
+/// use clap::Parser;
+///
+/// // e.g. `--mode remote --address 0.0.0.0:9001 --token <secret>`
+/// let args = vviz::app::Args::parse();
+/// vviz::app::spawn_with_args(args, |mut manager: vviz::manager::Manager| {
+///     loop {
+///         manager.sync_with_gui();
+///     }
+/// })
+/// .unwrap();
+/// ```
+pub fn spawn_with_args(
+    args: Args,
+    f: impl FnOnce(manager::Manager) + Send + 'static,
+) -> Result<(), VvizError> {
+    match args.mode {
+        VVizMode::Local => {
+            let mut vviz = App::new();
+            vviz.max_frame_rate = args.max_frame_rate;
+            vviz.spawn(f)
         }
         VVizMode::Remote => {
-            let manager = manager::Manager::new_remote();
-            f(manager);
+            let max_frame_rate = args.max_frame_rate;
+            let config = manager::RemoteConfig {
+                address: args.address,
+                token: args.token,
+            };
+            // The remote client's gui is configured through the connection.
+            spawn_remote(config, move |manager| {
+                if max_frame_rate.is_some() {
+                    manager.set_max_frame_rate(max_frame_rate);
+                }
+                f(manager)
+            })
         }
     }
 }
+
+/// Same as [spawn] in [VVizMode::Local], but with a custom egui style - e.g. font sizes, spacing
+/// and visuals.
+///
+/// Example
+/// ``` no_run
+/// let mut style = egui::Style::default();
+/// style.visuals = egui::Visuals::light();
+/// style.spacing.item_spacing = egui::vec2(10.0, 6.0);
+/// vviz::app::spawn_with_style(style, |mut manager: vviz::manager::Manager| {
+///     loop {
+///         manager.sync_with_gui();
+///     }
+/// })
+/// .unwrap();
+/// ```
+pub fn spawn_with_style(
+    style: egui::Style,
+    f: impl FnOnce(manager::Manager) + Send + 'static,
+) -> Result<(), VvizError> {
+    let mut vviz = App::new();
+    vviz.style = Some(style);
+    vviz.spawn(f)
+}
+
+/// Same as [spawn] in [VVizMode::Remote], but with explicit websocket server configuration, e.g.
+/// to require a shared secret token from the remote client.
+pub fn spawn_remote(
+    config: manager::RemoteConfig,
+    f: impl FnOnce(manager::Manager) + Send + 'static,
+) -> Result<(), VvizError> {
+    let manager = manager::Manager::new_remote(config)?;
+    f(manager);
+    Ok(())
+}
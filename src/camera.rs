@@ -0,0 +1,134 @@
+//! Camera control of 3d widgets, independent of the gui.
+
+use super::common::RotationMode;
+
+/// Orbit camera control, as used by [super::common::Widget3] for mouse interaction.
+///
+/// The camera rotates about an orbit center, zooms towards it and pans in its image plane. The
+/// controller can also be driven programmatically, e.g. for scripted camera paths.
+///
+/// Example
+/// ```
+/// use vviz::camera::OrbitController;
+/// use vviz::common::RotationMode;
+///
+/// let start = nalgebra::Isometry3::translation(0.0, 0.0, -4.0) * vviz::math::rot_x(0.3);
+/// for rotation_mode in [RotationMode::Trackball, RotationMode::Turntable] {
+///     let mut controller = OrbitController::new(start);
+///     controller.set_rotation_mode(rotation_mode);
+///     controller.set_scene_point_orbit_center(nalgebra::Point3::new(1.0, 0.5, 0.0));
+///     // A full orbit returns to the start pose.
+///     for _ in 0..36 {
+///         controller.rotate(nalgebra::Vector2::new(10.0_f32.to_radians(), 0.0));
+///     }
+///     let pose = controller.camera_pose_scene();
+///     assert!((pose.translation.vector - start.translation.vector).norm() < 1e-4);
+///     assert!(pose.rotation.angle_to(&start.rotation) < 1e-4);
+///
+///     controller.zoom(2.0);
+///     controller.pan(nalgebra::Vector2::new(0.5, -0.5));
+///     controller.reset();
+///     assert_eq!(controller.camera_pose_scene(), start);
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OrbitController {
+    camera_pose_scene: nalgebra::Isometry3<f32>,
+    initial_camera_pose_scene: nalgebra::Isometry3<f32>,
+    scene_point_orbit_center: nalgebra::Point3<f32>,
+    rotation_mode: RotationMode,
+}
+
+impl OrbitController {
+    /// Creates controller with given camera pose - i.e. the transformation from scene to camera,
+    /// which is also the pose [OrbitController::reset] returns to. The orbit center is the scene
+    /// origin.
+    pub fn new(camera_pose_scene: nalgebra::Isometry3<f32>) -> Self {
+        Self {
+            camera_pose_scene,
+            initial_camera_pose_scene: camera_pose_scene,
+            scene_point_orbit_center: nalgebra::Point3::origin(),
+            rotation_mode: RotationMode::default(),
+        }
+    }
+
+    /// The camera pose - i.e. the transformation from scene to camera.
+    pub fn camera_pose_scene(&self) -> nalgebra::Isometry3<f32> {
+        self.camera_pose_scene
+    }
+
+    /// Sets the camera pose, without changing the pose [OrbitController::reset] returns to.
+    pub fn set_camera_pose_scene(&mut self, camera_pose_scene: nalgebra::Isometry3<f32>) {
+        self.camera_pose_scene = camera_pose_scene;
+    }
+
+    /// The point in scene coordinates the camera rotates about and zooms towards.
+    pub fn scene_point_orbit_center(&self) -> nalgebra::Point3<f32> {
+        self.scene_point_orbit_center
+    }
+
+    /// Sets the point in scene coordinates the camera rotates about and zooms towards.
+    pub fn set_scene_point_orbit_center(&mut self, scene_point: nalgebra::Point3<f32>) {
+        self.scene_point_orbit_center = scene_point;
+    }
+
+    /// How [OrbitController::rotate] rotates the scene.
+    pub fn rotation_mode(&self) -> RotationMode {
+        self.rotation_mode
+    }
+
+    /// Sets how [OrbitController::rotate] rotates the scene.
+    pub fn set_rotation_mode(&mut self, rotation_mode: RotationMode) {
+        self.rotation_mode = rotation_mode;
+    }
+
+    /// Rotates the scene about the orbit center by `delta.x` (yaw, about the vertical) and
+    /// `delta.y` (pitch, about the horizontal), in radians, according to the [RotationMode].
+    pub fn rotate(&mut self, delta: nalgebra::Vector2<f32>) {
+        let (yaw, pitch) = (delta.x, delta.y);
+        let rotation = match self.rotation_mode {
+            RotationMode::Trackball => {
+                let scaled_axis = nalgebra::Vector3::new(pitch, yaw, 0.0);
+                let scene_rot_camera = self.camera_pose_scene.rotation.inverse();
+                self.camera_pose_scene.rotation
+                    * nalgebra::UnitQuaternion::from_scaled_axis(
+                        scene_rot_camera.transform_vector(&scaled_axis),
+                    )
+            }
+            RotationMode::Turntable => {
+                // Azimuth about the scene's up axis, elevation about the camera's x axis.
+                let azimuth =
+                    nalgebra::UnitQuaternion::from_axis_angle(&nalgebra::Vector3::y_axis(), yaw);
+                let elevation =
+                    nalgebra::UnitQuaternion::from_axis_angle(&nalgebra::Vector3::x_axis(), pitch);
+                elevation * self.camera_pose_scene.rotation * azimuth
+            }
+        };
+        // The orbit center keeps its position in the camera frame.
+        let camera_point_center = self.camera_pose_scene * self.scene_point_orbit_center;
+        self.camera_pose_scene.rotation = rotation;
+        self.camera_pose_scene.translation.vector =
+            camera_point_center.coords - rotation * self.scene_point_orbit_center.coords;
+    }
+
+    /// Moves the camera by `delta` in its image plane, in scene units, i.e. the scene moves in the
+    /// opposite direction. The orbit center is not moved.
+    pub fn pan(&mut self, delta: nalgebra::Vector2<f32>) {
+        self.camera_pose_scene.translation.vector += nalgebra::Vector3::new(delta.x, delta.y, 0.0);
+    }
+
+    /// Moves the camera towards (`factor` > 1) or away from (`factor` < 1) the orbit center.
+    pub fn zoom(&mut self, factor: f32) {
+        if factor > 0.0 {
+            let camera_point_center = self.camera_pose_scene * self.scene_point_orbit_center;
+            let rotated_center = self.camera_pose_scene.rotation * self.scene_point_orbit_center;
+            self.camera_pose_scene.translation.vector =
+                camera_point_center.coords / factor - rotated_center.coords;
+        }
+    }
+
+    /// Returns to the initial camera pose.
+    pub fn reset(&mut self) {
+        self.camera_pose_scene = self.initial_camera_pose_scene;
+    }
+}
@@ -0,0 +1,78 @@
+//! Optional build-time features and backend limits, e.g. to degrade gracefully.
+
+/// Optional cargo features of vviz, and whether they are enabled in this build.
+const FEATURES: &[(&str, bool)] = &[("opencv", cfg!(feature = "opencv"))];
+
+/// GPU limits, queried once the gui window was created.
+static GPU_LIMITS: std::sync::OnceLock<GpuLimits> = std::sync::OnceLock::new();
+
+#[derive(Clone, Copy, Debug)]
+struct GpuLimits {
+    max_texture_size: u32,
+    max_msaa_samples: u32,
+}
+
+/// What is available in this build and on this machine, see [capabilities].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Capabilities {
+    /// Names of the enabled optional cargo features.
+    pub enabled_features: std::vec::Vec<&'static str>,
+    /// Whether points are rendered as GL points. If not, points are approximated by tiny
+    /// triangles, see [super::entities::ColoredPoints3].
+    pub gl_points: bool,
+    /// Whether the gui requests vsync, i.e. frames are paced by the display's refresh rate.
+    /// Miniquad always requests a swap interval of one; drivers might override it though.
+    pub vsync: bool,
+    /// Number of MSAA samples of the gui window; 1 means no multisampling.
+    pub msaa_sample_count: i32,
+    /// Maximal width and height of textures, e.g. images of 2d widgets. None if unknown.
+    pub max_texture_size: Option<u32>,
+    /// Maximal number of MSAA samples supported by the GPU. None if unknown.
+    pub max_msaa_samples: Option<u32>,
+}
+
+/// Returns the enabled features and the backend limits.
+///
+/// The GPU limits are only known once the gui window was created, i.e. shortly after
+/// [super::app::spawn] in local mode; they are None before and in remote mode.
+///
+/// Example
+/// ```
+/// let capabilities = vviz::capabilities();
+/// assert!(!capabilities.gl_points);
+/// assert_eq!(capabilities.max_texture_size, None);
+/// ```
+pub fn capabilities() -> Capabilities {
+    let gpu_limits = GPU_LIMITS.get();
+    Capabilities {
+        enabled_features: FEATURES
+            .iter()
+            .filter(|(_, enabled)| *enabled)
+            .map(|(name, _)| *name)
+            .collect(),
+        // Miniquad 0.3.0-alpha.43 has no `PrimitiveType::Points` (yet).
+        gl_points: false,
+        vsync: true,
+        // The window is created with the default configuration.
+        msaa_sample_count: miniquad::conf::Conf::default().sample_count,
+        max_texture_size: gpu_limits.map(|limits| limits.max_texture_size),
+        max_msaa_samples: gpu_limits
+            .map(|limits| limits.max_msaa_samples)
+            .filter(|samples| *samples > 0),
+    }
+}
+
+/// Queries the GPU limits; must be called on the gui thread with a current GL context.
+pub(crate) fn query_gpu_limits() {
+    let mut max_texture_size = 0;
+    let mut max_msaa_samples = 0;
+    unsafe {
+        miniquad::gl::glGetIntegerv(miniquad::gl::GL_MAX_TEXTURE_SIZE, &mut max_texture_size);
+        // Not available on GLES2 contexts, in which case the value is left unchanged.
+        miniquad::gl::glGetIntegerv(miniquad::gl::GL_MAX_SAMPLES, &mut max_msaa_samples);
+    }
+    let _ = GPU_LIMITS.set(GpuLimits {
+        max_texture_size: max_texture_size.max(0) as u32,
+        max_msaa_samples: max_msaa_samples.max(0) as u32,
+    });
+}
@@ -0,0 +1,184 @@
+//! Colormaps, to visualize scalar data such as depth images.
+
+use serde::{Deserialize, Serialize};
+
+use super::entities::Color;
+
+/// Maps a scalar in [0..1] to a color.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum Colormap {
+    /// Black to white.
+    Gray,
+    /// Blue - cyan - yellow - red, the classic MATLAB rainbow.
+    Jet,
+    /// Perceptually smoother rainbow; dark blue - green - dark red.
+    #[default]
+    Turbo,
+}
+
+impl Colormap {
+    /// Returns the color for `t`. Values outside of [0..1] are clamped.
+    pub fn map(&self, t: f32) -> Color {
+        let t = t.clamp(0.0, 1.0);
+        let (r, g, b) = match self {
+            Colormap::Gray => (t, t, t),
+            Colormap::Jet => {
+                let ramp = |x: f32| (1.5 - (4.0 * t - x).abs()).clamp(0.0, 1.0);
+                (ramp(3.0), ramp(2.0), ramp(1.0))
+            }
+            Colormap::Turbo => {
+                // Polynomial approximation of Google's Turbo colormap.
+                let t2 = t * t;
+                let t3 = t2 * t;
+                let t4 = t3 * t;
+                let t5 = t4 * t;
+                let r = 0.135_721_4 + 4.615_393 * t - 42.660_32 * t2 + 132.131_08 * t3
+                    - 152.942_4 * t4
+                    + 59.286_38 * t5;
+                let g = 0.091_402_6 + 2.194_188 * t + 4.842_967 * t2 - 14.185_033 * t3
+                    + 4.277_299 * t4
+                    + 2.829_566 * t5;
+                let b = 0.106_673_3 + 12.641_946 * t - 60.582_05 * t2 + 110.362_77 * t3
+                    - 89.903_11 * t4
+                    + 27.348_25 * t5;
+                (r.clamp(0.0, 1.0), g.clamp(0.0, 1.0), b.clamp(0.0, 1.0))
+            }
+        };
+        Color {
+            r,
+            g,
+            b,
+            alpha: 1.0,
+        }
+    }
+
+    /// Same as [Colormap::map], but as 8-bit RGBA values.
+    pub fn map_rgba8(&self, t: f32) -> [u8; 4] {
+        to_rgba8(self.map(t))
+    }
+}
+
+/// Converts `color` to 8-bit RGBA values, without (alpha) premultiplication.
+fn to_rgba8(color: Color) -> [u8; 4] {
+    let to_u8 = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+    [
+        to_u8(color.r),
+        to_u8(color.g),
+        to_u8(color.b),
+        to_u8(color.alpha),
+    ]
+}
+
+/// Colorizes a 16-bit depth image of size `width` x `height`, e.g. depth in millimeters.
+///
+/// Depth values inside of `range` are mapped linearly through `colormap`, with the near end of the
+/// range mapped to 0.0. Invalid pixels, i.e. zero or out of range, are set to `invalid_color`. If
+/// `range` is `None`, the min/max of all non-zero pixels is used.
+///
+/// Panics if `depth.len() != width * height`.
+///
+/// Example
+/// ```
+/// use vviz::colormap::{depth16_to_rgba8, Colormap};
+///
+/// let transparent = vviz::entities::Color { r: 0.0, g: 0.0, b: 0.0, alpha: 0.0 };
+/// let rgba = depth16_to_rgba8(&[0, 500, 1000, 4000], 2, 2, None, Colormap::Gray, transparent);
+/// assert_eq!(rgba.get_pixel(0, 0).0, [0, 0, 0, 0]);
+/// assert_eq!(rgba.get_pixel(1, 0).0, [0, 0, 0, 255]);
+/// assert_eq!(rgba.get_pixel(1, 1).0, [255, 255, 255, 255]);
+///
+/// let rgba = depth16_to_rgba8(&[0, 500, 1000, 4000], 2, 2, Some((500, 1000)), Colormap::Gray, transparent);
+/// assert_eq!(rgba.get_pixel(1, 1).0, [0, 0, 0, 0]);
+/// ```
+pub fn depth16_to_rgba8(
+    depth: &[u16],
+    width: u32,
+    height: u32,
+    range: Option<(u16, u16)>,
+    colormap: Colormap,
+    invalid_color: Color,
+) -> image::RgbaImage {
+    assert_eq!(
+        depth.len(),
+        width as usize * height as usize,
+        "depth length does not match width * height"
+    );
+    let (near, far) = range.unwrap_or_else(|| {
+        depth
+            .iter()
+            .filter(|d| **d != 0)
+            .fold((u16::MAX, 0), |(lo, hi), d| (lo.min(*d), hi.max(*d)))
+    });
+    let invalid = image::Rgba(to_rgba8(invalid_color));
+    let span = (far as f32 - near as f32).max(1.0);
+
+    image::RgbaImage::from_fn(width, height, |u, v| {
+        let d = depth[(v * width + u) as usize];
+        if d == 0 || d < near || d > far {
+            invalid
+        } else {
+            image::Rgba(colormap.map_rgba8((d - near) as f32 / span))
+        }
+    })
+}
+
+/// Tone-maps a float image of size `width` x `height`, e.g. radiance or disparity.
+///
+/// Values are normalized by `range` (min, max) - or by the min/max of all finite pixels if
+/// `range` is `None` - then scaled by `2^exposure`, gamma corrected by `1/gamma` and mapped through
+/// `colormap`. Non-finite pixels, i.e. NaN or infinite, are transparent.
+///
+/// Panics if `data.len() != width * height`.
+///
+/// Example
+/// ```
+/// use vviz::colormap::{float_to_rgba8, Colormap};
+///
+/// let data = [0.0, 0.25, 1.0, f32::NAN];
+/// let rgba = float_to_rgba8(&data, 2, 2, None, Colormap::Gray, 0.0, 1.0);
+/// assert_eq!(rgba.get_pixel(0, 0).0, [0, 0, 0, 255]);
+/// assert_eq!(rgba.get_pixel(1, 0).0, [64, 64, 64, 255]);
+/// assert_eq!(rgba.get_pixel(0, 1).0, [255, 255, 255, 255]);
+/// assert_eq!(rgba.get_pixel(1, 1).0, [0, 0, 0, 0]);
+///
+/// // One stop brighter, with values above the range saturating.
+/// let rgba = float_to_rgba8(&data, 2, 2, Some((0.0, 1.0)), Colormap::Gray, 1.0, 1.0);
+/// assert_eq!(rgba.get_pixel(1, 0).0, [128, 128, 128, 255]);
+///
+/// // Gamma 2.0 maps 0.25 to sqrt(0.25).
+/// let rgba = float_to_rgba8(&data, 2, 2, Some((0.0, 1.0)), Colormap::Gray, 0.0, 2.0);
+/// assert_eq!(rgba.get_pixel(1, 0).0, [128, 128, 128, 255]);
+/// ```
+pub fn float_to_rgba8(
+    data: &[f32],
+    width: u32,
+    height: u32,
+    range: Option<(f32, f32)>,
+    colormap: Colormap,
+    exposure: f32,
+    gamma: f32,
+) -> image::RgbaImage {
+    assert_eq!(
+        data.len(),
+        width as usize * height as usize,
+        "data length does not match width * height"
+    );
+    let (min, max) = range.unwrap_or_else(|| {
+        data.iter()
+            .filter(|v| v.is_finite())
+            .fold((f32::MAX, f32::MIN), |(lo, hi), v| (lo.min(*v), hi.max(*v)))
+    });
+    let span = (max - min).max(f32::EPSILON);
+    let scale = exposure.exp2();
+    let inv_gamma = 1.0 / gamma.max(f32::EPSILON);
+
+    image::RgbaImage::from_fn(width, height, |u, v| {
+        let value = data[(v * width + u) as usize];
+        if value.is_finite() {
+            let t = ((value - min) / span * scale).clamp(0.0, 1.0);
+            image::Rgba(colormap.map_rgba8(t.powf(inv_gamma)))
+        } else {
+            image::Rgba([0, 0, 0, 0])
+        }
+    })
+}
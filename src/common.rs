@@ -1,7 +1,10 @@
 //! Common structures shared between [super::manager::Manager] and [super::gui::GuiLoop].
 
+use super::camera;
+use super::colormap;
 use super::entities;
 use super::gui;
+use super::math;
 
 use ::slice_of_array::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -9,12 +12,30 @@ use serde::{Deserialize, Serialize};
 /// Component such as a button or a slider.
 pub trait Component: downcast_rs::DowncastSync {
     /// How to display the component on the side panel.
+    ///
+    /// Send errors are to be ignored: the application thread might have ended already, e.g. by a
+    /// panic, while the gui keeps showing.
     fn show(
         &mut self,
         label: &str,
         ui: &mut egui::Ui,
         sender: &mut std::sync::mpsc::Sender<FromGuiLoopMessage>,
     );
+
+    /// Message which recreates the component in its current state, see [gui::GuiSnapshot].
+    ///
+    /// Returns None if the component cannot be recreated.
+    fn snapshot(&self, _label: &str) -> Option<ToGuiLoopMessage> {
+        None
+    }
+
+    /// Messages which restore the current user-editable value of the component, see
+    /// [super::manager::Manager::save_preset].
+    ///
+    /// Returns an empty vector if the component has no such value, e.g. for buttons.
+    fn values(&self, _label: &str) -> std::vec::Vec<FromGuiLoopMessage> {
+        std::vec::Vec::new()
+    }
 }
 
 impl core::fmt::Debug for dyn Component {
@@ -52,16 +73,31 @@ impl Component for EnumStringRepr {
 
         if *self.value != selected {
             self.value = selected.to_string();
-            sender
-                .send(FromGuiLoopMessage::UpdateEnumStringRepr(
-                    UpdateEnumStringRepr {
-                        label: label.to_string(),
-                        value: self.value.clone(),
-                    },
-                ))
-                .unwrap();
+            let _ = sender.send(FromGuiLoopMessage::UpdateEnumStringRepr(
+                UpdateEnumStringRepr {
+                    label: label.to_string(),
+                    value: self.value.clone(),
+                },
+            ));
         }
     }
+
+    fn snapshot(&self, label: &str) -> Option<ToGuiLoopMessage> {
+        Some(ToGuiLoopMessage::AddEnumStringRepr(AddEnumStringRepr {
+            label: label.to_string(),
+            value: self.value.clone(),
+            values: self.values.clone(),
+        }))
+    }
+
+    fn values(&self, label: &str) -> std::vec::Vec<FromGuiLoopMessage> {
+        vec![FromGuiLoopMessage::UpdateEnumStringRepr(
+            UpdateEnumStringRepr {
+                label: label.to_string(),
+                value: self.value.clone(),
+            },
+        )]
+    }
 }
 
 /// Variable bool (checkbox) or numeric (read-only text box).
@@ -80,14 +116,26 @@ impl Component for Var<bool> {
         sender: &mut std::sync::mpsc::Sender<FromGuiLoopMessage>,
     ) {
         if ui.checkbox(&mut self.value, label).changed() {
-            sender
-                .send(FromGuiLoopMessage::UpdateValueBool(UpdateValue {
-                    label: label.to_string(),
-                    value: self.value,
-                }))
-                .unwrap();
+            let _ = sender.send(FromGuiLoopMessage::UpdateValueBool(UpdateValue {
+                label: label.to_string(),
+                value: self.value,
+            }));
         }
     }
+
+    fn snapshot(&self, label: &str) -> Option<ToGuiLoopMessage> {
+        Some(ToGuiLoopMessage::AddVarBool(AddVar {
+            label: label.to_string(),
+            value: self.value,
+        }))
+    }
+
+    fn values(&self, label: &str) -> std::vec::Vec<FromGuiLoopMessage> {
+        vec![FromGuiLoopMessage::UpdateValueBool(UpdateValue {
+            label: label.to_string(),
+            value: self.value,
+        })]
+    }
 }
 
 /// A button.
@@ -106,13 +154,367 @@ impl Component for Button {
         sender: &mut std::sync::mpsc::Sender<FromGuiLoopMessage>,
     ) {
         if ui.button(label).clicked() {
-            sender
-                .send(FromGuiLoopMessage::UpdateButton(UpdateButton {
+            let _ = sender.send(FromGuiLoopMessage::UpdateButton(UpdateButton {
+                label: label.to_string(),
+            }));
+        }
+    }
+
+    fn snapshot(&self, label: &str) -> Option<ToGuiLoopMessage> {
+        Some(ToGuiLoopMessage::AddButton(AddButton {
+            label: label.to_string(),
+        }))
+    }
+}
+
+/// A button which asks for confirmation in a modal dialog before it counts as pressed.
+///
+/// Interfaced by [super::manager::UiConfirmButton]. On the application side it is mirrored as a
+/// [Button], since a confirmed press is reported as [UpdateButton].
+pub struct ConfirmButton {
+    /// The question shown in the dialog.
+    pub prompt: String,
+    /// Whether the confirmation dialog is currently open.
+    pub dialog_open: bool,
+}
+
+impl Component for ConfirmButton {
+    fn show(
+        &mut self,
+        label: &str,
+        ui: &mut egui::Ui,
+        sender: &mut std::sync::mpsc::Sender<FromGuiLoopMessage>,
+    ) {
+        if ui.button(label).clicked() {
+            self.dialog_open = true;
+        }
+        if !self.dialog_open {
+            return;
+        }
+
+        // Covers the whole window, so that the rest of the ui does not receive any input.
+        let screen_rect = ui.ctx().input().screen_rect();
+        egui::Area::new(format!("{} modal blocker", label))
+            .fixed_pos(screen_rect.min)
+            .show(ui.ctx(), |ui| {
+                ui.allocate_response(screen_rect.size(), egui::Sense::click_and_drag());
+                ui.painter()
+                    .rect_filled(screen_rect, 0.0, egui::Color32::from_black_alpha(128));
+            });
+
+        let mut confirmed = false;
+        egui::Area::new(format!("{} modal", label))
+            .order(egui::Order::Foreground)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ui.ctx(), |ui| {
+                egui::Frame::window(ui.style()).show(ui, |ui| {
+                    ui.label(&self.prompt);
+                    ui.horizontal(|ui| {
+                        if ui.button("Confirm").clicked() {
+                            confirmed = true;
+                            self.dialog_open = false;
+                        }
+                        if ui.button("Cancel").clicked() {
+                            self.dialog_open = false;
+                        }
+                    });
+                });
+            });
+
+        if confirmed {
+            let _ = sender.send(FromGuiLoopMessage::UpdateButton(UpdateButton {
+                label: label.to_string(),
+            }));
+        }
+    }
+
+    fn snapshot(&self, label: &str) -> Option<ToGuiLoopMessage> {
+        Some(ToGuiLoopMessage::AddConfirmButton(AddConfirmButton {
+            label: label.to_string(),
+            prompt: self.prompt.clone(),
+        }))
+    }
+}
+
+/// A combo box to select a preset, and a row of buttons to save the current values of all
+/// components as new preset, to apply the selected preset and to reset all values.
+///
+/// Interfaced by [super::manager::Manager::add_preset_controls]. The presets themselves are kept
+/// by the manager.
+pub struct PresetControls {
+    /// Names of all presets, in the order they were first saved.
+    pub presets: std::vec::Vec<String>,
+    /// Index of the selected preset, if any.
+    pub selected: Option<usize>,
+}
+
+impl Component for PresetControls {
+    fn show(
+        &mut self,
+        label: &str,
+        ui: &mut egui::Ui,
+        sender: &mut std::sync::mpsc::Sender<FromGuiLoopMessage>,
+    ) {
+        let selected_name = self
+            .selected
+            .and_then(|i| self.presets.get(i))
+            .cloned()
+            .unwrap_or_default();
+        egui::ComboBox::from_label(label)
+            .selected_text(selected_name.clone())
+            .show_ui(ui, |ui| {
+                for (i, name) in self.presets.iter().enumerate() {
+                    ui.selectable_value(&mut self.selected, Some(i), name);
+                }
+            });
+
+        let mut action = None;
+        ui.horizontal(|ui| {
+            if ui.button("Save").clicked() {
+                action = Some(PresetAction::Save);
+            }
+            if ui
+                .add_enabled(!selected_name.is_empty(), egui::Button::new("Apply"))
+                .clicked()
+            {
+                action = Some(PresetAction::Apply(selected_name.clone()));
+            }
+            if ui.button("Reset").clicked() {
+                action = Some(PresetAction::Reset);
+            }
+        });
+        if let Some(action) = action {
+            let _ = sender.send(FromGuiLoopMessage::UpdatePresetControls(
+                UpdatePresetControls {
+                    label: label.to_string(),
+                    action,
+                },
+            ));
+        }
+    }
+
+    fn snapshot(&self, label: &str) -> Option<ToGuiLoopMessage> {
+        Some(ToGuiLoopMessage::AddPresetControls(AddPresetControls {
+            label: label.to_string(),
+            presets: self.presets.clone(),
+        }))
+    }
+}
+
+/// A list of checkboxes, e.g. to toggle layers.
+///
+/// Interfaced by [super::manager::UiCheckboxes].
+pub struct CheckboxList {
+    /// The items and whether they are checked, in display order.
+    pub items: std::vec::Vec<(String, bool)>,
+}
+
+impl CheckboxList {
+    /// Names of all checked items.
+    pub fn checked(&self) -> std::collections::HashSet<String> {
+        self.items
+            .iter()
+            .filter(|(_, checked)| *checked)
+            .map(|(item, _)| item.clone())
+            .collect()
+    }
+}
+
+impl Component for CheckboxList {
+    fn show(
+        &mut self,
+        label: &str,
+        ui: &mut egui::Ui,
+        sender: &mut std::sync::mpsc::Sender<FromGuiLoopMessage>,
+    ) {
+        ui.collapsing(label, |ui| {
+            for (item, checked) in &mut self.items {
+                if ui.checkbox(checked, item.as_str()).changed() {
+                    let _ =
+                        sender.send(FromGuiLoopMessage::UpdateCheckboxList(UpdateCheckboxList {
+                            label: label.to_string(),
+                            item: item.clone(),
+                            value: *checked,
+                        }));
+                }
+            }
+        });
+    }
+
+    fn snapshot(&self, label: &str) -> Option<ToGuiLoopMessage> {
+        Some(ToGuiLoopMessage::AddCheckboxList(AddCheckboxList {
+            label: label.to_string(),
+            items: self.items.clone(),
+        }))
+    }
+
+    fn values(&self, label: &str) -> std::vec::Vec<FromGuiLoopMessage> {
+        self.items
+            .iter()
+            .map(|(item, checked)| {
+                FromGuiLoopMessage::UpdateCheckboxList(UpdateCheckboxList {
                     label: label.to_string(),
-                }))
-                .unwrap();
+                    item: item.clone(),
+                    value: *checked,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Color legend of a [colormap::Colormap] in the side panel, with min/mid/max tick labels.
+pub struct Colorbar {
+    /// The colormap.
+    pub colormap: colormap::Colormap,
+    /// The values mapped to the start and the end of the colormap.
+    pub range: (f32, f32),
+}
+
+impl Colorbar {
+    /// Number of color steps of the gradient.
+    const STEPS: usize = 64;
+
+    /// Height of the gradient in points.
+    const HEIGHT: f32 = 16.0;
+}
+
+impl Component for Colorbar {
+    fn show(
+        &mut self,
+        label: &str,
+        ui: &mut egui::Ui,
+        _sender: &mut std::sync::mpsc::Sender<FromGuiLoopMessage>,
+    ) {
+        ui.label(label);
+        let (rect, _) = ui.allocate_exact_size(
+            egui::Vec2::new(ui.available_width(), Self::HEIGHT),
+            egui::Sense::hover(),
+        );
+        let painter = ui.painter_at(rect);
+        let step_width = rect.width() / Self::STEPS as f32;
+        for i in 0..Self::STEPS {
+            let t = (i as f32 + 0.5) / Self::STEPS as f32;
+            let [r, g, b, _] = self.colormap.map_rgba8(t);
+            let min = rect.min + egui::Vec2::new(i as f32 * step_width, 0.0);
+            // Slightly overlapping steps, to avoid gaps due to rounding.
+            let step =
+                egui::Rect::from_min_size(min, egui::Vec2::new(step_width + 0.5, rect.height()));
+            painter.rect_filled(step, 0.0, egui::Color32::from_rgb(r, g, b));
+        }
+
+        let (min, max) = self.range;
+        let (ticks_rect, _) = ui.allocate_exact_size(
+            egui::Vec2::new(rect.width(), ui.fonts().row_height(egui::TextStyle::Small)),
+            egui::Sense::hover(),
+        );
+        let painter = ui.painter_at(ticks_rect);
+        let color = ui.visuals().text_color();
+        for (anchor, pos, value) in [
+            (egui::Align2::LEFT_TOP, ticks_rect.left_top(), min),
+            (
+                egui::Align2::CENTER_TOP,
+                ticks_rect.center_top(),
+                0.5 * (min + max),
+            ),
+            (egui::Align2::RIGHT_TOP, ticks_rect.right_top(), max),
+        ] {
+            painter.text(
+                pos,
+                anchor,
+                format!("{:.3}", value),
+                egui::TextStyle::Small,
+                color,
+            );
         }
     }
+
+    fn snapshot(&self, label: &str) -> Option<ToGuiLoopMessage> {
+        Some(ToGuiLoopMessage::AddColorbar(AddColorbar {
+            label: label.to_string(),
+            colormap: self.colormap,
+            range: self.range,
+        }))
+    }
+}
+
+/// Read-only display of a pose, with a button to copy it to the clipboard.
+///
+/// The pose is shown as a 4x4 matrix and as translation plus quaternion.
+pub struct PoseReadout {
+    /// The pose.
+    pub pose: nalgebra::Isometry3<f32>,
+}
+
+impl PoseReadout {
+    /// The pose as 4x4 matrix, one row per line.
+    ///
+    /// Example
+    /// ```
+    /// use vviz::common::PoseReadout;
+    ///
+    /// let pose = nalgebra::Isometry3::translation(1.0, 2.0, 3.0);
+    /// assert_eq!(
+    ///     PoseReadout::matrix_text(&pose),
+    ///     "[ 1.000000,  0.000000,  0.000000,  1.000000]\n\
+    ///      [ 0.000000,  1.000000,  0.000000,  2.000000]\n\
+    ///      [ 0.000000,  0.000000,  1.000000,  3.000000]\n\
+    ///      [ 0.000000,  0.000000,  0.000000,  1.000000]"
+    /// );
+    /// assert_eq!(
+    ///     PoseReadout::translation_quaternion_text(&pose),
+    ///     "t: [1.000000, 2.000000, 3.000000]\n\
+    ///      q (w, x, y, z): [1.000000, 0.000000, 0.000000, 0.000000]"
+    /// );
+    /// ```
+    pub fn matrix_text(pose: &nalgebra::Isometry3<f32>) -> String {
+        let matrix = pose.to_homogeneous();
+        matrix
+            .row_iter()
+            .map(|row| {
+                let entries: std::vec::Vec<String> =
+                    row.iter().map(|v| format!("{:9.6}", v)).collect();
+                format!("[{}]", entries.join(", "))
+            })
+            .collect::<std::vec::Vec<_>>()
+            .join("\n")
+    }
+
+    /// The pose as translation and unit quaternion (w, x, y, z), one per line.
+    pub fn translation_quaternion_text(pose: &nalgebra::Isometry3<f32>) -> String {
+        let t = pose.translation.vector;
+        let q = pose.rotation.quaternion();
+        format!(
+            "t: [{:.6}, {:.6}, {:.6}]\nq (w, x, y, z): [{:.6}, {:.6}, {:.6}, {:.6}]",
+            t.x, t.y, t.z, q.w, q.i, q.j, q.k
+        )
+    }
+}
+
+impl Component for PoseReadout {
+    fn show(
+        &mut self,
+        label: &str,
+        ui: &mut egui::Ui,
+        _sender: &mut std::sync::mpsc::Sender<FromGuiLoopMessage>,
+    ) {
+        let matrix = Self::matrix_text(&self.pose);
+        let translation_quaternion = Self::translation_quaternion_text(&self.pose);
+        ui.horizontal(|ui| {
+            ui.label(label);
+            if ui.button("Copy").clicked() {
+                ui.output().copied_text = format!("{}\n{}", matrix, translation_quaternion);
+            }
+        });
+        ui.monospace(matrix);
+        ui.monospace(translation_quaternion);
+    }
+
+    fn snapshot(&self, label: &str) -> Option<ToGuiLoopMessage> {
+        Some(ToGuiLoopMessage::AddPoseReadout(AddPoseReadout {
+            label: label.to_string(),
+            pose: self.pose,
+        }))
+    }
 }
 
 impl<T: Number> Component for Var<T> {
@@ -124,6 +526,10 @@ impl<T: Number> Component for Var<T> {
     ) {
         ui.label(format!("{}: {}", label, self.value));
     }
+
+    fn snapshot(&self, label: &str) -> Option<ToGuiLoopMessage> {
+        Some(self.value.add_var_message(label.to_string()))
+    }
 }
 
 /// A range value, represented as slider.
@@ -147,11 +553,20 @@ impl<T: Number> Component for RangedVar<T> {
             .add(egui::Slider::new(&mut self.value, self.min_max.0..=self.min_max.1).text(label))
             .changed()
         {
-            sender
-                .send(self.value.update_range_value_message(label.to_owned()))
-                .unwrap();
+            let _ = sender.send(self.value.update_range_value_message(label.to_owned()));
         }
     }
+
+    fn snapshot(&self, label: &str) -> Option<ToGuiLoopMessage> {
+        Some(
+            self.value
+                .add_ranged_var_message(label.to_string(), self.min_max),
+        )
+    }
+
+    fn values(&self, label: &str) -> std::vec::Vec<FromGuiLoopMessage> {
+        vec![self.value.update_range_value_message(label.to_string())]
+    }
 }
 
 /// Widget for main panel.
@@ -169,6 +584,13 @@ pub trait Widget: downcast_rs::DowncastSync {
 
     /// The aspect ratio of self.
     fn aspect_ratio(&self) -> f32;
+
+    /// Messages which recreate the widget in its current state, see [gui::GuiSnapshot].
+    ///
+    /// Returns an empty list if the widget cannot be recreated.
+    fn snapshot(&self, _label: &str) -> std::vec::Vec<ToGuiLoopMessage> {
+        std::vec::Vec::new()
+    }
 }
 
 downcast_rs::impl_downcast!(sync Widget);
@@ -178,18 +600,56 @@ mod offscreen_shader {
     pub const VERTEX: &str = r#"#version 100
     attribute vec4 pos;
     attribute vec4 color0;
+    attribute vec3 normal;
     varying lowp vec4 color;
+    varying vec3 normal_scene;
     uniform mat4 mvp;
+    uniform float depth_bias;
+    uniform mat4 normal_matrix;
     void main() {
         gl_Position = mvp * pos;
+        // Offset in normalized device depth, independent of the distance to the camera.
+        gl_Position.z -= depth_bias * gl_Position.w;
         color = color0;
+        normal_scene = (normal_matrix * vec4(normal, 0.0)).xyz;
     }
     "#;
 
     pub const FRAGMENT: &str = r#"#version 100
+    precision mediump float;
     varying lowp vec4 color;
+    varying vec3 normal_scene;
+    uniform float srgb_output;
+    uniform float opacity;
+    uniform float depth_view;
+    uniform float near;
+    uniform float far;
+    uniform float orthographic;
+    uniform vec3 light_direction;
+    uniform float lighting;
+    vec3 linear_to_srgb(vec3 c) {
+        vec3 lo = c * 12.92;
+        vec3 hi = 1.055 * pow(c, vec3(1.0 / 2.4)) - 0.055;
+        return mix(lo, hi, step(vec3(0.0031308), c));
+    }
     void main() {
-        gl_FragColor = color;
+        vec3 shaded = color.rgb;
+        if (lighting > 0.5 && dot(normal_scene, normal_scene) > 0.0) {
+            // Ambient plus Lambert term; back faces are lit as seen from behind.
+            vec3 n = normalize(gl_FrontFacing ? normal_scene : -normal_scene);
+            shaded *= 0.3 + 0.7 * max(dot(n, -light_direction), 0.0);
+        }
+        vec3 rgb = srgb_output > 0.5 ? linear_to_srgb(shaded) : shaded;
+        if (depth_view > 0.5 && orthographic > 0.5) {
+            // The depth of orthographic projections is linear already.
+            rgb = vec3(gl_FragCoord.z);
+        } else if (depth_view > 0.5) {
+            // Linear depth between the near (black) and the far (white) clipping plane.
+            float z_ndc = 2.0 * gl_FragCoord.z - 1.0;
+            float depth = 2.0 * near * far / (far + near - z_ndc * (far - near));
+            rgb = vec3((depth - near) / (far - near));
+        }
+        gl_FragColor = vec4(rgb, color.a * opacity);
     }
     "#;
 
@@ -197,10 +657,19 @@ mod offscreen_shader {
         miniquad::ShaderMeta {
             images: vec![],
             uniforms: miniquad::UniformBlockLayout {
-                uniforms: vec![miniquad::UniformDesc::new(
-                    "mvp",
-                    miniquad::UniformType::Mat4,
-                )],
+                uniforms: vec![
+                    miniquad::UniformDesc::new("mvp", miniquad::UniformType::Mat4),
+                    miniquad::UniformDesc::new("srgb_output", miniquad::UniformType::Float1),
+                    miniquad::UniformDesc::new("depth_bias", miniquad::UniformType::Float1),
+                    miniquad::UniformDesc::new("opacity", miniquad::UniformType::Float1),
+                    miniquad::UniformDesc::new("depth_view", miniquad::UniformType::Float1),
+                    miniquad::UniformDesc::new("near", miniquad::UniformType::Float1),
+                    miniquad::UniformDesc::new("far", miniquad::UniformType::Float1),
+                    miniquad::UniformDesc::new("orthographic", miniquad::UniformType::Float1),
+                    miniquad::UniformDesc::new("normal_matrix", miniquad::UniformType::Mat4),
+                    miniquad::UniformDesc::new("light_direction", miniquad::UniformType::Float3),
+                    miniquad::UniformDesc::new("lighting", miniquad::UniformType::Float1),
+                ],
             },
         }
     }
@@ -208,26 +677,54 @@ mod offscreen_shader {
     #[repr(C)]
     pub struct Uniforms {
         pub mvp: nalgebra::Matrix4<f32>,
+        /// 1.0 to encode the (linear) output color as sRGB, 0.0 to write it unchanged.
+        pub srgb_output: f32,
+        /// Offset in normalized device depth; positive values move geometry towards the camera.
+        pub depth_bias: f32,
+        /// Factor applied to the alpha of the vertex colors.
+        pub opacity: f32,
+        /// 1.0 to output the linear depth as gray, 0.0 for the vertex colors.
+        pub depth_view: f32,
+        /// Near clipping plane distance, for the depth view.
+        pub near: f32,
+        /// Far clipping plane distance, for the depth view.
+        pub far: f32,
+        /// 1.0 for an orthographic projection, 0.0 for a perspective one; for the depth view.
+        pub orthographic: f32,
+        /// Maps entity normals to the scene, i.e. the inverse transpose of the entity transform.
+        pub normal_matrix: nalgebra::Matrix4<f32>,
+        /// Unit direction of the light in the scene, i.e. the direction the light travels.
+        pub light_direction: nalgebra::Vector3<f32>,
+        /// 1.0 to shade by the light, 0.0 for the plain vertex colors.
+        pub lighting: f32,
     }
 }
 
 /// [Widget] for 2d content.
 pub struct Widget2 {
     aspect_ratio: f32,
+    image_size: (u32, u32),
     maybe_image: Option<miniquad::Texture>,
+    /// Entities by label, including overlays placed by [PlaceOverlay2]; drawn in insertion order.
+    entities: linked_hash_map::LinkedHashMap<String, entities::Entity2>,
+    /// Image which still needs to be uploaded to the GPU in [Widget::render].
+    pending_image: Option<ImageRgba8>,
+    /// Size in physical screen pixels, if not fitted to the assigned space; see [SetFixedSize2].
+    fixed_size: Option<(u32, u32)>,
+    /// Id of the scroll area in case of a fixed size; unique per widget.
+    scroll_area_id: egui::Id,
 }
 
 impl Widget2 {
-    fn from_image(ctx: &mut miniquad::Context, rgba8: ImageRgba8) -> Self {
-        let tex = miniquad::Texture::from_rgba8(
-            ctx,
-            rgba8.width as u16,
-            rgba8.height as u16,
-            rgba8.bytes.as_slice(),
-        );
+    fn from_image(label: &str, rgba8: ImageRgba8) -> Self {
         Self {
             aspect_ratio: rgba8.width as f32 / rgba8.height as f32,
-            maybe_image: Some(tex),
+            image_size: (rgba8.width, rgba8.height),
+            maybe_image: None,
+            entities: linked_hash_map::LinkedHashMap::new(),
+            pending_image: Some(rgba8),
+            fixed_size: None,
+            scroll_area_id: egui::Id::new(("widget2", label)),
         }
     }
 
@@ -241,10 +738,94 @@ impl Widget2 {
     // fn from_image_size(width: f32, height: f32) -> Self {
     //     Self::from_aspect_ratio(width / height)
     // }
+
+    /// Shows the image and its entities, letterboxed into an area of given `size`.
+    fn show_sized(&mut self, ui: &mut egui::Ui, size: egui::Vec2) -> egui::Response {
+        let r = ui.allocate_response(size, egui::Sense::click_and_drag());
+        let image_rect = gui::image_rect(&r, self.aspect_ratio);
+        if let Some(image) = self.maybe_image {
+            let tex = egui::TextureId::User(image.gl_internal_id() as u64);
+            egui::Image::new(tex, image_rect.size()).paint_at(ui, image_rect);
+        }
+
+        let painter = ui.painter_at(image_rect);
+        let scale = egui::Vec2::new(
+            image_rect.width() / self.image_size.0 as f32,
+            image_rect.height() / self.image_size.1 as f32,
+        );
+        let to_screen =
+            |p: &nalgebra::Vector2<f32>| image_rect.min + egui::Vec2::new(p.x, p.y) * scale;
+        for (_, entity) in &self.entities {
+            match entity {
+                entities::Entity2::Points(points) => {
+                    for point in &points.points {
+                        painter.circle_filled(to_screen(point), points.radius, points.color);
+                    }
+                }
+                entities::Entity2::Polyline(polyline) => {
+                    let points = polyline.points.iter().map(to_screen).collect();
+                    let stroke = egui::Stroke::new(polyline.width, polyline.color);
+                    painter.add(if polyline.closed {
+                        egui::Shape::closed_line(points, stroke)
+                    } else {
+                        egui::Shape::line(points, stroke)
+                    });
+                }
+                entities::Entity2::LineSegments(segments) => {
+                    for segment in &segments.segments {
+                        painter.line_segment(
+                            [to_screen(&segment.start), to_screen(&segment.end)],
+                            egui::Stroke::new(1.5, segment.color),
+                        );
+                    }
+                }
+                entities::Entity2::Rectangle(rectangle) => {
+                    let rect = egui::Rect::from_min_max(
+                        to_screen(&rectangle.min),
+                        to_screen(&rectangle.max),
+                    );
+                    match rectangle.width {
+                        Some(width) => painter.rect_stroke(
+                            rect,
+                            0.0,
+                            egui::Stroke::new(width, rectangle.color),
+                        ),
+                        None => painter.rect_filled(rect, 0.0, rectangle.color),
+                    }
+                }
+                entities::Entity2::Text(text) => {
+                    painter.text(
+                        to_screen(&text.position),
+                        egui::Align2::LEFT_TOP,
+                        &text.text,
+                        Widget3::text_style(ui, text.size),
+                        text.color.into(),
+                    );
+                }
+            }
+        }
+        r
+    }
 }
 
 impl Widget for Widget2 {
-    fn render(&mut self, _ctx: &mut miniquad::Context) {}
+    fn render(&mut self, ctx: &mut miniquad::Context) {
+        if let Some(rgba8) = self.pending_image.take() {
+            match self.maybe_image {
+                // Images of a widget all have the same size, see [TryUpdateImage].
+                Some(tex) => tex.update(ctx, rgba8.bytes.as_slice()),
+                None => {
+                    let tex = miniquad::Texture::from_rgba8(
+                        ctx,
+                        rgba8.width as u16,
+                        rgba8.height as u16,
+                        rgba8.bytes.as_slice(),
+                    );
+                    self.maybe_image = Some(tex);
+                }
+            }
+        }
+    }
 
     fn show(
         &mut self,
@@ -252,14 +833,21 @@ impl Widget for Widget2 {
         assigned_width: f32,
         assigned_height: f32,
     ) -> Option<egui::Response> {
-        let w = (self.aspect_ratio * assigned_height).min(assigned_width);
-        let h = w / self.aspect_ratio;
-
-        let tex = egui::TextureId::User(self.maybe_image.unwrap().gl_internal_id() as u64);
-
-        let r = ui
-            .add(egui::Image::new(tex, egui::Vec2::new(w, h)).sense(egui::Sense::click_and_drag()));
-        Some(r)
+        let assigned_size = egui::Vec2::new(assigned_width, assigned_height);
+        let (width, height) = match self.fixed_size {
+            Some(fixed_size) => fixed_size,
+            None => return Some(self.show_sized(ui, assigned_size)),
+        };
+        // The fixed size is in physical pixels, which is what makes 1:1 display pixel-exact.
+        let size = egui::Vec2::new(width as f32, height as f32) / ui.ctx().pixels_per_point();
+        Some(
+            egui::ScrollArea::both()
+                .id_source(self.scroll_area_id)
+                .max_width(assigned_width)
+                .max_height(assigned_height)
+                .auto_shrink([false; 2])
+                .show(ui, |ui| self.show_sized(ui, size)),
+        )
     }
 
     fn aspect_ratio(&self) -> f32 {
@@ -267,624 +855,4296 @@ impl Widget for Widget2 {
     }
 }
 
-/// [Widget] for 3d content such as meshes, line segments and point clouds.
-pub struct Widget3 {
-    camera_pose_scene: nalgebra::Isometry3<f32>,
-    entities: linked_hash_map::LinkedHashMap<String, entities::NamedEntity3>,
-    mesh_pipeline: miniquad::Pipeline,
-    segments_pipeline: miniquad::Pipeline,
-    offscreen_pass: miniquad::RenderPass,
-    aspect_ratio: f32,
-    texture_id: Option<egui::TextureId>,
+/// Face culling mode of a [Widget3] or of a single [super::entities::NamedEntity3].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CullMode {
+    /// No culling, i.e. double-sided rendering.
+    #[default]
+    Nothing,
+    /// Cull front faces.
+    Front,
+    /// Cull back faces.
+    Back,
 }
 
-impl Widget3 {
-    fn new(ctx: &mut miniquad::Context) -> Self {
-        let color_img = miniquad::Texture::new_render_texture(
-            ctx,
-            miniquad::TextureParams {
-                width: 640,
-                height: 480,
-                format: miniquad::TextureFormat::RGBA8,
-                ..Default::default()
-            },
-        );
-        let depth_img = miniquad::Texture::new_render_texture(
-            ctx,
-            miniquad::TextureParams {
-                width: 640,
-                height: 480,
-                format: miniquad::TextureFormat::Depth,
-                ..Default::default()
-            },
-        );
-
-        let offscreen_pass = miniquad::RenderPass::new(ctx, color_img, depth_img);
-
-        let offscreen_shader = miniquad::Shader::new(
-            ctx,
-            offscreen_shader::VERTEX,
-            offscreen_shader::FRAGMENT,
-            offscreen_shader::meta(),
-        )
-        .unwrap();
+impl From<CullMode> for miniquad::CullFace {
+    fn from(mode: CullMode) -> miniquad::CullFace {
+        match mode {
+            CullMode::Nothing => miniquad::CullFace::Nothing,
+            CullMode::Front => miniquad::CullFace::Front,
+            CullMode::Back => miniquad::CullFace::Back,
+        }
+    }
+}
 
-        let mesh_pipeline = miniquad::Pipeline::with_params(
-            ctx,
-            &[miniquad::BufferLayout {
-                stride: (3 + 4) * std::mem::size_of::<f32>() as i32,
-                ..Default::default()
-            }],
-            &[
-                miniquad::VertexAttribute::new("pos", miniquad::VertexFormat::Float3),
-                miniquad::VertexAttribute::new("color0", miniquad::VertexFormat::Float4),
-            ],
-            offscreen_shader,
-            miniquad::PipelineParams {
-                depth_test: miniquad::Comparison::LessOrEqual,
-                depth_write: true,
-                ..Default::default()
-            },
-        );
+/// Color theme of the gui.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Theme {
+    /// Light panels, white 3d background.
+    Light,
+    /// Dark panels, dark 3d background.
+    Dark,
+}
 
-        let segments_pipeline = miniquad::Pipeline::with_params(
-            ctx,
-            &[miniquad::BufferLayout {
-                stride: (3 + 4) * std::mem::size_of::<f32>() as i32,
-                ..Default::default()
-            }],
-            &[
-                miniquad::VertexAttribute::new("pos", miniquad::VertexFormat::Float3),
-                miniquad::VertexAttribute::new("color0", miniquad::VertexFormat::Float4),
-            ],
-            offscreen_shader,
-            miniquad::PipelineParams {
-                depth_test: miniquad::Comparison::LessOrEqual,
-                depth_write: true,
-                primitive_type: miniquad::PrimitiveType::Lines,
-                ..Default::default()
-            },
-        );
+impl Theme {
+    /// The egui visuals of the theme.
+    pub fn visuals(&self) -> egui::Visuals {
+        match self {
+            Theme::Light => egui::Visuals::light(),
+            Theme::Dark => egui::Visuals::dark(),
+        }
+    }
 
-        Self {
-            camera_pose_scene: nalgebra::Isometry3::<f32>::from_parts(
-                nalgebra::Translation3::<f32>::new(0.0, 0.0, -4.0),
-                nalgebra::UnitQuaternion::<f32>::from_euler_angles(0.0, 0.0, 0.),
-            ),
-            entities: linked_hash_map::LinkedHashMap::new(),
-            mesh_pipeline,
-            segments_pipeline,
-            offscreen_pass,
-            aspect_ratio: 640.0 / 480.0,
-            texture_id: None,
+    /// The background color of [Widget3]s; defaults to white without a theme.
+    fn background_color(theme: Option<Theme>) -> (f32, f32, f32, f32) {
+        match theme {
+            None | Some(Theme::Light) => (1.0, 1.0, 1.0, 1.0),
+            Some(Theme::Dark) => (0.1, 0.1, 0.1, 1.0),
         }
     }
 }
 
-impl Widget for Widget3 {
-    fn render(&mut self, ctx: &mut miniquad::Context) {
-        let proj = nalgebra_glm::perspective_fov_rh(60.0f32.to_radians(), 640.0, 480.0, 0.01, 10.0);
+/// Ground grid of a [Widget3], in the scene's xz-plane (i.e. normal to the up axis y) and
+/// centered at the origin.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct WorldGrid {
+    /// Side length of the square grid.
+    pub size: f32,
+    /// Number of cells along each side; at most [WorldGrid::MAX_DIVISIONS].
+    pub divisions: u32,
+    /// Line color.
+    pub color: entities::Color,
+}
 
-        // the offscreen render pipeline, following this example:
-        // https://github.com/not-fl3/egui-miniquad/blob/master/examples/render_to_egui_image.rs
-        ctx.begin_pass(
-            self.offscreen_pass,
-            miniquad::PassAction::clear_color(1.0, 1.0, 1.0, 1.),
-        );
-        for (_, named_entity) in &self.entities {
-            match &named_entity.entity {
-                entities::Entity3::Mesh(mesh) => {
-                    let vertex_buffer = miniquad::Buffer::immutable(
-                        ctx,
-                        miniquad::BufferType::VertexBuffer,
-                        mesh.vertices.as_position_color().unwrap().vertices.flat(),
-                    );
+impl Default for WorldGrid {
+    fn default() -> Self {
+        Self {
+            size: 10.0,
+            divisions: 10,
+            color: entities::Color {
+                r: 0.6,
+                g: 0.6,
+                b: 0.6,
+                alpha: 1.0,
+            },
+        }
+    }
+}
 
-                    let index_buffer = miniquad::Buffer::immutable(
-                        ctx,
-                        miniquad::BufferType::IndexBuffer,
-                        mesh.faces.indices.flat(),
-                    );
+impl WorldGrid {
+    /// Largest number of divisions, such that all grid lines fit into a single
+    /// [entities::LineSegments3].
+    pub const MAX_DIVISIONS: u32 = (entities::LineSegments3::MAX_VERTICES / 4 - 1) as u32;
 
-                    let offscreen_bind = miniquad::Bindings {
-                        vertex_buffers: vec![vertex_buffer],
-                        index_buffer,
-                        images: vec![],
-                    };
+    /// Returns the grid with its divisions clamped to [WorldGrid::MAX_DIVISIONS].
+    ///
+    /// Example
+    /// ```
+    /// use vviz::common::WorldGrid;
+    ///
+    /// let grid = WorldGrid {
+    ///     divisions: u32::MAX,
+    ///     ..WorldGrid::default()
+    /// };
+    /// assert_eq!(grid.clamped().divisions, WorldGrid::MAX_DIVISIONS);
+    /// assert_eq!(WorldGrid::default().clamped(), WorldGrid::default());
+    /// ```
+    pub fn clamped(self) -> WorldGrid {
+        WorldGrid {
+            divisions: self.divisions.min(Self::MAX_DIVISIONS),
+            ..self
+        }
+    }
 
-                    ctx.apply_pipeline(&self.mesh_pipeline);
-                    ctx.apply_bindings(&offscreen_bind);
+    /// Lines of the grid; shall be called on a clamped grid, see [WorldGrid::clamped].
+    fn line_segments(&self) -> entities::LineSegments3 {
+        let mut vertices = entities::PositionColorVertices::default();
+        let mut indices = std::vec::Vec::new();
+        let half = 0.5 * self.size;
+        for i in 0..=self.divisions {
+            let t = -half + self.size * i as f32 / self.divisions.max(1) as f32;
+            for (start, end) in [
+                (
+                    nalgebra::Vector3::new(t, 0.0, -half),
+                    nalgebra::Vector3::new(t, 0.0, half),
+                ),
+                (
+                    nalgebra::Vector3::new(-half, 0.0, t),
+                    nalgebra::Vector3::new(half, 0.0, t),
+                ),
+            ] {
+                let idx = vertices.vertices.len() as i16;
+                vertices
+                    .vertices
+                    .push(entities::PositionColorVertices::to_array(start, self.color));
+                vertices
+                    .vertices
+                    .push(entities::PositionColorVertices::to_array(end, self.color));
+                indices.push([idx, idx + 1]);
+            }
+        }
+        entities::LineSegments3 { vertices, indices }
+    }
+}
 
-                    let vs_params = offscreen_shader::Uniforms {
-                        mvp: proj
-                            * self.camera_pose_scene.to_matrix()
-                            * named_entity.scene_pose_entity.to_matrix(),
-                    };
-                    ctx.apply_uniforms(&vs_params);
+/// How mouse drags rotate the scene of a [Widget3].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RotationMode {
+    /// Free rotation about the camera's axes, which might introduce roll.
+    #[default]
+    Trackball,
+    /// Azimuth about the scene's up axis (y) and elevation only; never introduces roll.
+    Turntable,
+}
 
-                    ctx.draw(0, mesh.faces.indices.flat().len() as i32, 1);
-                }
-                entities::Entity3::LineSegments(segments) => {
-                    let vertex_buffer = miniquad::Buffer::immutable(
-                        ctx,
-                        miniquad::BufferType::VertexBuffer,
-                        segments.vertices.vertices.flat(),
-                    );
+/// Projection of the camera of a [Widget3].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum ProjectionMode {
+    /// Perspective projection, with the vertical field of view in degrees.
+    Perspective {
+        /// Vertical field of view in degrees; must be in (0, 180).
+        fov_deg: f32,
+    },
+    /// Orthographic projection, e.g. for CAD-like geometry and top-down maps. Objects keep their
+    /// size regardless of their distance to the camera.
+    Orthographic {
+        /// Vertical extent of the view in scene units; must be positive.
+        height: f32,
+    },
+}
 
-                    let index_buffer = miniquad::Buffer::immutable(
-                        ctx,
-                        miniquad::BufferType::IndexBuffer,
-                        segments.indices.flat(),
-                    );
+impl Default for ProjectionMode {
+    fn default() -> Self {
+        ProjectionMode::Perspective { fov_deg: 60.0 }
+    }
+}
 
-                    let offscreen_bind = miniquad::Bindings {
-                        vertex_buffers: vec![vertex_buffer],
-                        index_buffer,
-                        images: vec![],
-                    };
+/// Render mode of a [Widget3], e.g. to diagnose z-fighting or geometry issues.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DebugMode {
+    /// Regular rendering with the vertex colors.
+    #[default]
+    Normal,
+    /// Linear depth as grayscale, from black at the near to white at the far clipping plane.
+    Depth,
+    /// Meshes colored by their vertex normals, see [super::entities::normals_as_color].
+    NormalsAsColor,
+    /// Meshes drawn as their edges, see [super::entities::wireframe].
+    Wireframe,
+}
 
-                    ctx.apply_pipeline(&self.segments_pipeline);
-                    ctx.apply_bindings(&offscreen_bind);
+/// Width of rendered [super::entities::LineSegments3].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum LineWidth {
+    /// GL lines, which are one pixel wide on most platforms.
+    #[default]
+    Hairline,
+    /// Camera-facing quads of given width in scene units.
+    World(f32),
+    /// Camera-facing quads of given width in pixels.
+    Pixels(f32),
+}
 
-                    let vs_params = offscreen_shader::Uniforms {
-                        mvp: proj
-                            * self.camera_pose_scene.to_matrix()
-                            * named_entity.scene_pose_entity.to_matrix(),
-                    };
-                    ctx.apply_uniforms(&vs_params);
+impl LineWidth {
+    /// Largest supported width in pixels.
+    pub const MAX_PIXELS: f32 = 64.0;
 
-                    ctx.draw(0, segments.indices.flat().len() as i32, 1);
-                }
+    /// Returns the width clamped to the supported range: pixel widths are capped at
+    /// [LineWidth::MAX_PIXELS], and widths which are not positive - or in pixels at most one -
+    /// fall back to [LineWidth::Hairline].
+    ///
+    /// Example
+    /// ```
+    /// use vviz::common::LineWidth;
+    ///
+    /// assert_eq!(LineWidth::Pixels(3.0).clamped(), LineWidth::Pixels(3.0));
+    /// assert_eq!(LineWidth::Pixels(1000.0).clamped(), LineWidth::Pixels(LineWidth::MAX_PIXELS));
+    /// assert_eq!(LineWidth::Pixels(0.5).clamped(), LineWidth::Hairline);
+    /// assert_eq!(LineWidth::World(f32::NAN).clamped(), LineWidth::Hairline);
+    /// ```
+    pub fn clamped(self) -> LineWidth {
+        match self {
+            LineWidth::Pixels(width) if width > 1.0 => {
+                LineWidth::Pixels(width.min(LineWidth::MAX_PIXELS))
             }
+            LineWidth::World(width) if width > 0.0 && width.is_finite() => self,
+            _ => LineWidth::Hairline,
         }
-        ctx.end_render_pass();
+    }
+}
 
-        // Extract texture from offscreen render pass
-        let mq_texture = self.offscreen_pass.texture(ctx);
-        //print!("{} {}", mq_texture.width, mq_texture.height);
+/// Expands each line segment into a camera-facing quad (two triangles), in the camera frame.
+///
+/// `half_width_at` returns the half width of the quad given the depth of a segment endpoint. The
+/// quads face the camera center, or - if `orthographic` - the viewing direction.
+fn thick_line_triangles(
+    segments: &entities::LineSegments3,
+    camera_from_entity: &nalgebra::Matrix4<f32>,
+    orthographic: bool,
+    half_width_at: impl Fn(f32) -> Option<f32>,
+) -> (entities::PositionColorVertices, std::vec::Vec<[i16; 3]>) {
+    let mut vertices = entities::PositionColorVertices::default();
+    let mut faces = std::vec::Vec::with_capacity(2 * segments.indices.len());
+    let vertex = |i: i16| {
+        let v = segments.vertices.vertices[i as usize];
+        let p = camera_from_entity.transform_point(&nalgebra::Point3::new(v[0], v[1], v[2]));
+        (p.coords, v)
+    };
+    for [a, b] in &segments.indices {
+        let (pa, va) = vertex(*a);
+        let (pb, vb) = vertex(*b);
+        let direction = pb - pa;
+        for (p, v) in [(pa, va), (pb, vb)] {
+            // The camera is at the origin, hence `p` is the viewing ray in perspective projections.
+            let ray = if orthographic {
+                -nalgebra::Vector3::z()
+            } else {
+                p
+            };
+            let side = direction
+                .cross(&ray)
+                .try_normalize(f32::EPSILON)
+                .unwrap_or_else(nalgebra::Vector3::zeros)
+                * half_width_at(-p.z).unwrap_or(0.0);
+            for q in [p - side, p + side] {
+                vertices
+                    .vertices
+                    .push([q.x, q.y, q.z, v[3], v[4], v[5], v[6]]);
+            }
+        }
+        let i: i16 = (vertices.vertices.len() - 4).try_into().unwrap();
+        faces.push([i, i + 1, i + 3]);
+        faces.push([i, i + 3, i + 2]);
+    }
+    (vertices, faces)
+}
 
-        // create egui TextureId from Miniquad GL texture Id
-        self.texture_id = Some(egui::TextureId::User(mq_texture.gl_internal_id() as u64));
+/// [Widget] for 3d content such as meshes, line segments and point clouds.
+///
+/// All GPU resources are created lazily in [Widget::render], so that the widget state can be
+/// manipulated without a graphics context.
+///
+/// Color convention: as for 2d widgets and the background, entity colors are considered sRGB and
+/// are written to the render target unchanged by default. With sRGB output enabled, entity colors
+/// are considered linear instead and are encoded as sRGB when written, while the background color
+/// is still written unchanged.
+pub struct Widget3 {
+    orbit_controller: camera::OrbitController,
+    entities: linked_hash_map::LinkedHashMap<String, entities::NamedEntity3>,
+    cull_mode: CullMode,
+    frustum_culling: bool,
+    srgb_output: bool,
+    normals_length: Option<f32>,
+    line_width: LineWidth,
+    world_grid: Option<entities::LineSegments3>,
+    world_axes: Option<entities::LineSegments3>,
+    background_color: (f32, f32, f32, f32),
+    /// Whether meshes are shaded by a directional light, see [SetLighting].
+    lighting: bool,
+    /// Unit direction the light travels, in scene coordinates.
+    light_direction: nalgebra::Vector3<f32>,
+    /// Background color set for this widget, which overrides the one of the [Theme].
+    custom_background_color: Option<entities::Color>,
+    entity_bounds: std::collections::HashMap<String, Option<entities::Aabb3>>,
+    normal_lines: std::collections::HashMap<String, entities::LineSegments3>,
+    debug_mode: DebugMode,
+    /// Meshes converted for the debug mode, by entity label.
+    debug_entities: std::collections::HashMap<String, entities::Entity3>,
+    /// GPU buffers by entity label, uploaded when the entity is drawn first. They are only
+    /// replaced when the geometry changes, not when the entity is moved. None for text labels.
+    entity_buffers: std::collections::HashMap<String, Option<GpuBuffers>>,
+    /// Quads of line segment entities drawn as thick lines, by entity label. They are only built
+    /// again when the view changes, e.g. when the camera moves.
+    thick_line_buffers: std::collections::HashMap<String, ThickLineBuffers>,
+    /// Buffers of changed or removed entities, which are deleted at the next render.
+    stale_buffers: std::vec::Vec<GpuBuffers>,
+    /// Labels of entities whose colors changed, and hence whose vertices are uploaded again when
+    /// drawn next.
+    recolored_entities: std::collections::HashSet<String>,
+    /// Animations by entity label. The mutex only makes the widget [Sync]; it is never contended.
+    animations:
+        std::collections::HashMap<String, (std::time::Instant, std::sync::Mutex<Animation>)>,
+    /// Labels of hidden entities; kept separately so that hiding survives placing an entity again.
+    hidden_entities: std::collections::HashSet<String>,
+    /// Labels of entities drawn on top of all others; kept separately for the same reason.
+    on_top_entities: std::collections::HashSet<String>,
+    /// Opacity by entity label, if not 1; kept separately for the same reason.
+    entity_opacities: std::collections::HashMap<String, f32>,
+    groups: std::collections::HashMap<String, EntityGroup>,
+    gpu: Option<Widget3Gpu>,
+    requested_offscreen_size: (u32, u32),
+    aspect_ratio: f32,
+    projection_mode: ProjectionMode,
+    clipping: (f32, f32),
+    /// Pose of the second camera in split view, if enabled.
+    split_camera_pose_scene: Option<nalgebra::Isometry3<f32>>,
+    /// Hovered position, relative to the widget in [0..1]; x to the right and y downwards.
+    hover_position: Option<egui::Vec2>,
+    hovered_point: Option<nalgebra::Vector3<f32>>,
+    /// Hovered point which was last reported to the manager.
+    reported_hovered_point: Option<nalgebra::Vector3<f32>>,
+    /// Label of the entity whose bounding box contains the hovered point.
+    hovered_entity: Option<String>,
+    /// Hovered entity which was last reported to the manager.
+    reported_hovered_entity: Option<String>,
+    /// Camera pose which was last reported to the manager.
+    reported_camera_pose_scene: Option<nalgebra::Isometry3<f32>>,
+    /// Whether the bounding box of the hovered entity is outlined.
+    hover_highlight: bool,
+    measure_mode: bool,
+    /// First point of a measurement in progress.
+    measure_start: Option<nalgebra::Vector3<f32>>,
+    /// Start and end point of the last measurement, which is shown.
+    measurement: Option<(nalgebra::Vector3<f32>, nalgebra::Vector3<f32>)>,
+    unreported_measurement: Option<f32>,
+    /// Visible text labels of the last rendered frame, at their position relative to the widget.
+    text_placements: std::vec::Vec<(egui::Pos2, entities::TextLabel3)>,
+    texture_id: Option<egui::TextureId>,
+}
 
-        ctx.clear(Some((1., 1., 1., 1.)), None, None);
-        ctx.begin_default_pass(miniquad::PassAction::clear_color(0.3, 0.3, 0.3, 1.0));
-        ctx.end_render_pass();
+/// Named set of entities of a [Widget3], which are shown/hidden and moved together.
+///
+/// Groups are one level deep, i.e. they cannot be nested, and an entity belongs to at most one
+/// group.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct EntityGroup {
+    /// Labels of the member entities.
+    pub members: std::collections::HashSet<String>,
+    /// Pose of the group in the scene, applied as parent transform of its members, i.e. a member
+    /// is drawn at `scene_pose_group * scene_pose_entity`.
+    pub scene_pose_group: nalgebra::Isometry3<f32>,
+    /// Whether the members are shown.
+    pub visible: bool,
+}
+
+impl Default for EntityGroup {
+    fn default() -> Self {
+        Self {
+            members: std::collections::HashSet::new(),
+            scene_pose_group: nalgebra::Isometry3::identity(),
+            visible: true,
+        }
     }
+}
 
-    fn show(
-        &mut self,
-        ui: &mut egui::Ui,
-        assigned_width: f32,
-        assigned_height: f32,
-    ) -> Option<egui::Response> {
-        let w = (self.aspect_ratio * assigned_height).min(assigned_width);
-        let h = w / self.aspect_ratio;
+/// Per draw call parameters of the offscreen shader, besides the transformation.
+#[derive(Clone, Copy)]
+struct DrawParams {
+    srgb_output: f32,
+    depth_bias: f32,
+    opacity: f32,
+    depth_view: f32,
+    clipping: (f32, f32),
+    orthographic: f32,
+    lighting: f32,
+    light_direction: nalgebra::Vector3<f32>,
+}
 
-        let r = ui.add(
-            egui::Image::new(self.texture_id.unwrap(), egui::Vec2::new(w, h))
-                .sense(egui::Sense::click_and_drag()),
-        );
+/// View for which the quads of thick line segments were built, see [Widget3Gpu::draw_line_segments].
+#[derive(Clone, Copy, PartialEq)]
+struct ThickLineView {
+    proj: nalgebra::Matrix4<f32>,
+    camera_from_entity: nalgebra::Matrix4<f32>,
+    line_width: LineWidth,
+    offscreen_height: u32,
+}
 
-        if ui.ctx().input().pointer.secondary_down() {
-            // TODO: Calculate delta scale based on scene depth.
-            let delta = 0.01 * ui.ctx().input().pointer.delta();
-            let mut scene_pose_camera = self.camera_pose_scene.inverse();
-            let translation_update = scene_pose_camera
-                .rotation
-                .transform_vector(&nalgebra::Vector3::new(-delta.x, -delta.y, 0.0));
-            scene_pose_camera.append_translation_mut(&nalgebra::Translation3 {
-                vector: translation_update,
-            });
-            self.camera_pose_scene = scene_pose_camera.inverse();
-        } else if ui.ctx().input().pointer.primary_down() {
-            // TODO: Rotates about scene center. Make the center point of rotation configurable.
-            let delta = 0.01 * ui.ctx().input().pointer.delta();
-            let mut scaled_axis = nalgebra::Vector3::zeros();
-            scaled_axis.x = -delta.y;
-            scaled_axis.y = delta.x;
+/// Quads of thick line segments on the GPU, and the view they were built for.
+type ThickLineBuffers = Option<(ThickLineView, GpuBuffers)>;
 
-            let scene_rot_camera = self.camera_pose_scene.rotation.inverse();
+/// Vertex and index buffer on the GPU, e.g. of an entity; kept until its geometry changes.
+///
+/// Meshes have a second vertex buffer with the vertex normals, which the mesh pipeline requires.
+struct GpuBuffers {
+    bindings: miniquad::Bindings,
+    num_indices: i32,
+}
 
-            self.camera_pose_scene.rotation *= nalgebra::UnitQuaternion::from_scaled_axis(
-                scene_rot_camera.transform_vector(&scaled_axis),
-            );
+impl GpuBuffers {
+    fn new(
+        ctx: &mut miniquad::Context,
+        vertices: &entities::PositionColorVertices,
+        normals: Option<&[nalgebra::Vector3<f32>]>,
+        indices: &[i16],
+    ) -> Self {
+        let mut vertex_buffers = vec![miniquad::Buffer::immutable(
+            ctx,
+            miniquad::BufferType::VertexBuffer,
+            vertices.vertices.flat(),
+        )];
+        if let Some(normals) = normals {
+            vertex_buffers.push(miniquad::Buffer::immutable(
+                ctx,
+                miniquad::BufferType::VertexBuffer,
+                normals,
+            ));
         }
+        let index_buffer =
+            miniquad::Buffer::immutable(ctx, miniquad::BufferType::IndexBuffer, indices);
+        Self {
+            bindings: miniquad::Bindings {
+                vertex_buffers,
+                index_buffer,
+                images: vec![],
+            },
+            num_indices: indices.len() as i32,
+        }
+    }
 
-        Some(r)
+    /// Buffers of a mesh or line segments entity; None for text labels.
+    fn from_entity(ctx: &mut miniquad::Context, entity: &entities::Entity3) -> Option<Self> {
+        match entity {
+            entities::Entity3::Mesh(mesh) => Some(Self::new(
+                ctx,
+                mesh.vertices.as_position_color().unwrap(),
+                Some(&mesh.vertex_normals()),
+                mesh.faces.indices.flat(),
+            )),
+            entities::Entity3::LineSegments(segments) => Some(Self::new(
+                ctx,
+                &segments.vertices,
+                None,
+                segments.indices.flat(),
+            )),
+            entities::Entity3::Text(_) => None,
+        }
     }
 
-    fn aspect_ratio(&self) -> f32 {
-        self.aspect_ratio
+    /// Uploads the positions and colors again, e.g. after the colors changed; normals and indices
+    /// are kept.
+    fn update_vertices(
+        &mut self,
+        ctx: &mut miniquad::Context,
+        vertices: &entities::PositionColorVertices,
+    ) {
+        let vertex_buffer = miniquad::Buffer::immutable(
+            ctx,
+            miniquad::BufferType::VertexBuffer,
+            vertices.vertices.flat(),
+        );
+        std::mem::replace(&mut self.bindings.vertex_buffers[0], vertex_buffer).delete();
+    }
+
+    fn delete(&self) {
+        for vertex_buffer in &self.bindings.vertex_buffers {
+            vertex_buffer.delete();
+        }
+        self.bindings.index_buffer.delete();
     }
 }
 
-/// Integer or floating point number.
+/// GPU resources of a [Widget3].
+struct Widget3Gpu {
+    mesh_pipeline: miniquad::Pipeline,
+    segments_pipeline: miniquad::Pipeline,
+    offscreen_pass: miniquad::RenderPass,
+    offscreen_size: (u32, u32),
+}
 
-pub trait Number:
-    egui::emath::Numeric + downcast_rs::DowncastSync + std::fmt::Display + serde::Serialize
-{
-    /// AddVar message.
-    fn add_var_message(self, label: String) -> ToGuiLoopMessage;
+impl Widget3Gpu {
+    /// The offscreen render targets are only recreated if the on-screen size changed by more than
+    /// this many pixels, so that tiny layout jitter does not trigger reallocations.
+    const OFFSCREEN_RESIZE_THRESHOLD: u32 = 8;
 
-    /// AddRangedVar message.
-    fn add_ranged_var_message(self, label: String, min_max: (Self, Self)) -> ToGuiLoopMessage;
+    fn new_offscreen_pass(
+        ctx: &mut miniquad::Context,
+        (width, height): (u32, u32),
+    ) -> miniquad::RenderPass {
+        let color_img = miniquad::Texture::new_render_texture(
+            ctx,
+            miniquad::TextureParams {
+                width,
+                height,
+                format: miniquad::TextureFormat::RGBA8,
+                ..Default::default()
+            },
+        );
+        let depth_img = miniquad::Texture::new_render_texture(
+            ctx,
+            miniquad::TextureParams {
+                width,
+                height,
+                format: miniquad::TextureFormat::Depth,
+                ..Default::default()
+            },
+        );
+        miniquad::RenderPass::new(ctx, color_img, depth_img)
+    }
 
-    /// UpdateRangedValue message
-    fn update_range_value_message(self, label: String) -> FromGuiLoopMessage;
-}
+    fn new(ctx: &mut miniquad::Context, offscreen_size: (u32, u32)) -> Self {
+        let offscreen_pass = Self::new_offscreen_pass(ctx, offscreen_size);
 
-impl Number for usize {
-    fn add_var_message(self, label: String) -> ToGuiLoopMessage {
-        ToGuiLoopMessage::AddVarUSize(AddVar::<usize> { label, value: self })
-    }
+        let offscreen_shader = miniquad::Shader::new(
+            ctx,
+            offscreen_shader::VERTEX,
+            offscreen_shader::FRAGMENT,
+            offscreen_shader::meta(),
+        )
+        .unwrap();
 
-    fn add_ranged_var_message(self, label: String, min_max: (usize, usize)) -> ToGuiLoopMessage {
-        ToGuiLoopMessage::AddRangedVarUSize(AddRangedVar::<usize> {
-            label,
-            min_max,
-            value: self,
-        })
-    }
+        // Usual alpha blending, while keeping the render target opaque.
+        let color_blend = Some(miniquad::BlendState::new(
+            miniquad::Equation::Add,
+            miniquad::BlendFactor::Value(miniquad::BlendValue::SourceAlpha),
+            miniquad::BlendFactor::OneMinusValue(miniquad::BlendValue::SourceAlpha),
+        ));
+        let alpha_blend = Some(miniquad::BlendState::new(
+            miniquad::Equation::Add,
+            miniquad::BlendFactor::One,
+            miniquad::BlendFactor::OneMinusValue(miniquad::BlendValue::SourceAlpha),
+        ));
 
-    fn update_range_value_message(self, label: String) -> FromGuiLoopMessage {
-        FromGuiLoopMessage::UpdateRangedValueUSize(UpdateRangedValue { label, value: self })
-    }
-}
+        let mesh_pipeline = miniquad::Pipeline::with_params(
+            ctx,
+            &[
+                miniquad::BufferLayout {
+                    stride: (3 + 4) * std::mem::size_of::<f32>() as i32,
+                    ..Default::default()
+                },
+                miniquad::BufferLayout {
+                    stride: 3 * std::mem::size_of::<f32>() as i32,
+                    ..Default::default()
+                },
+            ],
+            &[
+                miniquad::VertexAttribute::new("pos", miniquad::VertexFormat::Float3),
+                miniquad::VertexAttribute::new("color0", miniquad::VertexFormat::Float4),
+                miniquad::VertexAttribute::with_buffer("normal", miniquad::VertexFormat::Float3, 1),
+            ],
+            offscreen_shader,
+            miniquad::PipelineParams {
+                depth_test: miniquad::Comparison::LessOrEqual,
+                depth_write: true,
+                color_blend,
+                alpha_blend,
+                ..Default::default()
+            },
+        );
 
-impl Number for i32 {
-    fn add_var_message(self, label: String) -> ToGuiLoopMessage {
-        ToGuiLoopMessage::AddVarI32(AddVar::<i32> { label, value: self })
-    }
+        let segments_pipeline = miniquad::Pipeline::with_params(
+            ctx,
+            &[miniquad::BufferLayout {
+                stride: (3 + 4) * std::mem::size_of::<f32>() as i32,
+                ..Default::default()
+            }],
+            &[
+                miniquad::VertexAttribute::new("pos", miniquad::VertexFormat::Float3),
+                miniquad::VertexAttribute::new("color0", miniquad::VertexFormat::Float4),
+            ],
+            offscreen_shader,
+            miniquad::PipelineParams {
+                depth_test: miniquad::Comparison::LessOrEqual,
+                depth_write: true,
+                color_blend,
+                alpha_blend,
+                primitive_type: miniquad::PrimitiveType::Lines,
+                ..Default::default()
+            },
+        );
 
-    fn add_ranged_var_message(self, label: String, min_max: (i32, i32)) -> ToGuiLoopMessage {
-        ToGuiLoopMessage::AddRangedVarI32(AddRangedVar::<i32> {
-            label,
-            min_max,
-            value: self,
-        })
+        Self {
+            mesh_pipeline,
+            segments_pipeline,
+            offscreen_pass,
+            offscreen_size,
+        }
     }
 
-    fn update_range_value_message(self, label: String) -> FromGuiLoopMessage {
-        FromGuiLoopMessage::UpdateRangedValueI32(UpdateRangedValue { label, value: self })
-    }
-}
+    /// Draws a mesh, where `scene_from_entity` is used to shade it by the light of `params`.
+    #[allow(clippy::too_many_arguments)]
+    fn draw_mesh(
+        &self,
+        ctx: &mut miniquad::Context,
+        buffers: &GpuBuffers,
+        cull_mode: CullMode,
+        mvp: nalgebra::Matrix4<f32>,
+        scene_from_entity: nalgebra::Matrix4<f32>,
+        params: DrawParams,
+    ) {
+        ctx.apply_pipeline(&self.mesh_pipeline);
+        ctx.set_cull_face(cull_mode.into());
+        ctx.apply_bindings(&buffers.bindings);
 
-impl Number for i64 {
-    fn add_var_message(self, label: String) -> ToGuiLoopMessage {
-        ToGuiLoopMessage::AddVarI64(AddVar::<i64> { label, value: self })
-    }
+        let vs_params = offscreen_shader::Uniforms {
+            mvp,
+            srgb_output: params.srgb_output,
+            depth_bias: params.depth_bias,
+            opacity: params.opacity,
+            depth_view: params.depth_view,
+            near: params.clipping.0,
+            far: params.clipping.1,
+            orthographic: params.orthographic,
+            normal_matrix: scene_from_entity
+                .try_inverse()
+                .unwrap_or_else(nalgebra::Matrix4::identity)
+                .transpose(),
+            light_direction: params.light_direction,
+            lighting: params.lighting,
+        };
+        ctx.apply_uniforms(&vs_params);
 
-    fn add_ranged_var_message(self, label: String, min_max: (i64, i64)) -> ToGuiLoopMessage {
-        ToGuiLoopMessage::AddRangedVarI64(AddRangedVar::<i64> {
-            label,
-            min_max,
-            value: self,
-        })
+        ctx.draw(0, buffers.num_indices, 1);
     }
 
-    fn update_range_value_message(self, label: String) -> FromGuiLoopMessage {
-        FromGuiLoopMessage::UpdateRangedValueI64(UpdateRangedValue { label, value: self })
-    }
-}
+    /// Draws `segments` either as GL lines, or - for thick lines - as camera-facing quads using the
+    /// mesh pipeline.
+    ///
+    /// For GL lines, `buffers` of the segments are used if given; otherwise temporary buffers are
+    /// uploaded. For thick lines, the quads in `thick_line_buffers` are used if given and built for
+    /// the same view; otherwise they are built again and kept there, or are temporary if None.
+    #[allow(clippy::too_many_arguments)]
+    fn draw_line_segments(
+        &self,
+        ctx: &mut miniquad::Context,
+        segments: &entities::LineSegments3,
+        buffers: Option<&GpuBuffers>,
+        thick_line_buffers: Option<&mut ThickLineBuffers>,
+        line_width: LineWidth,
+        proj: nalgebra::Matrix4<f32>,
+        camera_from_entity: nalgebra::Matrix4<f32>,
+        params: DrawParams,
+    ) {
+        let half_width_at = |depth: f32| match line_width {
+            LineWidth::Hairline => None,
+            LineWidth::World(width) => Some(0.5 * width),
+            LineWidth::Pixels(width) => {
+                // Size of a pixel at the given depth; proj[(1, 1)] maps camera y to clip y, and the
+                // last row of proj maps the depth to clip w (w = depth if perspective, else 1).
+                let clip_w = proj[(3, 3)] - proj[(3, 2)] * depth;
+                let pixel_size = 2.0 * clip_w / (proj[(1, 1)] * self.offscreen_size.1 as f32);
+                Some(0.5 * width * pixel_size)
+            }
+        };
+        if half_width_at(1.0).is_some() {
+            let view = ThickLineView {
+                proj,
+                camera_from_entity,
+                line_width,
+                offscreen_height: self.offscreen_size.1,
+            };
+            let mut temporary = None;
+            let cache = thick_line_buffers.unwrap_or(&mut temporary);
+            if cache
+                .as_ref()
+                .is_none_or(|(cached_view, _)| *cached_view != view)
+            {
+                let orthographic = proj[(3, 2)] == 0.0;
+                let (vertices, faces) = thick_line_triangles(
+                    segments,
+                    &camera_from_entity,
+                    orthographic,
+                    half_width_at,
+                );
+                // The quads are expanded in the camera frame.
+                let normals = vec![nalgebra::Vector3::zeros(); vertices.vertices.len()];
+                let quads = GpuBuffers::new(ctx, &vertices, Some(&normals), faces.flat());
+                if let Some((_, stale)) = cache.replace((view, quads)) {
+                    stale.delete();
+                }
+            }
+            let params = DrawParams {
+                lighting: 0.0,
+                ..params
+            };
+            let identity = nalgebra::Matrix4::identity();
+            if let Some((_, quads)) = cache.as_ref() {
+                self.draw_mesh(ctx, quads, CullMode::Nothing, proj, identity, params);
+            }
+            if let Some((_, quads)) = temporary {
+                quads.delete();
+            }
+            return;
+        }
 
-impl Number for f32 {
-    fn add_var_message(self, label: String) -> ToGuiLoopMessage {
-        ToGuiLoopMessage::AddVarF32(AddVar::<f32> { label, value: self })
-    }
+        let temporary = match buffers {
+            Some(_) => None,
+            None => Some(GpuBuffers::new(
+                ctx,
+                &segments.vertices,
+                None,
+                segments.indices.flat(),
+            )),
+        };
+        let buffers = buffers.or(temporary.as_ref()).unwrap();
 
-    fn add_ranged_var_message(self, label: String, min_max: (f32, f32)) -> ToGuiLoopMessage {
-        ToGuiLoopMessage::AddRangedVarF32(AddRangedVar::<f32> {
-            label,
-            min_max,
-            value: self,
-        })
+        ctx.apply_pipeline(&self.segments_pipeline);
+        ctx.apply_bindings(&buffers.bindings);
+
+        let vs_params = offscreen_shader::Uniforms {
+            mvp: proj * camera_from_entity,
+            srgb_output: params.srgb_output,
+            depth_bias: params.depth_bias,
+            opacity: params.opacity,
+            depth_view: params.depth_view,
+            near: params.clipping.0,
+            far: params.clipping.1,
+            orthographic: params.orthographic,
+            normal_matrix: nalgebra::Matrix4::identity(),
+            light_direction: params.light_direction,
+            lighting: 0.0,
+        };
+        ctx.apply_uniforms(&vs_params);
+
+        ctx.draw(0, buffers.num_indices, 1);
+        if let Some(temporary) = temporary {
+            temporary.delete();
+        }
     }
 
-    fn update_range_value_message(self, label: String) -> FromGuiLoopMessage {
-        FromGuiLoopMessage::UpdateRangedValueF32(UpdateRangedValue { label, value: self })
+    fn resize_if_needed(&mut self, ctx: &mut miniquad::Context, requested_size: (u32, u32)) {
+        let (width, height) = self.offscreen_size;
+        let (requested_width, requested_height) = requested_size;
+        if width.abs_diff(requested_width) > Self::OFFSCREEN_RESIZE_THRESHOLD
+            || height.abs_diff(requested_height) > Self::OFFSCREEN_RESIZE_THRESHOLD
+        {
+            self.offscreen_pass.delete(ctx);
+            self.offscreen_pass = Self::new_offscreen_pass(ctx, requested_size);
+            self.offscreen_size = requested_size;
+        }
     }
 }
 
-impl Number for f64 {
-    fn add_var_message(self, label: String) -> ToGuiLoopMessage {
-        ToGuiLoopMessage::AddVarF64(AddVar::<f64> { label, value: self })
-    }
+impl Widget3 {
+    /// Offscreen resolution until the widget is shown for the first time.
+    const INITIAL_OFFSCREEN_SIZE: (u32, u32) = (640, 480);
 
-    fn add_ranged_var_message(self, label: String, min_max: (f64, f64)) -> ToGuiLoopMessage {
-        ToGuiLoopMessage::AddRangedVarF64(AddRangedVar::<f64> {
-            label,
-            min_max,
-            value: self,
-        })
+    /// Default direction of the light, which falls from the upper right onto the front of the
+    /// scene as seen from the default camera.
+    const DEFAULT_LIGHT_DIRECTION: nalgebra::Vector3<f32> =
+        nalgebra::Vector3::new(-0.5, -1.0, -1.0);
+
+    /// Default near and far clipping planes.
+    const DEFAULT_CLIPPING: (f32, f32) = (0.01, 100.0);
+
+    /// Zoom per scrolled point, on a logarithmic scale.
+    const SCROLL_ZOOM_SPEED: f32 = 0.002;
+
+    /// Color of the measurement line, see [SetMeasureMode].
+    const MEASUREMENT_COLOR: entities::Color = entities::Color {
+        r: 1.0,
+        g: 0.8,
+        b: 0.0,
+        alpha: 1.0,
+    };
+
+    /// Color of the normal lines, see [SetShowNormals].
+    const NORMALS_COLOR: entities::Color = entities::Color {
+        r: 0.0,
+        g: 0.0,
+        b: 1.0,
+        alpha: 1.0,
+    };
+
+    fn new() -> Self {
+        Self {
+            orbit_controller: camera::OrbitController::new(nalgebra::Isometry3::<f32>::from_parts(
+                nalgebra::Translation3::<f32>::new(0.0, 0.0, -4.0),
+                nalgebra::UnitQuaternion::<f32>::from_euler_angles(0.0, 0.0, 0.),
+            )),
+            entities: linked_hash_map::LinkedHashMap::new(),
+            cull_mode: CullMode::default(),
+            frustum_culling: true,
+            srgb_output: false,
+            normals_length: None,
+            line_width: LineWidth::default(),
+            world_grid: None,
+            world_axes: None,
+            background_color: Theme::background_color(None),
+            custom_background_color: None,
+            lighting: false,
+            light_direction: Self::DEFAULT_LIGHT_DIRECTION.normalize(),
+            entity_bounds: std::collections::HashMap::new(),
+            normal_lines: std::collections::HashMap::new(),
+            debug_mode: DebugMode::default(),
+            debug_entities: std::collections::HashMap::new(),
+            entity_buffers: std::collections::HashMap::new(),
+            thick_line_buffers: std::collections::HashMap::new(),
+            recolored_entities: std::collections::HashSet::new(),
+            stale_buffers: std::vec::Vec::new(),
+            animations: std::collections::HashMap::new(),
+            hidden_entities: std::collections::HashSet::new(),
+            on_top_entities: std::collections::HashSet::new(),
+            entity_opacities: std::collections::HashMap::new(),
+            groups: std::collections::HashMap::new(),
+            gpu: None,
+            requested_offscreen_size: Self::INITIAL_OFFSCREEN_SIZE,
+            aspect_ratio: 640.0 / 480.0,
+            projection_mode: ProjectionMode::default(),
+            clipping: Self::DEFAULT_CLIPPING,
+            split_camera_pose_scene: None,
+            hover_position: None,
+            hovered_point: None,
+            reported_hovered_point: None,
+            hovered_entity: None,
+            reported_hovered_entity: None,
+            reported_camera_pose_scene: None,
+            hover_highlight: true,
+            measure_mode: false,
+            measure_start: None,
+            measurement: None,
+            unreported_measurement: None,
+            text_placements: std::vec::Vec::new(),
+            texture_id: None,
+        }
     }
 
-    fn update_range_value_message(self, label: String) -> FromGuiLoopMessage {
-        FromGuiLoopMessage::UpdateRangedValueF64(UpdateRangedValue { label, value: self })
+    /// Drops everything derived from the geometry of the entity with given label - bounds, normal
+    /// lines, debug meshes and GPU buffers - after the geometry was changed or the entity removed.
+    fn invalidate_entity(&mut self, label: &str) {
+        self.entity_bounds.remove(label);
+        self.normal_lines.remove(label);
+        self.debug_entities.remove(label);
+        self.recolored_entities.remove(label);
+        if let Some(Some(buffers)) = self.entity_buffers.remove(label) {
+            self.stale_buffers.push(buffers);
+        }
+        if let Some(Some((_, quads))) = self.thick_line_buffers.remove(label) {
+            self.stale_buffers.push(quads);
+        }
     }
-}
 
-/// Message from  [super::manager::Manager] to [super::gui::GuiLoop], such as to add a component or
-/// widget.
-#[derive(Serialize, Deserialize, Debug)]
-pub enum ToGuiLoopMessage {
-    /// enum combobox
-    AddEnumStringRepr(AddEnumStringRepr),
-    /// button
-    AddButton(AddButton),
-    /// bool checkbox
-    AddVarBool(AddVar<bool>),
-    /// usize textbox
-    AddVarUSize(AddVar<usize>),
-    /// i32 textbox
-    AddVarI32(AddVar<i32>),
-    /// i64 textbox
-    AddVarI64(AddVar<i64>),
-    /// f32 textbox
-    AddVarF32(AddVar<f32>),
-    /// f64 textbox
-    AddVarF64(AddVar<f64>),
-    /// usize slider
-    AddRangedVarUSize(AddRangedVar<usize>),
-    /// i32 textbox
-    AddRangedVarI32(AddRangedVar<i32>),
-    /// i64 textbox
-    AddRangedVarI64(AddRangedVar<i64>),
-    /// f32 textbox
-    AddRangedVarF32(AddRangedVar<f32>),
-    /// f64 textbox
-    AddRangedVarF64(AddRangedVar<f64>),
-    /// 2d widget
-    AddWidget2(AddWidget2),
-    /// 3d widget
-    AddWidget3(AddWidget3),
-    /// place 3d entity
-    PlaceEntity3(PlaceEntity3),
-    /// delete component
-    DeleteComponent(DeleteComponent),
-    /// update pose of 3d entity
-    UpdateScenePoseEntity3(UpdateScenePoseEntity3),
-}
+    /// Projection from camera to clip space, for a viewport of given aspect ratio.
+    fn projection(&self, aspect_ratio: f32) -> nalgebra::Matrix4<f32> {
+        let (near, far) = self.clipping;
+        match self.projection_mode {
+            ProjectionMode::Perspective { fov_deg } => {
+                math::perspective(fov_deg.to_radians(), aspect_ratio, near, far)
+            }
+            ProjectionMode::Orthographic { height } => {
+                math::orthographic(height, aspect_ratio, near, far)
+            }
+        }
+    }
 
-impl ToGuiLoopMessage {
-    /// How that component or widget shall be displayed.
-    pub fn update_gui(self, data: &mut gui::GuiData, ctx: &mut miniquad::Context) {
-        use ToGuiLoopMessage::*;
+    /// Zooms in if `factor` > 1 and out if `factor` < 1: in perspective mode by moving the camera
+    /// towards the orbit center, in orthographic mode by shrinking the view.
+    fn zoom(&mut self, factor: f32) {
+        match &mut self.projection_mode {
+            ProjectionMode::Perspective { .. } => self.orbit_controller.zoom(factor),
+            ProjectionMode::Orthographic { height } if factor > 0.0 => *height /= factor,
+            ProjectionMode::Orthographic { .. } => {}
+        }
+    }
 
-        match self {
-            AddEnumStringRepr(e) => {
-                e.update_gui(data, ctx);
+    /// Zooms by the mouse wheel, `scroll` being the vertical scroll delta in points. Each step is
+    /// relative to the current distance to the orbit center, and in perspective mode the camera
+    /// stays at least the near clipping distance away from it.
+    fn scroll_zoom(&mut self, scroll: f32) {
+        let mut factor = (Self::SCROLL_ZOOM_SPEED * scroll).exp();
+        if let ProjectionMode::Perspective { .. } = self.projection_mode {
+            let distance = (self.orbit_controller.camera_pose_scene()
+                * self.orbit_controller.scene_point_orbit_center())
+            .coords
+            .norm();
+            factor = factor.min(distance / self.clipping.0);
+        }
+        if factor > 1.0 || scroll < 0.0 {
+            self.zoom(factor);
+        }
+    }
+
+    /// The camera poses and their viewports, relative to the widget in [0..1] with y downwards.
+    ///
+    /// In split view, the primary camera is shown on the left and the second one on the right.
+    fn views(&self) -> std::vec::Vec<(nalgebra::Isometry3<f32>, egui::Rect)> {
+        let full = egui::Rect::from_min_max(egui::Pos2::ZERO, egui::Pos2::new(1.0, 1.0));
+        match self.split_camera_pose_scene {
+            None => vec![(self.camera_pose_scene(), full)],
+            Some(split_camera_pose_scene) => {
+                let (left, right) = (
+                    egui::Rect::from_min_max(full.min, egui::Pos2::new(0.5, 1.0)),
+                    egui::Rect::from_min_max(egui::Pos2::new(0.5, 0.0), full.max),
+                );
+                vec![
+                    (self.camera_pose_scene(), left),
+                    (split_camera_pose_scene, right),
+                ]
             }
-            AddButton(e) => {
-                e.update_gui(data, ctx);
+        }
+    }
+
+    fn scene_params(&self) -> DrawParams {
+        DrawParams {
+            srgb_output: if self.srgb_output { 1.0 } else { 0.0 },
+            depth_bias: 0.0,
+            opacity: 1.0,
+            depth_view: if self.debug_mode == DebugMode::Depth {
+                1.0
+            } else {
+                0.0
+            },
+            clipping: self.clipping,
+            orthographic: match self.projection_mode {
+                ProjectionMode::Perspective { .. } => 0.0,
+                ProjectionMode::Orthographic { .. } => 1.0,
+            },
+            // Debug colors are shown unshaded.
+            lighting: if self.lighting && self.debug_mode == DebugMode::Normal {
+                1.0
+            } else {
+                0.0
+            },
+            light_direction: self.light_direction,
+        }
+    }
+
+    /// Draws the world references and all entities into the current viewport.
+    ///
+    /// Text labels are not drawn, but returned with the clip coordinates of their anchor point.
+    fn draw_scene(
+        &mut self,
+        ctx: &mut miniquad::Context,
+        gpu: &Widget3Gpu,
+        proj: nalgebra::Matrix4<f32>,
+        camera_from_scene: nalgebra::Matrix4<f32>,
+        on_top: bool,
+    ) -> std::vec::Vec<(nalgebra::Vector4<f32>, entities::TextLabel3)> {
+        let mut text_labels = std::vec::Vec::new();
+        let scene_params = self.scene_params();
+        // World references are drawn in scene coordinates and never culled.
+        let world_references = [&self.world_grid, &self.world_axes];
+        for segments in world_references.into_iter().flatten().filter(|_| !on_top) {
+            gpu.draw_line_segments(
+                ctx,
+                segments,
+                None,
+                None,
+                self.line_width,
+                proj,
+                camera_from_scene,
+                scene_params,
+            );
+        }
+        for (label, named_entity) in &self.entities {
+            if !self.is_entity_visible(label) || self.on_top_entities.contains(label) != on_top {
+                continue;
             }
-            AddVarBool(e) => {
-                e.update_gui(data, ctx);
+            let scene_transform_entity = self.scene_transform_entity(named_entity);
+            let mvp = proj * camera_from_scene * scene_transform_entity;
+            if self.frustum_culling {
+                let aabb = self
+                    .entity_bounds
+                    .entry(label.clone())
+                    .or_insert_with(|| named_entity.entity.aabb());
+                match aabb {
+                    Some(aabb) if !math::is_aabb_outside_frustum(&mvp, aabb) => {}
+                    _ => continue,
+                }
             }
-            AddVarUSize(e) => {
-                e.update_gui(data, ctx);
+            let params = DrawParams {
+                depth_bias: named_entity.depth_bias,
+                opacity: self.entity_opacities.get(label).copied().unwrap_or(1.0),
+                ..scene_params
+            };
+            let entity = match (&named_entity.entity, self.debug_mode) {
+                (entities::Entity3::Mesh(mesh), DebugMode::NormalsAsColor) => &*self
+                    .debug_entities
+                    .entry(label.clone())
+                    .or_insert_with(|| entities::normals_as_color(mesh)),
+                (entities::Entity3::Mesh(mesh), DebugMode::Wireframe) => &*self
+                    .debug_entities
+                    .entry(label.clone())
+                    .or_insert_with(|| entities::wireframe(mesh)),
+                (entity, _) => entity,
+            };
+            let buffers = self
+                .entity_buffers
+                .entry(label.clone())
+                .or_insert_with(|| GpuBuffers::from_entity(ctx, entity));
+            if self.recolored_entities.remove(label) {
+                match (buffers.as_mut(), entity) {
+                    (Some(buffers), entities::Entity3::Mesh(mesh)) => {
+                        buffers.update_vertices(ctx, mesh.vertices.as_position_color().unwrap())
+                    }
+                    (Some(buffers), entities::Entity3::LineSegments(segments)) => {
+                        buffers.update_vertices(ctx, &segments.vertices)
+                    }
+                    _ => {}
+                }
             }
-            AddVarI32(e) => {
-                e.update_gui(data, ctx);
+            let buffers = buffers.as_ref();
+            match entity {
+                entities::Entity3::Mesh(_) => {
+                    gpu.draw_mesh(
+                        ctx,
+                        buffers.unwrap(),
+                        named_entity.cull_mode.unwrap_or(self.cull_mode),
+                        mvp,
+                        scene_transform_entity,
+                        params,
+                    );
+                }
+                entities::Entity3::LineSegments(segments) => {
+                    gpu.draw_line_segments(
+                        ctx,
+                        segments,
+                        buffers,
+                        Some(self.thick_line_buffers.entry(label.clone()).or_default()),
+                        self.line_width,
+                        proj,
+                        camera_from_scene * scene_transform_entity,
+                        params,
+                    );
+                }
+                entities::Entity3::Text(label) => {
+                    text_labels.push((mvp * nalgebra::Vector4::w(), label.clone()));
+                }
             }
-            AddVarI64(e) => {
-                e.update_gui(data, ctx);
+            if let (Some(length), entities::Entity3::Mesh(mesh)) =
+                (self.normals_length, &named_entity.entity)
+            {
+                let normals = self.normal_lines.entry(label.clone()).or_insert_with(|| {
+                    entities::normal_line_segments(mesh, length, Self::NORMALS_COLOR)
+                });
+                gpu.draw_line_segments(
+                    ctx,
+                    normals,
+                    None,
+                    None,
+                    self.line_width,
+                    proj,
+                    camera_from_scene * scene_transform_entity,
+                    params,
+                );
             }
-            AddVarF32(e) => {
-                e.update_gui(data, ctx);
+        }
+        text_labels
+    }
+
+    /// egui color of the entity color `[r, g, b, alpha]`, which is sRGB unless `srgb_output` is
+    /// enabled, see [SetSrgbOutput].
+    fn egui_color(srgb_output: bool, [r, g, b, alpha]: [f32; 4]) -> egui::Color32 {
+        if srgb_output {
+            egui::Rgba::from_rgba_unmultiplied(r, g, b, alpha).into()
+        } else {
+            let to_u8 = |c: f32| (255.0 * c).round() as u8;
+            egui::Color32::from_rgba_unmultiplied(to_u8(r), to_u8(g), to_u8(b), to_u8(alpha))
+        }
+    }
+
+    /// egui text style whose font size is closest to `size` in points.
+    fn text_style(ui: &egui::Ui, size: f32) -> egui::TextStyle {
+        let fonts = ui.fonts();
+        let sizes = &fonts.definitions().family_and_size;
+        [
+            egui::TextStyle::Small,
+            egui::TextStyle::Body,
+            egui::TextStyle::Heading,
+        ]
+        .into_iter()
+        .min_by(|a, b| {
+            let distance = |style| (sizes[style].1 - size).abs();
+            distance(a).total_cmp(&distance(b))
+        })
+        .unwrap()
+    }
+
+    /// Handles a click in measure mode, see [SetMeasureMode].
+    fn measure_click(&mut self) {
+        match (self.hovered_point, self.measure_start.take()) {
+            (Some(end), Some(start)) => {
+                self.measurement = Some((start, end));
+                self.unreported_measurement = Some((end - start).norm());
             }
-            AddVarF64(e) => {
-                e.update_gui(data, ctx);
+            (Some(start), None) => {
+                self.measure_start = Some(start);
+                self.measurement = None;
             }
-            AddRangedVarUSize(e) => {
-                e.update_gui(data, ctx);
+            // Clicking on the background cancels.
+            (None, _) => self.measurement = None,
+        }
+    }
+
+    /// Returns the distance of a measurement if one was completed since the last call, see
+    /// [SetMeasureMode].
+    pub fn take_measurement_update(&mut self) -> Option<f32> {
+        self.unreported_measurement.take()
+    }
+
+    /// The entities of the widget, by label.
+    pub fn entities(&self) -> &linked_hash_map::LinkedHashMap<String, entities::NamedEntity3> {
+        &self.entities
+    }
+
+    /// Whether the entity with name `label` is shown, see [SetEntityVisible] and
+    /// [SetEntityGroupVisible].
+    pub fn is_entity_visible(&self, label: &str) -> bool {
+        !self.hidden_entities.contains(label)
+            && self
+                .groups
+                .values()
+                .all(|group| group.visible || !group.members.contains(label))
+    }
+
+    /// Transformation from entity to scene, including the pose of its group - if any.
+    fn scene_transform_entity(
+        &self,
+        named_entity: &entities::NamedEntity3,
+    ) -> nalgebra::Matrix4<f32> {
+        let group = self
+            .groups
+            .values()
+            .find(|g| g.members.contains(&named_entity.label));
+        let scene_transform_group = match group {
+            Some(group) => group.scene_pose_group.to_matrix(),
+            None => nalgebra::Matrix4::identity(),
+        };
+        scene_transform_group * named_entity.scene_transform_entity()
+    }
+
+    /// Pixel distance within which the mouse cursor hits a line segment, see [Widget3::pick].
+    const LINE_PICK_RADIUS: f32 = 4.0;
+
+    /// Ray through `ndc` - in normalized device coordinates of a view with `clip_from_scene` - as
+    /// origin on the near plane and direction to the far plane, both in scene coordinates.
+    fn view_ray(
+        clip_from_scene: &nalgebra::Matrix4<f32>,
+        ndc: nalgebra::Vector2<f32>,
+    ) -> Option<(nalgebra::Vector3<f32>, nalgebra::Vector3<f32>)> {
+        let scene_from_clip = clip_from_scene.try_inverse()?;
+        let unproject = |z: f32| {
+            let p = scene_from_clip * nalgebra::Vector4::new(ndc.x, ndc.y, z, 1.0);
+            p.xyz() / p.w
+        };
+        let origin = unproject(-1.0);
+        Some((origin, unproject(1.0) - origin))
+    }
+
+    /// Nearest visible entity along the ray through `ndc`, see [Widget3::view_ray], as scene point
+    /// and label. Meshes are hit by their triangles. If `viewport_size` in pixels is given, line
+    /// segments are hit within [Widget3::LINE_PICK_RADIUS] pixels of the cursor.
+    ///
+    /// This is a CPU hit test, culled by the entity bounding boxes, hence nothing is read back
+    /// from the GPU.
+    fn pick(
+        &mut self,
+        clip_from_scene: &nalgebra::Matrix4<f32>,
+        ndc: nalgebra::Vector2<f32>,
+        viewport_size: Option<(f32, f32)>,
+    ) -> Option<(nalgebra::Vector3<f32>, String)> {
+        let (origin, direction) = Self::view_ray(clip_from_scene, ndc)?;
+        // Parameter of the nearest hit along the ray, in [0, 1] from the near to the far plane.
+        let mut best: Option<(f32, nalgebra::Vector3<f32>, String)> = None;
+        for (label, named_entity) in &self.entities {
+            if !self.is_entity_visible(label) || named_entity.entity.as_text().is_some() {
+                continue;
             }
-            AddRangedVarI32(e) => {
-                e.update_gui(data, ctx);
+            let aabb = match self
+                .entity_bounds
+                .entry(label.clone())
+                .or_insert_with(|| named_entity.entity.aabb())
+            {
+                Some(aabb) => *aabb,
+                None => continue,
+            };
+            let scene_transform_entity = self.scene_transform_entity(named_entity);
+            let entity_from_scene = match scene_transform_entity.try_inverse() {
+                Some(entity_from_scene) => entity_from_scene,
+                None => continue,
+            };
+            let max_t = best.as_ref().map_or(1.0, |(t, _, _)| *t);
+            let hit = match &named_entity.entity {
+                entities::Entity3::Mesh(mesh) => {
+                    // The ray parameter is the same in entity and scene coordinates.
+                    let entity_origin = (entity_from_scene * origin.push(1.0)).xyz();
+                    let entity_direction = (entity_from_scene * direction.push(0.0)).xyz();
+                    if math::ray_aabb_intersection(&entity_origin, &entity_direction, &aabb)
+                        .is_none_or(|t| t > max_t)
+                    {
+                        continue;
+                    }
+                    let positions = mesh.positions();
+                    let vertex = |i: i16| positions.get(i as usize).copied();
+                    mesh.faces
+                        .indices
+                        .iter()
+                        .filter_map(|face| {
+                            let triangle = [vertex(face[0])?, vertex(face[1])?, vertex(face[2])?];
+                            math::ray_triangle_intersection(
+                                &entity_origin,
+                                &entity_direction,
+                                &triangle,
+                            )
+                        })
+                        .min_by(|a, b| a.total_cmp(b))
+                        .map(|t| (t, origin + t * direction))
+                }
+                entities::Entity3::LineSegments(segments) => viewport_size
+                    .and_then(|viewport_size| {
+                        Self::pick_line_segments(
+                            segments,
+                            &(clip_from_scene * scene_transform_entity),
+                            viewport_size,
+                            ndc,
+                        )
+                    })
+                    .map(|entity_point| {
+                        let point = (scene_transform_entity * entity_point.push(1.0)).xyz();
+                        let t = (point - origin).dot(&direction) / direction.norm_squared();
+                        (t, point)
+                    }),
+                entities::Entity3::Text(_) => None,
+            };
+            if let Some((t, point)) = hit.filter(|(t, _)| (0.0..=max_t).contains(t)) {
+                best = Some((t, point, label.clone()));
             }
-            AddRangedVarI64(e) => {
-                e.update_gui(data, ctx);
+        }
+        best.map(|(_, point, label)| (point, label))
+    }
+
+    /// Point of `segments`, in entity coordinates, closest to `ndc` within
+    /// [Widget3::LINE_PICK_RADIUS] pixels of a view with `clip_from_entity` and `viewport_size`.
+    fn pick_line_segments(
+        segments: &entities::LineSegments3,
+        clip_from_entity: &nalgebra::Matrix4<f32>,
+        (width, height): (f32, f32),
+        ndc: nalgebra::Vector2<f32>,
+    ) -> Option<nalgebra::Vector3<f32>> {
+        let to_pixels = |v: nalgebra::Vector2<f32>| {
+            nalgebra::Vector2::new(0.5 * v.x * width, 0.5 * v.y * height)
+        };
+        let cursor = to_pixels(ndc);
+        let vertex = |i: i16| {
+            segments
+                .vertices
+                .vertices
+                .get(i as usize)
+                .map(|v| nalgebra::Vector3::new(v[0], v[1], v[2]))
+        };
+        let mut best: Option<(f32, nalgebra::Vector3<f32>)> = None;
+        for [i, j] in &segments.indices {
+            let (a, b) = match (vertex(*i), vertex(*j)) {
+                (Some(a), Some(b)) => (a, b),
+                _ => continue,
+            };
+            let (clip_a, clip_b) = (
+                clip_from_entity * a.push(1.0),
+                clip_from_entity * b.push(1.0),
+            );
+            if clip_a.w <= 0.0 || clip_b.w <= 0.0 {
+                continue;
             }
-            AddRangedVarF32(e) => {
-                e.update_gui(data, ctx);
+            let pixel_a = to_pixels(clip_a.xy() / clip_a.w);
+            let pixel_ab = to_pixels(clip_b.xy() / clip_b.w) - pixel_a;
+            let s = if pixel_ab.norm_squared() > 0.0 {
+                ((cursor - pixel_a).dot(&pixel_ab) / pixel_ab.norm_squared()).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            let distance = (pixel_a + s * pixel_ab - cursor).norm();
+            if distance <= Self::LINE_PICK_RADIUS && best.is_none_or(|(d, _)| distance < d) {
+                // Not perspective correct, which is fine within a few pixels.
+                best = Some((distance, a + s * (b - a)));
             }
-            AddRangedVarF64(e) => {
-                e.update_gui(data, ctx);
+        }
+        best.map(|(_, point)| point)
+    }
+
+    /// Whether a mesh is in front of the point with clip coordinates `clip`, e.g. the anchor of a
+    /// text label, in a view with `clip_from_scene`; see [Widget3::pick].
+    fn is_occluded(
+        &mut self,
+        clip_from_scene: &nalgebra::Matrix4<f32>,
+        clip: &nalgebra::Vector4<f32>,
+    ) -> bool {
+        let ndc = clip.xy() / clip.w;
+        let (origin, anchor) = match (
+            Self::view_ray(clip_from_scene, ndc),
+            clip_from_scene.try_inverse(),
+        ) {
+            (Some((origin, _)), Some(scene_from_clip)) => {
+                let anchor = scene_from_clip * clip;
+                (origin, anchor.xyz() / anchor.w)
             }
-            AddWidget2(e) => {
-                e.update_gui(data, ctx);
+            _ => return false,
+        };
+        match self.pick(clip_from_scene, ndc, None) {
+            Some((point, _)) => (point - origin).norm() < (1.0 - 1e-3) * (anchor - origin).norm(),
+            None => false,
+        }
+    }
+
+    /// The entity under the mouse cursor, as of the last rendered frame, i.e. the one hit at
+    /// [Widget3::hovered_point].
+    pub fn hovered_entity(&self) -> Option<&str> {
+        self.hovered_entity.as_deref()
+    }
+
+    /// Returns the hovered entity if it changed since the last call, see
+    /// [Widget3::hovered_entity].
+    pub fn take_hovered_entity_update(&mut self) -> Option<Option<String>> {
+        if self.hovered_entity == self.reported_hovered_entity {
+            return None;
+        }
+        self.reported_hovered_entity = self.hovered_entity.clone();
+        Some(self.hovered_entity.clone())
+    }
+
+    /// The entity groups of the widget, by label.
+    pub fn groups(&self) -> &std::collections::HashMap<String, EntityGroup> {
+        &self.groups
+    }
+
+    /// Shows one row per entity, with a checkbox to toggle its visibility and a swatch of its
+    /// color.
+    pub fn show_entity_list(&mut self, ui: &mut egui::Ui) {
+        let srgb_output = self.srgb_output;
+        for (label, named_entity) in &self.entities {
+            ui.horizontal(|ui| {
+                let mut visible = !self.hidden_entities.contains(label);
+                if ui.checkbox(&mut visible, label).changed() {
+                    if visible {
+                        self.hidden_entities.remove(label);
+                    } else {
+                        self.hidden_entities.insert(label.clone());
+                    }
+                }
+                let vertex_color = |v: &[f32; 7]| [v[3], v[4], v[5]];
+                let color = match &named_entity.entity {
+                    entities::Entity3::Mesh(mesh) => mesh
+                        .vertices
+                        .as_position_color()
+                        .and_then(|v| v.vertices.first())
+                        .map(vertex_color),
+                    entities::Entity3::LineSegments(segments) => {
+                        segments.vertices.vertices.first().map(vertex_color)
+                    }
+                    entities::Entity3::Text(label) => {
+                        Some([label.color.r, label.color.g, label.color.b])
+                    }
+                };
+                if let Some([r, g, b]) = color {
+                    let color = Self::egui_color(srgb_output, [r, g, b, 1.0]);
+                    egui::color_picker::show_color(ui, color, egui::Vec2::splat(12.0));
+                }
+            });
+        }
+    }
+
+    /// The scene point under the mouse cursor, as of the last rendered frame; None if the cursor
+    /// is not over the widget, or over the background. Meshes are hit exactly, line segments
+    /// within a few pixels; text labels cannot be hovered.
+    pub fn hovered_point(&self) -> Option<nalgebra::Vector3<f32>> {
+        self.hovered_point
+    }
+
+    /// Returns the hovered point if it changed since the last call, see [Widget3::hovered_point].
+    pub fn take_hovered_point_update(&mut self) -> Option<Option<nalgebra::Vector3<f32>>> {
+        if self.hovered_point == self.reported_hovered_point {
+            return None;
+        }
+        self.reported_hovered_point = self.hovered_point;
+        Some(self.hovered_point)
+    }
+
+    /// Id of the rendered scene as egui texture, to show it in a custom egui layout, e.g. using
+    /// `egui::Image`. It is None until the widget was rendered for the first time.
+    pub fn texture_id(&self) -> Option<egui::TextureId> {
+        self.texture_id
+    }
+
+    /// Color texture of the offscreen render pass, which the scene is rendered to. It is None until
+    /// the widget was rendered for the first time.
+    ///
+    /// Note that the texture is recreated when the widget is resized.
+    pub fn offscreen_texture(&self, ctx: &mut miniquad::Context) -> Option<miniquad::Texture> {
+        self.gpu.as_ref().map(|gpu| gpu.offscreen_pass.texture(ctx))
+    }
+
+    /// The camera pose - i.e. the transformation from scene to camera.
+    pub fn camera_pose_scene(&self) -> nalgebra::Isometry3<f32> {
+        self.orbit_controller.camera_pose_scene()
+    }
+
+    /// Returns the camera pose - i.e. the transformation from scene to camera - if it changed
+    /// since the last call, e.g. by mouse interaction.
+    pub fn take_camera_pose_update(&mut self) -> Option<nalgebra::Isometry3<f32>> {
+        let camera_pose_scene = self.camera_pose_scene();
+        if self.reported_camera_pose_scene == Some(camera_pose_scene) {
+            return None;
+        }
+        self.reported_camera_pose_scene = Some(camera_pose_scene);
+        Some(camera_pose_scene)
+    }
+
+    /// The camera control, driven by mouse interaction.
+    pub fn orbit_controller(&self) -> &camera::OrbitController {
+        &self.orbit_controller
+    }
+
+    /// The camera control, e.g. to move the camera programmatically.
+    pub fn orbit_controller_mut(&mut self) -> &mut camera::OrbitController {
+        &mut self.orbit_controller
+    }
+}
+
+impl Widget for Widget3 {
+    fn render(&mut self, ctx: &mut miniquad::Context) {
+        for (label, (start, animation)) in &mut self.animations {
+            if let Some(named_entity) = self.entities.get_mut(label) {
+                let animation = animation.get_mut().unwrap();
+                named_entity.scene_pose_entity = animation(start.elapsed().as_secs_f64());
             }
-            AddWidget3(e) => {
-                e.update_gui(data, ctx);
+        }
+
+        for buffers in self.stale_buffers.drain(..) {
+            buffers.delete();
+        }
+
+        let requested_offscreen_size = self.requested_offscreen_size;
+        // Taken out temporarily, so that the scene can be drawn by a method of the widget.
+        let mut gpu = self
+            .gpu
+            .take()
+            .unwrap_or_else(|| Widget3Gpu::new(ctx, requested_offscreen_size));
+        gpu.resize_if_needed(ctx, requested_offscreen_size);
+
+        // the offscreen render pipeline, following this example:
+        // https://github.com/not-fl3/egui-miniquad/blob/master/examples/render_to_egui_image.rs
+        let (r, g, b, a) = self.background_color;
+        ctx.begin_pass(
+            gpu.offscreen_pass,
+            miniquad::PassAction::clear_color(r, g, b, a),
+        );
+        let (width, height) = gpu.offscreen_size;
+        let hover_position = self.hover_position;
+        let mut hovered = None;
+        self.text_placements.clear();
+        for (camera_pose_scene, viewport) in self.views() {
+            let (x, y) = (
+                (viewport.min.x * width as f32) as i32,
+                (viewport.min.y * height as f32) as i32,
+            );
+            let (w, h) = (
+                ((viewport.width() * width as f32) as i32).max(1),
+                ((viewport.height() * height as f32) as i32).max(1),
+            );
+            ctx.apply_viewport(x, y, w, h);
+            let proj = self.projection(self.aspect_ratio * viewport.aspect_ratio());
+            let camera_from_scene = camera_pose_scene.to_matrix();
+            let text_labels = self.draw_scene(ctx, &gpu, proj, camera_from_scene, false);
+
+            let clip_from_scene = proj * camera_from_scene;
+
+            // Text labels are hidden behind meshes.
+            for (clip, label) in text_labels {
+                let ndc = clip.xyz() / clip.w;
+                if clip.w <= 0.0 || ndc.x.abs() > 1.0 || ndc.y.abs() > 1.0 {
+                    continue;
+                }
+                if !label.always_on_top && self.is_occluded(&clip_from_scene, &clip) {
+                    continue;
+                }
+                let local_position = egui::Vec2::new(0.5 * (ndc.x + 1.0), 0.5 * (ndc.y + 1.0));
+                self.text_placements
+                    .push((viewport.min + local_position * viewport.size(), label));
             }
-            PlaceEntity3(e) => {
-                e.update_gui(data, ctx);
+
+            if let Some(position) = hover_position.filter(|p| viewport.contains(p.to_pos2())) {
+                let local_position = (position.to_pos2() - viewport.min) / viewport.size();
+                // The texture is shown with its first row, i.e. the GL bottom row, at the top.
+                let ndc = nalgebra::Vector2::new(
+                    2.0 * local_position.x - 1.0,
+                    2.0 * local_position.y - 1.0,
+                );
+                hovered = self.pick(&clip_from_scene, ndc, Some((w as f32, h as f32)));
             }
-            DeleteComponent(e) => {
-                e.update_gui(data, ctx);
+            // Not an entity, hence it cannot be picked.
+            if let Some((start, end)) = self.measurement {
+                let mut segments = entities::LineSegments3::default();
+                for point in [start, end] {
+                    segments.append_to_path(point, Self::MEASUREMENT_COLOR, None);
+                }
+                let params = DrawParams {
+                    depth_bias: 1e-3,
+                    ..self.scene_params()
+                };
+                gpu.draw_line_segments(
+                    ctx,
+                    &segments,
+                    None,
+                    None,
+                    self.line_width,
+                    proj,
+                    camera_from_scene,
+                    params,
+                );
             }
-            UpdateScenePoseEntity3(e) => {
-                e.update_gui(data, ctx);
+            let hovered = self
+                .hovered_entity
+                .as_ref()
+                .filter(|_| self.hover_highlight)
+                .and_then(|label| self.entities.get(label));
+            if let Some(named_entity) = hovered {
+                if let Some(Some(aabb)) = self.entity_bounds.get(&named_entity.label) {
+                    let params = DrawParams {
+                        depth_bias: 1e-3,
+                        ..self.scene_params()
+                    };
+                    gpu.draw_line_segments(
+                        ctx,
+                        &aabb.edges(Self::MEASUREMENT_COLOR),
+                        None,
+                        None,
+                        self.line_width,
+                        proj,
+                        camera_from_scene * self.scene_transform_entity(named_entity),
+                        params,
+                    );
+                }
+            }
+            // Entities on top are drawn last after clearing the depth buffer, so that they occlude
+            // each other but nothing else occludes them. Clearing covers the whole texture, but
+            // views are drawn one after the other, so previous views are not affected.
+            if !self.on_top_entities.is_empty() {
+                ctx.clear(None, Some(1.0), None);
+                let text_labels = self.draw_scene(ctx, &gpu, proj, camera_from_scene, true);
+                for (clip, mut label) in text_labels {
+                    let ndc = clip.xyz() / clip.w;
+                    if clip.w <= 0.0 || ndc.x.abs() > 1.0 || ndc.y.abs() > 1.0 {
+                        continue;
+                    }
+                    label.always_on_top = true;
+                    let local_position = egui::Vec2::new(0.5 * (ndc.x + 1.0), 0.5 * (ndc.y + 1.0));
+                    self.text_placements
+                        .push((viewport.min + local_position * viewport.size(), label));
+                }
             }
         }
+        // Outlined in the next frame.
+        (self.hovered_point, self.hovered_entity) = match hovered {
+            Some((point, label)) => (Some(point), Some(label)),
+            None => (None, None),
+        };
+        ctx.end_render_pass();
+
+        // Extract texture from offscreen render pass
+        let mq_texture = gpu.offscreen_pass.texture(ctx);
+        //print!("{} {}", mq_texture.width, mq_texture.height);
+
+        // create egui TextureId from Miniquad GL texture Id
+        self.texture_id = Some(egui::TextureId::User(mq_texture.gl_internal_id() as u64));
+        self.gpu = Some(gpu);
+
+        ctx.clear(Some((1., 1., 1., 1.)), None, None);
+        ctx.begin_default_pass(miniquad::PassAction::clear_color(0.3, 0.3, 0.3, 1.0));
+        ctx.end_render_pass();
     }
-}
 
-/// Add an enum (as string representation) as combo box to side panel.
-#[derive(Serialize, Deserialize, Debug)]
-pub struct AddEnumStringRepr {
-    /// The name of the enum.
-    pub label: String,
-    /// Initial value.
-    pub value: String,
-    /// List of possible values.
-    pub values: std::vec::Vec<String>,
-}
+    fn show(
+        &mut self,
+        ui: &mut egui::Ui,
+        assigned_width: f32,
+        assigned_height: f32,
+    ) -> Option<egui::Response> {
+        let r = ui.allocate_response(
+            egui::Vec2::new(assigned_width, assigned_height),
+            egui::Sense::click_and_drag(),
+        );
+        let image_rect = gui::image_rect(&r, self.aspect_ratio);
+        egui::Image::new(self.texture_id.unwrap(), image_rect.size()).paint_at(ui, image_rect);
 
-impl AddEnumStringRepr {
-    fn update_gui(self, data: &mut gui::GuiData, _ctx: &mut miniquad::Context) {
-        data.components.insert(
-            self.label,
-            Box::new(EnumStringRepr {
-                value: self.value,
-                values: self.values,
-            }),
+        // Render at the physical on-screen resolution; picked up by the next call to render.
+        let pixels_per_point = ui.ctx().pixels_per_point();
+        self.requested_offscreen_size = (
+            ((image_rect.width() * pixels_per_point).round() as u32).max(1),
+            ((image_rect.height() * pixels_per_point).round() as u32).max(1),
         );
+
+        let painter = ui.painter_at(image_rect);
+        for (position, label) in &self.text_placements {
+            let color = label.color;
+            painter.text(
+                image_rect.min + position.to_vec2() * image_rect.size(),
+                egui::Align2::CENTER_CENTER,
+                &label.text,
+                Self::text_style(ui, label.size),
+                Self::egui_color(self.srgb_output, [color.r, color.g, color.b, color.alpha]),
+            );
+        }
+
+        self.hover_position = r
+            .hover_pos()
+            .filter(|pos| image_rect.contains(*pos))
+            .map(|pos| (pos - image_rect.min) / image_rect.size());
+        if self.measure_mode && r.clicked() {
+            self.measure_click();
+        }
+        for (camera_pose_scene, viewport) in self.views() {
+            let (start, end) = match self.measurement {
+                Some(measurement) => measurement,
+                None => break,
+            };
+            let proj = self.projection(self.aspect_ratio * viewport.aspect_ratio());
+            let center = proj * camera_pose_scene.to_matrix() * (0.5 * (start + end)).push(1.0);
+            if center.w > 0.0 {
+                // Same convention as the texture, i.e. GL's y axis pointing downwards on screen.
+                let ndc = center.xy() / center.w;
+                let rect = egui::Rect::from_min_max(
+                    image_rect.min + viewport.min.to_vec2() * image_rect.size(),
+                    image_rect.min + viewport.max.to_vec2() * image_rect.size(),
+                );
+                let pos = rect.min
+                    + egui::Vec2::new(0.5 * (ndc.x + 1.0), 0.5 * (ndc.y + 1.0)) * rect.size();
+                ui.painter_at(rect).text(
+                    pos,
+                    egui::Align2::CENTER_BOTTOM,
+                    format!("{:.3}", (end - start).norm()),
+                    egui::TextStyle::Body,
+                    egui::Color32::from_rgb(255, 204, 0),
+                );
+            }
+        }
+
+        let scroll = ui.ctx().input().scroll_delta.y;
+        if r.hovered() && scroll != 0.0 {
+            self.scroll_zoom(scroll);
+        }
+        if r.double_clicked() {
+            self.orbit_controller.reset();
+        } else if ui.ctx().input().pointer.secondary_down() {
+            // TODO: Calculate delta scale based on scene depth.
+            let delta = 0.01 * ui.ctx().input().pointer.delta();
+            self.orbit_controller
+                .pan(nalgebra::Vector2::new(delta.x, delta.y));
+        } else if ui.ctx().input().pointer.primary_down() {
+            let delta = 0.01 * ui.ctx().input().pointer.delta();
+            self.orbit_controller
+                .rotate(nalgebra::Vector2::new(delta.x, -delta.y));
+        }
+
+        Some(r)
+    }
+
+    fn aspect_ratio(&self) -> f32 {
+        self.aspect_ratio
+    }
+
+    fn snapshot(&self, label: &str) -> std::vec::Vec<ToGuiLoopMessage> {
+        let mut messages = vec![
+            ToGuiLoopMessage::AddWidget3(AddWidget3 {
+                label: label.to_string(),
+                camera_pose_scene: Some(self.camera_pose_scene()),
+            }),
+            ToGuiLoopMessage::SetCullMode(SetCullMode {
+                widget_label: label.to_string(),
+                cull_mode: self.cull_mode,
+            }),
+            ToGuiLoopMessage::SetProjectionMode(SetProjectionMode {
+                widget_label: label.to_string(),
+                projection_mode: self.projection_mode,
+            }),
+            ToGuiLoopMessage::SetClipping(SetClipping {
+                widget_label: label.to_string(),
+                near: self.clipping.0,
+                far: self.clipping.1,
+            }),
+        ];
+        for (_, named_entity) in &self.entities {
+            messages.push(ToGuiLoopMessage::PlaceEntity3(PlaceEntity3 {
+                widget_label: label.to_string(),
+                named_entity: named_entity.clone(),
+            }));
+        }
+        for (entity_label, opacity) in &self.entity_opacities {
+            messages.push(ToGuiLoopMessage::SetEntityOpacity(SetEntityOpacity {
+                widget_label: label.to_string(),
+                entity_label: entity_label.clone(),
+                opacity: *opacity,
+            }));
+        }
+        for entity_label in &self.hidden_entities {
+            messages.push(ToGuiLoopMessage::SetEntityVisible(SetEntityVisible {
+                widget_label: label.to_string(),
+                entity_label: entity_label.clone(),
+                visible: false,
+            }));
+        }
+        for entity_label in &self.on_top_entities {
+            messages.push(ToGuiLoopMessage::SetEntityOnTop(SetEntityOnTop {
+                widget_label: label.to_string(),
+                entity_label: entity_label.clone(),
+                on_top: true,
+            }));
+        }
+        if !self.frustum_culling {
+            messages.push(ToGuiLoopMessage::SetFrustumCulling(SetFrustumCulling {
+                widget_label: label.to_string(),
+                enabled: false,
+            }));
+        }
+        if self.srgb_output {
+            messages.push(ToGuiLoopMessage::SetSrgbOutput(SetSrgbOutput {
+                widget_label: label.to_string(),
+                enabled: true,
+            }));
+        }
+        if self.debug_mode != DebugMode::default() {
+            messages.push(ToGuiLoopMessage::SetDebugMode(SetDebugMode {
+                widget_label: label.to_string(),
+                debug_mode: self.debug_mode,
+            }));
+        }
+        if self.normals_length.is_some() {
+            messages.push(ToGuiLoopMessage::SetShowNormals(SetShowNormals {
+                widget_label: label.to_string(),
+                length: self.normals_length,
+            }));
+        }
+        if self.line_width != LineWidth::default() {
+            messages.push(ToGuiLoopMessage::SetLineWidth(SetLineWidth {
+                widget_label: label.to_string(),
+                line_width: self.line_width,
+            }));
+        }
+        if !self.hover_highlight {
+            messages.push(ToGuiLoopMessage::SetHoverHighlight(SetHoverHighlight {
+                widget_label: label.to_string(),
+                enabled: false,
+            }));
+        }
+        if self.world_axes.is_some() {
+            messages.push(ToGuiLoopMessage::SetShowWorldAxes(SetShowWorldAxes {
+                widget_label: label.to_string(),
+                enabled: true,
+            }));
+        }
+        if self.split_camera_pose_scene.is_some() {
+            messages.push(ToGuiLoopMessage::SetSplitView(SetSplitView {
+                widget_label: label.to_string(),
+                split_camera_pose_scene: self.split_camera_pose_scene,
+            }));
+        }
+        let pivot = self.orbit_controller.scene_point_orbit_center();
+        if pivot != nalgebra::Point3::origin() {
+            messages.push(ToGuiLoopMessage::SetRotationPivot(SetRotationPivot {
+                widget_label: label.to_string(),
+                pivot: pivot.coords,
+            }));
+        }
+        if self.lighting {
+            messages.push(ToGuiLoopMessage::SetLighting(SetLighting {
+                widget_label: label.to_string(),
+                enabled: Some(true),
+                light_direction: Some(self.light_direction),
+            }));
+        }
+        if let Some(color) = self.custom_background_color {
+            messages.push(ToGuiLoopMessage::SetBackgroundColor(SetBackgroundColor {
+                widget_label: label.to_string(),
+                color: Some(color),
+            }));
+        }
+        for (group_label, group) in &self.groups {
+            messages.push(ToGuiLoopMessage::CreateEntityGroup(CreateEntityGroup {
+                widget_label: label.to_string(),
+                group_label: group_label.clone(),
+                group: Some(group.clone()),
+            }));
+        }
+        messages
+    }
+}
+
+/// Integer or floating point number.
+pub trait Number:
+    egui::emath::Numeric + downcast_rs::DowncastSync + std::fmt::Display + serde::Serialize
+{
+    /// AddVar message.
+    fn add_var_message(self, label: String) -> ToGuiLoopMessage;
+
+    /// AddRangedVar message.
+    fn add_ranged_var_message(self, label: String, min_max: (Self, Self)) -> ToGuiLoopMessage;
+
+    /// UpdateRangedValue message
+    fn update_range_value_message(self, label: String) -> FromGuiLoopMessage;
+
+    /// UpdateVar message.
+    fn update_var_message(self, label: String) -> ToGuiLoopMessage;
+}
+
+impl Number for usize {
+    fn add_var_message(self, label: String) -> ToGuiLoopMessage {
+        ToGuiLoopMessage::AddVarUSize(AddVar::<usize> { label, value: self })
+    }
+
+    fn add_ranged_var_message(self, label: String, min_max: (usize, usize)) -> ToGuiLoopMessage {
+        ToGuiLoopMessage::AddRangedVarUSize(AddRangedVar::<usize> {
+            label,
+            min_max,
+            value: self,
+        })
+    }
+
+    fn update_range_value_message(self, label: String) -> FromGuiLoopMessage {
+        FromGuiLoopMessage::UpdateRangedValueUSize(UpdateRangedValue { label, value: self })
+    }
+
+    fn update_var_message(self, label: String) -> ToGuiLoopMessage {
+        ToGuiLoopMessage::UpdateVarUSize(UpdateVar::<usize> { label, value: self })
+    }
+}
+
+impl Number for i32 {
+    fn add_var_message(self, label: String) -> ToGuiLoopMessage {
+        ToGuiLoopMessage::AddVarI32(AddVar::<i32> { label, value: self })
+    }
+
+    fn add_ranged_var_message(self, label: String, min_max: (i32, i32)) -> ToGuiLoopMessage {
+        ToGuiLoopMessage::AddRangedVarI32(AddRangedVar::<i32> {
+            label,
+            min_max,
+            value: self,
+        })
+    }
+
+    fn update_range_value_message(self, label: String) -> FromGuiLoopMessage {
+        FromGuiLoopMessage::UpdateRangedValueI32(UpdateRangedValue { label, value: self })
+    }
+
+    fn update_var_message(self, label: String) -> ToGuiLoopMessage {
+        ToGuiLoopMessage::UpdateVarI32(UpdateVar::<i32> { label, value: self })
+    }
+}
+
+impl Number for i64 {
+    fn add_var_message(self, label: String) -> ToGuiLoopMessage {
+        ToGuiLoopMessage::AddVarI64(AddVar::<i64> { label, value: self })
+    }
+
+    fn add_ranged_var_message(self, label: String, min_max: (i64, i64)) -> ToGuiLoopMessage {
+        ToGuiLoopMessage::AddRangedVarI64(AddRangedVar::<i64> {
+            label,
+            min_max,
+            value: self,
+        })
+    }
+
+    fn update_range_value_message(self, label: String) -> FromGuiLoopMessage {
+        FromGuiLoopMessage::UpdateRangedValueI64(UpdateRangedValue { label, value: self })
+    }
+
+    fn update_var_message(self, label: String) -> ToGuiLoopMessage {
+        ToGuiLoopMessage::UpdateVarI64(UpdateVar::<i64> { label, value: self })
+    }
+}
+
+impl Number for f32 {
+    fn add_var_message(self, label: String) -> ToGuiLoopMessage {
+        ToGuiLoopMessage::AddVarF32(AddVar::<f32> { label, value: self })
+    }
+
+    fn add_ranged_var_message(self, label: String, min_max: (f32, f32)) -> ToGuiLoopMessage {
+        ToGuiLoopMessage::AddRangedVarF32(AddRangedVar::<f32> {
+            label,
+            min_max,
+            value: self,
+        })
+    }
+
+    fn update_range_value_message(self, label: String) -> FromGuiLoopMessage {
+        FromGuiLoopMessage::UpdateRangedValueF32(UpdateRangedValue { label, value: self })
+    }
+
+    fn update_var_message(self, label: String) -> ToGuiLoopMessage {
+        ToGuiLoopMessage::UpdateVarF32(UpdateVar::<f32> { label, value: self })
+    }
+}
+
+impl Number for f64 {
+    fn add_var_message(self, label: String) -> ToGuiLoopMessage {
+        ToGuiLoopMessage::AddVarF64(AddVar::<f64> { label, value: self })
+    }
+
+    fn add_ranged_var_message(self, label: String, min_max: (f64, f64)) -> ToGuiLoopMessage {
+        ToGuiLoopMessage::AddRangedVarF64(AddRangedVar::<f64> {
+            label,
+            min_max,
+            value: self,
+        })
+    }
+
+    fn update_range_value_message(self, label: String) -> FromGuiLoopMessage {
+        FromGuiLoopMessage::UpdateRangedValueF64(UpdateRangedValue { label, value: self })
+    }
+
+    fn update_var_message(self, label: String) -> ToGuiLoopMessage {
+        ToGuiLoopMessage::UpdateVarF64(UpdateVar::<f64> { label, value: self })
+    }
+}
+
+/// Message from  [super::manager::Manager] to [super::gui::GuiLoop], such as to add a component or
+/// widget.
+#[derive(Serialize, Deserialize, Debug)]
+pub enum ToGuiLoopMessage {
+    /// enum combobox
+    AddEnumStringRepr(AddEnumStringRepr),
+    /// button
+    AddButton(AddButton),
+    /// button with confirmation dialog
+    AddConfirmButton(AddConfirmButton),
+    /// list of checkboxes
+    AddCheckboxList(AddCheckboxList),
+    /// bool checkbox
+    AddVarBool(AddVar<bool>),
+    /// usize textbox
+    AddVarUSize(AddVar<usize>),
+    /// i32 textbox
+    AddVarI32(AddVar<i32>),
+    /// i64 textbox
+    AddVarI64(AddVar<i64>),
+    /// f32 textbox
+    AddVarF32(AddVar<f32>),
+    /// f64 textbox
+    AddVarF64(AddVar<f64>),
+    /// usize slider
+    AddRangedVarUSize(AddRangedVar<usize>),
+    /// i32 textbox
+    AddRangedVarI32(AddRangedVar<i32>),
+    /// i64 textbox
+    AddRangedVarI64(AddRangedVar<i64>),
+    /// f32 textbox
+    AddRangedVarF32(AddRangedVar<f32>),
+    /// f64 textbox
+    AddRangedVarF64(AddRangedVar<f64>),
+    /// usize textbox update
+    UpdateVarUSize(UpdateVar<usize>),
+    /// i32 textbox update
+    UpdateVarI32(UpdateVar<i32>),
+    /// i64 textbox update
+    UpdateVarI64(UpdateVar<i64>),
+    /// f32 textbox update
+    UpdateVarF32(UpdateVar<f32>),
+    /// f64 textbox update
+    UpdateVarF64(UpdateVar<f64>),
+    /// 2d widget
+    AddWidget2(AddWidget2),
+    /// 3d widget
+    AddWidget3(AddWidget3),
+    /// place 3d entity
+    PlaceEntity3(PlaceEntity3),
+    /// delete component
+    DeleteComponent(DeleteComponent),
+    /// update pose of 3d entity
+    UpdateScenePoseEntity3(UpdateScenePoseEntity3),
+    /// update scale of 3d entity
+    UpdateSceneScaleEntity3(UpdateSceneScaleEntity3),
+    /// place 2d overlay
+    PlaceOverlay2(PlaceOverlay2),
+    /// remove 2d overlay
+    RemoveOverlay2(RemoveOverlay2),
+    /// set cull mode of 3d widget
+    SetCullMode(SetCullMode),
+    /// application thread panicked
+    AppThreadPanic(AppThreadPanic),
+    /// set cull mode of 3d entity
+    SetEntityCullMode(SetEntityCullMode),
+    /// save snapshot to file
+    SaveSnapshot(SaveSnapshot),
+    /// enable/disable frustum culling of 3d widget
+    SetFrustumCulling(SetFrustumCulling),
+    /// show a transient notification
+    Notify(Notify),
+    /// enable/disable sRGB output of 3d widget
+    SetSrgbOutput(SetSrgbOutput),
+    /// show/hide normal lines of all meshes of 3d widget
+    SetShowNormals(SetShowNormals),
+    /// set line width of 3d widget
+    SetLineWidth(SetLineWidth),
+    /// set rotation mode of 3d widget
+    SetRotationMode(SetRotationMode),
+    /// set aspect ratio of 3d widget
+    SetAspectRatio(SetAspectRatio),
+    /// show/hide world grid of 3d widget
+    SetWorldGrid(SetWorldGrid),
+    /// show/hide world axes of 3d widget
+    SetShowWorldAxes(SetShowWorldAxes),
+    /// orbit camera of 3d widget
+    OrbitCamera(OrbitCamera),
+    /// zoom camera of 3d widget
+    ZoomCamera(ZoomCamera),
+    /// pan camera of 3d widget
+    PanCamera(PanCamera),
+    /// set color theme
+    SetTheme(SetTheme),
+    /// update color of 3d entity
+    UpdateEntityColor(UpdateEntityColor),
+    /// set depth bias of 3d entity
+    SetEntityDepthBias(SetEntityDepthBias),
+    /// animate 3d entity; cannot be serialized, hence local mode only
+    #[serde(skip)]
+    AnimateEntity3(AnimateEntity3),
+    /// show/hide 3d entity
+    SetEntityVisible(SetEntityVisible),
+    /// show/hide the auto-generated entity list in the side panel
+    SetShowEntityList(SetShowEntityList),
+    /// save screenshot of whole window
+    SaveWindowPng(SaveWindowPng),
+    /// set field of view of 3d widget
+    SetFieldOfView(SetFieldOfView),
+    /// set near/far clipping planes of 3d widget
+    SetClipping(SetClipping),
+    /// append point to 3d path
+    AppendPathPoint(AppendPathPoint),
+    /// replace image of 2d widget
+    TryUpdateImage(TryUpdateImage),
+    /// add colorbar
+    AddColorbar(AddColorbar),
+    /// enable/disable measure mode of 3d widget
+    SetMeasureMode(SetMeasureMode),
+    /// show/hide the title bars of all widgets
+    SetShowWidgetTitles(SetShowWidgetTitles),
+    /// set opacity of 3d entity
+    SetEntityOpacity(SetEntityOpacity),
+    /// enable/disable split view of 3d widget
+    SetSplitView(SetSplitView),
+    /// add pose readout to side panel
+    AddPoseReadout(AddPoseReadout),
+    /// update pose of pose readout
+    UpdatePoseReadout(AddPoseReadout),
+    /// create group of 3d entities
+    CreateEntityGroup(CreateEntityGroup),
+    /// add 3d entity to group
+    AddToEntityGroup(AddToEntityGroup),
+    /// show/hide group of 3d entities
+    SetEntityGroupVisible(SetEntityGroupVisible),
+    /// set pose of group of 3d entities
+    SetEntityGroupPose(SetEntityGroupPose),
+    /// set value of component, e.g. when applying a preset
+    SetComponentValue(FromGuiLoopMessage),
+    /// remove 3d entity from widget
+    RemoveEntity3(RemoveEntity3),
+    /// set debug render mode of 3d widget
+    SetDebugMode(SetDebugMode),
+    /// draw 3d entity on top of all others
+    SetEntityOnTop(SetEntityOnTop),
+    /// place 2d entity
+    PlaceEntity2(PlaceEntity2),
+    /// enable/disable hover highlight of 3d widget
+    SetHoverHighlight(SetHoverHighlight),
+    /// set maximal frame rate of gui
+    SetMaxFrameRate(SetMaxFrameRate),
+    /// append points to 3d point cloud
+    AppendPoints(AppendPoints),
+    /// set number of columns of widget grid
+    SetGridColumns(SetGridColumns),
+    /// set fixed size of 2d widget
+    SetFixedSize2(SetFixedSize2),
+    /// remove all 3d entities from widget
+    ClearWidget3(ClearWidget3),
+    /// set camera pose of 3d widget
+    SetCameraPose(SetCameraPose),
+    /// set projection mode of 3d widget
+    SetProjectionMode(SetProjectionMode),
+    /// set background color of 3d widget
+    SetBackgroundColor(SetBackgroundColor),
+    /// enable/disable lighting of 3d widget
+    SetLighting(SetLighting),
+    /// reset camera of 3d widget to its initial pose
+    ResetCamera(ResetCamera),
+    /// set rotation pivot of 3d widget
+    SetRotationPivot(SetRotationPivot),
+    /// remove component from side panel
+    RemoveComponent(RemoveComponent),
+    /// enable/disable forwarding of input events
+    SetInputEventForwarding(SetInputEventForwarding),
+    /// set scale of text and spacing of gui
+    SetUiScale(SetUiScale),
+    /// preset combo box and buttons
+    AddPresetControls(AddPresetControls),
+}
+
+impl ToGuiLoopMessage {
+    /// How that component or widget shall be displayed.
+    ///
+    /// This only mutates [gui::GuiData]; GPU resources are created lazily when the widgets are
+    /// rendered. Hence, messages can be applied without a graphics context, e.g. in tests.
+    pub fn update_gui(self, data: &mut gui::GuiData) {
+        use ToGuiLoopMessage::*;
+
+        match self {
+            AddEnumStringRepr(e) => {
+                e.update_gui(data);
+            }
+            AddButton(e) => {
+                e.update_gui(data);
+            }
+            AddConfirmButton(e) => {
+                e.update_gui(data);
+            }
+            AddCheckboxList(e) => {
+                e.update_gui(data);
+            }
+            AddVarBool(e) => {
+                e.update_gui(data);
+            }
+            AddVarUSize(e) => {
+                e.update_gui(data);
+            }
+            AddVarI32(e) => {
+                e.update_gui(data);
+            }
+            AddVarI64(e) => {
+                e.update_gui(data);
+            }
+            AddVarF32(e) => {
+                e.update_gui(data);
+            }
+            AddVarF64(e) => {
+                e.update_gui(data);
+            }
+            AddRangedVarUSize(e) => {
+                e.update_gui(data);
+            }
+            AddRangedVarI32(e) => {
+                e.update_gui(data);
+            }
+            AddRangedVarI64(e) => {
+                e.update_gui(data);
+            }
+            AddRangedVarF32(e) => {
+                e.update_gui(data);
+            }
+            AddRangedVarF64(e) => {
+                e.update_gui(data);
+            }
+            UpdateVarUSize(e) => {
+                e.update_gui(data);
+            }
+            UpdateVarI32(e) => {
+                e.update_gui(data);
+            }
+            UpdateVarI64(e) => {
+                e.update_gui(data);
+            }
+            UpdateVarF32(e) => {
+                e.update_gui(data);
+            }
+            UpdateVarF64(e) => {
+                e.update_gui(data);
+            }
+            AddWidget2(e) => {
+                e.update_gui(data);
+            }
+            AddWidget3(e) => {
+                e.update_gui(data);
+            }
+            PlaceEntity3(e) => {
+                e.update_gui(data);
+            }
+            DeleteComponent(e) => {
+                e.update_gui(data);
+            }
+            UpdateScenePoseEntity3(e) => {
+                e.update_gui(data);
+            }
+            UpdateSceneScaleEntity3(e) => {
+                e.update_gui(data);
+            }
+            PlaceOverlay2(e) => {
+                e.update_gui(data);
+            }
+            RemoveOverlay2(e) => {
+                e.update_gui(data);
+            }
+            SetCullMode(e) => {
+                e.update_gui(data);
+            }
+            SetEntityCullMode(e) => {
+                e.update_gui(data);
+            }
+            AppThreadPanic(e) => {
+                e.update_gui(data);
+            }
+            SetFrustumCulling(e) => {
+                e.update_gui(data);
+            }
+            SaveSnapshot(e) => {
+                e.update_gui(data);
+            }
+            Notify(e) => {
+                e.update_gui(data);
+            }
+            SetSrgbOutput(e) => {
+                e.update_gui(data);
+            }
+            SetShowNormals(e) => {
+                e.update_gui(data);
+            }
+            SetLineWidth(e) => {
+                e.update_gui(data);
+            }
+            SetRotationMode(e) => {
+                e.update_gui(data);
+            }
+            SetAspectRatio(e) => {
+                e.update_gui(data);
+            }
+            SetWorldGrid(e) => {
+                e.update_gui(data);
+            }
+            SetShowWorldAxes(e) => {
+                e.update_gui(data);
+            }
+            OrbitCamera(e) => {
+                e.update_gui(data);
+            }
+            ZoomCamera(e) => {
+                e.update_gui(data);
+            }
+            PanCamera(e) => {
+                e.update_gui(data);
+            }
+            SetTheme(e) => {
+                e.update_gui(data);
+            }
+            UpdateEntityColor(e) => {
+                e.update_gui(data);
+            }
+            SetEntityDepthBias(e) => {
+                e.update_gui(data);
+            }
+            AnimateEntity3(e) => {
+                e.update_gui(data);
+            }
+            SetEntityVisible(e) => {
+                e.update_gui(data);
+            }
+            SetShowEntityList(e) => {
+                e.update_gui(data);
+            }
+            SaveWindowPng(e) => {
+                e.update_gui(data);
+            }
+            SetFieldOfView(e) => {
+                e.update_gui(data);
+            }
+            SetClipping(e) => {
+                e.update_gui(data);
+            }
+            AppendPathPoint(e) => {
+                e.update_gui(data);
+            }
+            TryUpdateImage(e) => {
+                e.update_gui(data);
+            }
+            AddColorbar(e) => {
+                e.update_gui(data);
+            }
+            SetMeasureMode(e) => {
+                e.update_gui(data);
+            }
+            SetShowWidgetTitles(e) => {
+                e.update_gui(data);
+            }
+            SetEntityOpacity(e) => {
+                e.update_gui(data);
+            }
+            SetSplitView(e) => {
+                e.update_gui(data);
+            }
+            AddPoseReadout(e) => {
+                e.update_gui(data);
+            }
+            UpdatePoseReadout(e) => {
+                e.update_pose_gui(data);
+            }
+            CreateEntityGroup(e) => {
+                e.update_gui(data);
+            }
+            AddToEntityGroup(e) => {
+                e.update_gui(data);
+            }
+            SetEntityGroupVisible(e) => {
+                e.update_gui(data);
+            }
+            SetEntityGroupPose(e) => {
+                e.update_gui(data);
+            }
+            SetComponentValue(e) => {
+                e.update(&mut data.components);
+            }
+            RemoveEntity3(e) => {
+                e.update_gui(data);
+            }
+            SetDebugMode(e) => {
+                e.update_gui(data);
+            }
+            SetEntityOnTop(e) => {
+                e.update_gui(data);
+            }
+            PlaceEntity2(e) => {
+                e.update_gui(data);
+            }
+            SetHoverHighlight(e) => {
+                e.update_gui(data);
+            }
+            SetMaxFrameRate(e) => {
+                e.update_gui(data);
+            }
+            AppendPoints(e) => {
+                e.update_gui(data);
+            }
+            SetGridColumns(e) => {
+                e.update_gui(data);
+            }
+            SetFixedSize2(e) => {
+                e.update_gui(data);
+            }
+            ClearWidget3(e) => {
+                e.update_gui(data);
+            }
+            SetCameraPose(e) => {
+                e.update_gui(data);
+            }
+            SetProjectionMode(e) => {
+                e.update_gui(data);
+            }
+            SetBackgroundColor(e) => {
+                e.update_gui(data);
+            }
+            SetLighting(e) => {
+                e.update_gui(data);
+            }
+            ResetCamera(e) => {
+                e.update_gui(data);
+            }
+            SetRotationPivot(e) => {
+                e.update_gui(data);
+            }
+            RemoveComponent(e) => {
+                e.update_gui(data);
+            }
+            SetInputEventForwarding(e) => {
+                e.update_gui(data);
+            }
+            SetUiScale(e) => {
+                e.update_gui(data);
+            }
+            AddPresetControls(e) => {
+                e.update_gui(data);
+            }
+        }
+    }
+}
+
+/// Add an enum (as string representation) as combo box to side panel.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AddEnumStringRepr {
+    /// The name of the enum.
+    pub label: String,
+    /// Initial value.
+    pub value: String,
+    /// List of possible values.
+    pub values: std::vec::Vec<String>,
+}
+
+impl AddEnumStringRepr {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.components.insert(
+            self.label,
+            Box::new(EnumStringRepr {
+                value: self.value,
+                values: self.values,
+            }),
+        );
+    }
+}
+
+/// To add a button to side panel.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AddButton {
+    /// The name of button.
+    pub label: String,
+}
+
+impl AddButton {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.components
+            .insert(self.label, Box::new(Button { pressed: false }));
+    }
+}
+
+/// To add a button with confirmation dialog to side panel.
+///
+/// Also see [ConfirmButton].
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AddConfirmButton {
+    /// The name of button.
+    pub label: String,
+    /// The question shown in the confirmation dialog.
+    pub prompt: String,
+}
+
+impl AddConfirmButton {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.components.insert(
+            self.label,
+            Box::new(ConfirmButton {
+                prompt: self.prompt,
+                dialog_open: false,
+            }),
+        );
+    }
+}
+
+/// To add preset controls to side panel, or to update the presets of existing ones.
+///
+/// Also see [PresetControls].
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AddPresetControls {
+    /// The name of the preset controls.
+    pub label: String,
+    /// Names of all presets.
+    pub presets: std::vec::Vec<String>,
+}
+
+impl AddPresetControls {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        let existing = data
+            .components
+            .get_mut(&self.label)
+            .and_then(|c| c.downcast_mut::<PresetControls>());
+        match existing {
+            Some(controls) => {
+                // Select a newly saved preset.
+                if self.presets.len() > controls.presets.len() {
+                    controls.selected = Some(self.presets.len() - 1);
+                }
+                controls.presets = self.presets;
+            }
+            None => {
+                data.components.insert(
+                    self.label,
+                    Box::new(PresetControls {
+                        presets: self.presets,
+                        selected: None,
+                    }),
+                );
+            }
+        }
+    }
+}
+
+/// To add a list of checkboxes to side panel.
+///
+/// Also see [CheckboxList].
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AddCheckboxList {
+    /// The name of the list.
+    pub label: String,
+    /// The items and whether they are initially checked, in display order.
+    pub items: std::vec::Vec<(String, bool)>,
+}
+
+impl AddCheckboxList {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.components
+            .insert(self.label, Box::new(CheckboxList { items: self.items }));
+    }
+}
+
+/// To add a color legend to side panel.
+///
+/// Also see [Colorbar].
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AddColorbar {
+    /// The name of the colorbar.
+    pub label: String,
+    /// The colormap.
+    pub colormap: colormap::Colormap,
+    /// The values mapped to the start and the end of the colormap.
+    pub range: (f32, f32),
+}
+
+impl AddColorbar {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.components.insert(
+            self.label,
+            Box::new(Colorbar {
+                colormap: self.colormap,
+                range: self.range,
+            }),
+        );
+    }
+}
+
+/// Add pose readout to side panel, or update its pose.
+///
+/// Also see [PoseReadout].
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AddPoseReadout {
+    /// The name of the readout.
+    pub label: String,
+    /// The pose to show.
+    pub pose: nalgebra::Isometry3<f32>,
+}
+
+impl AddPoseReadout {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.components
+            .insert(self.label, Box::new(PoseReadout { pose: self.pose }));
+    }
+
+    fn update_pose_gui(self, data: &mut gui::GuiData) {
+        data.components
+            .get_mut(&self.label)
+            .unwrap()
+            .downcast_mut::<PoseReadout>()
+            .unwrap()
+            .pose = self.pose;
+    }
+}
+
+/// Add bool (as checkbox) or numeric value (as read-only text box) to side panel.
+///
+/// Also see [Var].
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AddVar<T> {
+    /// The name of variable.
+    pub label: String,
+    /// The initial value.
+    pub value: T,
+}
+
+impl AddVar<bool> {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.components
+            .insert(self.label, Box::new(Var::<bool> { value: self.value }));
+    }
+}
+
+impl<T: Number> AddVar<T> {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.components
+            .insert(self.label, Box::new(Var::<T> { value: self.value }));
+    }
+}
+
+/// Update numeric value (read-only text box) in side panel.
+///
+/// Also see [Var].
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UpdateVar<T> {
+    /// The name of variable.
+    pub label: String,
+    /// The new value.
+    pub value: T,
+}
+
+impl<T: Number> UpdateVar<T> {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.components
+            .get_mut(&self.label)
+            .unwrap()
+            .downcast_mut::<Var<T>>()
+            .unwrap()
+            .value = self.value;
+    }
+}
+
+/// Add a numeric value as a slider to side panel.
+///
+/// Also see [RangedVar].
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AddRangedVar<T> {
+    /// Name of variable.
+    pub label: String,
+    /// Initial value.
+    pub value: T,
+    /// Min, max bounds
+    pub min_max: (T, T),
+}
+
+impl<T: Number> AddRangedVar<T> {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.components.insert(
+            self.label,
+            Box::new(RangedVar::<T> {
+                value: self.value,
+                min_max: self.min_max,
+            }),
+        );
+    }
+}
+
+/// Removes a component from the side panel, e.g. the opacity slider of a removed entity.
+///
+/// It is no-op, if a component with that name `label` does not exist.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RemoveComponent {
+    /// Name of component.
+    pub label: String,
+}
+
+impl RemoveComponent {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.components.remove(&self.label);
+    }
+}
+
+/// Enables or disables forwarding of keyboard and mouse events to the application, see
+/// [InputEvent]. It is disabled by default.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SetInputEventForwarding {
+    /// Whether events are forwarded.
+    pub enabled: bool,
+}
+
+impl SetInputEventForwarding {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.forward_input_events = self.enabled;
+    }
+}
+
+/// Scales the text and spacing of the gui on top of the DPI scale of the window, e.g. 1.5 for
+/// 50% larger panels on a presentation screen. The default is 1.0; see
+/// [super::manager::Manager::set_ui_scale].
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SetUiScale {
+    /// Scale factor.
+    pub scale: f32,
+}
+
+impl SetUiScale {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.ui_scale = self.scale;
+    }
+}
+
+/// u8 RGBA image
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ImageRgba8 {
+    /// raw bytes
+    pub bytes: Vec<u8>,
+    /// image width
+    pub width: u32,
+    /// image height
+    pub height: u32,
+}
+
+/// Adds [Widget2] to main panel, see [super::manager::Manager::add_widget2].
+///
+/// Example
+/// ```
+/// use vviz::common::*;
+///
+/// let mut data = vviz::gui::GuiData::default();
+/// let image = ImageRgba8 { bytes: vec![255; 2 * 2 * 4], width: 2, height: 2 };
+/// ToGuiLoopMessage::AddWidget2(AddWidget2 { label: "w2d".to_string(), image })
+///     .update_gui(&mut data);
+/// assert!(data.widgets["w2d"].downcast_ref::<Widget2>().is_some());
+/// ```
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AddWidget2 {
+    /// Name of widget
+    pub label: String,
+    /// Image to show in (background of) widget
+    pub image: ImageRgba8,
+}
+
+impl AddWidget2 {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        let widget = Widget2::from_image(&self.label, self.image);
+        data.widgets.insert(self.label, Box::new(widget));
+    }
+}
+
+/// Replaces the image of the corresponding [Widget2], e.g. to stream camera frames.
+///
+/// The texture is updated in place, hence the new image must have the same size as the original
+/// one. It is no-op otherwise.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TryUpdateImage {
+    /// Name of widget.
+    pub widget_label: String,
+    /// The new image.
+    pub image: ImageRgba8,
+}
+
+impl TryUpdateImage {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        let widget = data
+            .widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget2>()
+            .unwrap();
+        if widget.image_size != (self.image.width, self.image.height) {
+            // No-op.
+            return;
+        }
+        widget.pending_image = Some(self.image);
+    }
+}
+
+/// Adds [Widget3] to main panel.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AddWidget3 {
+    /// Name of widget
+    pub label: String,
+    /// Initial camera pose, i.e. the transformation from scene to camera. If None, the camera is
+    /// placed 4 units in front of the scene origin.
+    #[serde(default)]
+    pub camera_pose_scene: Option<nalgebra::Isometry3<f32>>,
+}
+
+impl AddWidget3 {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        let mut widget = Widget3::new();
+        widget.background_color = Theme::background_color(data.theme);
+        if let Some(camera_pose_scene) = self.camera_pose_scene {
+            widget.orbit_controller = camera::OrbitController::new(camera_pose_scene);
+        }
+        data.widgets.insert(self.label, Box::new(widget));
+    }
+}
+
+/// Place [super::entities::Entity3] in corresponding [Widget3].
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PlaceEntity3 {
+    /// Name of widget.
+    pub widget_label: String,
+    /// The 3d entity
+    pub named_entity: entities::NamedEntity3,
+}
+
+impl PlaceEntity3 {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        let widget = data
+            .widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget3>()
+            .unwrap();
+        widget.invalidate_entity(&self.named_entity.label);
+        widget
+            .entities
+            .insert(self.named_entity.label.clone(), self.named_entity);
+    }
+}
+
+/// Appends points to the point cloud [super::entities::Entity3] in corresponding [Widget3], see
+/// [super::entities::append_points].
+///
+/// If there is no entity with name `entity_label` yet, a point cloud is placed at the scene
+/// origin. It is a no-op if the entity is not a colored mesh.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AppendPoints {
+    /// Name of widget.
+    pub widget_label: String,
+    /// Name of entity.
+    pub entity_label: String,
+    /// Points to append, in entity coordinates.
+    pub points: std::vec::Vec<[f32; 3]>,
+    /// One color per point, or a single color for all points.
+    pub colors: std::vec::Vec<entities::Color>,
+    /// If set, the oldest points are dropped to keep at most that many.
+    pub max_points: Option<usize>,
+}
+
+impl AppendPoints {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        let widget = data
+            .widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget3>()
+            .unwrap();
+        let named_entity = widget
+            .entities
+            .entry(self.entity_label.clone())
+            .or_insert_with(|| entities::NamedEntity3 {
+                label: self.entity_label.clone(),
+                entity: entities::ColoredPoints3::default().into(),
+                scene_pose_entity: nalgebra::Isometry3::identity(),
+                scale: nalgebra::Vector3::repeat(1.0),
+                cull_mode: None,
+                depth_bias: 0.0,
+            });
+        if let entities::Entity3::Mesh(mesh) = &mut named_entity.entity {
+            entities::append_points(mesh, &self.points, &self.colors, self.max_points);
+            widget.invalidate_entity(&self.entity_label);
+        }
+    }
+}
+
+/// Removes [super::entities::Entity3] from corresponding [Widget3].
+///
+/// This is a no-op if there is no entity with name `entity_label`.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RemoveEntity3 {
+    /// Name of widget.
+    pub widget_label: String,
+    /// Name of entity.
+    pub entity_label: String,
+}
+
+impl RemoveEntity3 {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        let widget = data
+            .widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget3>()
+            .unwrap();
+        widget.invalidate_entity(&self.entity_label);
+        widget.entities.remove(&self.entity_label);
+    }
+}
+
+/// Removes all [super::entities::Entity3]s from corresponding [Widget3], and frees their GPU
+/// buffers.
+///
+/// Example
+/// ```
+/// use vviz::common::*;
+///
+/// let mut data = vviz::gui::GuiData::default();
+/// let w3d = "w3d".to_string();
+/// ToGuiLoopMessage::AddWidget3(AddWidget3 { label: w3d.clone(), camera_pose_scene: None })
+///     .update_gui(&mut data);
+/// let place = |label: &str| {
+///     ToGuiLoopMessage::PlaceEntity3(PlaceEntity3 {
+///         widget_label: "w3d".to_string(),
+///         named_entity: vviz::entities::NamedEntity3 {
+///             label: label.to_string(),
+///             entity: vviz::entities::colored_cube(1.0),
+///             scene_pose_entity: nalgebra::Isometry3::identity(),
+///             scale: nalgebra::Vector3::repeat(1.0),
+///             cull_mode: None,
+///             depth_bias: 0.0,
+///         },
+///     })
+/// };
+/// for label in ["a", "b", "c"] {
+///     place(label).update_gui(&mut data);
+/// }
+/// ToGuiLoopMessage::ClearWidget3(ClearWidget3 { widget_label: w3d.clone() })
+///     .update_gui(&mut data);
+/// place("d").update_gui(&mut data);
+/// let widget = data.widgets[&w3d].downcast_ref::<Widget3>().unwrap();
+/// assert_eq!(widget.entities().len(), 1);
+/// ```
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ClearWidget3 {
+    /// Name of widget.
+    pub widget_label: String,
+}
+
+impl ClearWidget3 {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        let widget = data
+            .widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget3>()
+            .unwrap();
+        let labels: std::vec::Vec<String> = widget.entities.keys().cloned().collect();
+        for label in labels {
+            widget.invalidate_entity(&label);
+        }
+        widget.entities.clear();
+    }
+}
+
+/// Appends a point to a path, i.e. a polyline [super::entities::LineSegments3], in corresponding
+/// [Widget3]; see [super::entities::LineSegments3::append_to_path].
+///
+/// If no entity with name `entity_label` exists, or if it is not a line segments entity, a new path
+/// is placed at the scene origin.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AppendPathPoint {
+    /// Name of widget.
+    pub widget_label: String,
+    /// Name of entity.
+    pub entity_label: String,
+    /// The new point, in the entity frame.
+    pub point: nalgebra::Vector3<f32>,
+    /// Color of the new point.
+    pub color: entities::Color,
+    /// Maximal number of retained points, if any. Older points are dropped.
+    pub max_points: Option<usize>,
+}
+
+impl AppendPathPoint {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        let widget = data
+            .widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget3>()
+            .unwrap();
+        widget.invalidate_entity(&self.entity_label);
+        let named_entity = widget
+            .entities
+            .entry(self.entity_label.clone())
+            .or_insert_with(|| entities::NamedEntity3 {
+                label: self.entity_label.clone(),
+                entity: entities::Entity3::LineSegments(entities::LineSegments3::default()),
+                scene_pose_entity: nalgebra::Isometry3::identity(),
+                scale: nalgebra::Vector3::repeat(1.0),
+                cull_mode: None,
+                depth_bias: 0.0,
+            });
+        if named_entity.entity.as_line_segments().is_none() {
+            named_entity.entity =
+                entities::Entity3::LineSegments(entities::LineSegments3::default());
+        }
+        named_entity
+            .entity
+            .as_line_segments_mut()
+            .unwrap()
+            .append_to_path(self.point, self.color, self.max_points);
+    }
+}
+
+/// Updates pose of [super::entities::Entity3] in corresponding [Widget3].
+///
+/// It is no-op, if an entity with that name `entity_label` does not exist.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UpdateScenePoseEntity3 {
+    /// Name of widget.
+    pub widget_label: String,
+    /// Name of entity.
+    pub entity_label: String,
+    /// Pose of the entity in the scene.
+    pub scene_pose_entity: nalgebra::Isometry3<f32>,
+}
+
+impl UpdateScenePoseEntity3 {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        let maybe_entity = data
+            .widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget3>()
+            .unwrap()
+            .entities
+            .get_mut(&self.entity_label);
+        if maybe_entity.is_none() {
+            // No-op.
+            return;
+        }
+        maybe_entity.unwrap().scene_pose_entity = self.scene_pose_entity;
+    }
+}
+
+/// Updates (non-uniform) scale of [super::entities::Entity3] in corresponding [Widget3].
+///
+/// It is no-op, if an entity with that name `entity_label` does not exist.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UpdateSceneScaleEntity3 {
+    /// Name of widget.
+    pub widget_label: String,
+    /// Name of entity.
+    pub entity_label: String,
+    /// Scale along the x, y and z axis of the entity.
+    pub scale: nalgebra::Vector3<f32>,
+}
+
+impl UpdateSceneScaleEntity3 {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        let maybe_entity = data
+            .widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget3>()
+            .unwrap()
+            .entities
+            .get_mut(&self.entity_label);
+        if maybe_entity.is_none() {
+            // No-op.
+            return;
+        }
+        maybe_entity.unwrap().scale = self.scale;
+    }
+}
+
+/// Updates the color of [super::entities::Entity3] in corresponding [Widget3], without replacing
+/// its geometry.
+///
+/// It is no-op, if an entity with that name `entity_label` does not exist.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UpdateEntityColor {
+    /// Name of widget.
+    pub widget_label: String,
+    /// Name of entity.
+    pub entity_label: String,
+    /// The new color of all vertices.
+    pub color: entities::Color,
+}
+
+impl UpdateEntityColor {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        let widget = data
+            .widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget3>()
+            .unwrap();
+        let maybe_entity = widget.entities.get_mut(&self.entity_label);
+        if maybe_entity.is_none() {
+            // No-op.
+            return;
+        }
+        maybe_entity.unwrap().entity.set_color(self.color);
+        if let Some(Some((_, quads))) = widget.thick_line_buffers.remove(&self.entity_label) {
+            widget.stale_buffers.push(quads);
+        }
+        // The geometry is unchanged, hence bounds and normals are kept. Only the vertices are
+        // uploaded again - unless the mesh is drawn converted by the debug mode.
+        if widget.debug_mode == DebugMode::Normal {
+            if let Some(Some(_)) = widget.entity_buffers.get(&self.entity_label) {
+                widget.recolored_entities.insert(self.entity_label);
+            }
+        } else {
+            widget.debug_entities.remove(&self.entity_label);
+            if let Some(Some(buffers)) = widget.entity_buffers.remove(&self.entity_label) {
+                widget.stale_buffers.push(buffers);
+            }
+        }
+    }
+}
+
+/// Place 2d line segments as overlay in corresponding [Widget2].
+///
+/// If an overlay with such `label` already exists, it will be replaced.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PlaceOverlay2 {
+    /// Name of widget.
+    pub widget_label: String,
+    /// Name of overlay.
+    pub label: String,
+    /// The line segments in image pixel coordinates.
+    pub segments: entities::LineSegments2,
+}
+
+impl PlaceOverlay2 {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget2>()
+            .unwrap()
+            .entities
+            .insert(self.label, entities::Entity2::LineSegments(self.segments));
+    }
+}
+
+/// Removes overlay from corresponding [Widget2].
+///
+/// It is no-op, if an overlay with that name `label` does not exist.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RemoveOverlay2 {
+    /// Name of widget.
+    pub widget_label: String,
+    /// Name of overlay.
+    pub label: String,
+}
+
+impl RemoveOverlay2 {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget2>()
+            .unwrap()
+            .entities
+            .remove(&self.label);
+    }
+}
+
+/// Shows the corresponding [Widget2] at a fixed size in physical screen pixels, with scroll bars
+/// if it exceeds the assigned space; e.g. at the image size for 1:1 display. If `size` is None,
+/// the widget is fitted to the assigned space, which is the default.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SetFixedSize2 {
+    /// Name of widget.
+    pub widget_label: String,
+    /// Width and height in physical screen pixels.
+    pub size: Option<(u32, u32)>,
+}
+
+impl SetFixedSize2 {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget2>()
+            .unwrap()
+            .fixed_size = self.size;
+    }
+}
+
+/// Place [super::entities::Entity2] in corresponding [Widget2].
+///
+/// If an entity with such `label` already exists, it will be replaced.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PlaceEntity2 {
+    /// Name of widget.
+    pub widget_label: String,
+    /// Name of entity.
+    pub label: String,
+    /// The entity in image pixel coordinates.
+    pub entity: entities::Entity2,
+    /// If true, the entity is only replaced, i.e. it is no-op if no entity named `label` exists.
+    pub update_only: bool,
+}
+
+impl PlaceEntity2 {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        let widget_entities = &mut data
+            .widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget2>()
+            .unwrap()
+            .entities;
+        match widget_entities.get_mut(&self.label) {
+            Some(entity) => *entity = self.entity,
+            None if self.update_only => {}
+            None => {
+                widget_entities.insert(self.label, self.entity);
+            }
+        }
+    }
+}
+
+/// Sets the [CullMode] of the corresponding [Widget3].
+///
+/// It applies to all meshes of the widget which do not override it.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SetCullMode {
+    /// Name of widget.
+    pub widget_label: String,
+    /// The cull mode.
+    pub cull_mode: CullMode,
+}
+
+impl SetCullMode {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget3>()
+            .unwrap()
+            .cull_mode = self.cull_mode;
+    }
+}
+
+/// Enables or disables frustum culling of the corresponding [Widget3].
+///
+/// If enabled (default), entities whose bounding box is fully outside the view frustum are not
+/// drawn.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SetFrustumCulling {
+    /// Name of widget.
+    pub widget_label: String,
+    /// Whether frustum culling is enabled.
+    pub enabled: bool,
+}
+
+impl SetFrustumCulling {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget3>()
+            .unwrap()
+            .frustum_culling = self.enabled;
+    }
+}
+
+/// Enables or disables sRGB encoding of the output colors of the corresponding [Widget3].
+///
+/// If enabled, the entity colors are considered linear and are gamma-encoded to sRGB; if disabled
+/// (default), they are written unchanged, consistent with 2d widgets.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SetSrgbOutput {
+    /// Name of widget.
+    pub widget_label: String,
+    /// Whether sRGB encoding is enabled.
+    pub enabled: bool,
+}
+
+impl SetSrgbOutput {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget3>()
+            .unwrap()
+            .srgb_output = self.enabled;
+    }
+}
+
+/// Shows or hides the vertex normals of all meshes in the corresponding [Widget3].
+///
+/// See [super::entities::normal_lines].
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SetShowNormals {
+    /// Name of widget.
+    pub widget_label: String,
+    /// Length of the normal lines; None to hide them.
+    pub length: Option<f32>,
+}
+
+impl SetShowNormals {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        let widget = data
+            .widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget3>()
+            .unwrap();
+        widget.normals_length = self.length;
+        widget.normal_lines.clear();
+    }
+}
+
+/// Enables/disables outlining the bounding box of the entity under the mouse cursor in the
+/// corresponding [Widget3]; enabled by default. See [Widget3::hovered_entity].
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SetHoverHighlight {
+    /// Name of widget.
+    pub widget_label: String,
+    /// Whether the hovered entity is outlined.
+    pub enabled: bool,
+}
+
+impl SetHoverHighlight {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget3>()
+            .unwrap()
+            .hover_highlight = self.enabled;
+    }
+}
+
+/// Sets the [DebugMode] of the corresponding [Widget3].
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SetDebugMode {
+    /// Name of widget.
+    pub widget_label: String,
+    /// The render mode.
+    pub debug_mode: DebugMode,
+}
+
+impl SetDebugMode {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        let widget = data
+            .widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget3>()
+            .unwrap();
+        widget.debug_mode = self.debug_mode;
+        widget.debug_entities.clear();
+        widget.recolored_entities.clear();
+        // Meshes are drawn converted in some debug modes.
+        let buffers = widget
+            .entity_buffers
+            .drain()
+            .filter_map(|(_, buffers)| buffers);
+        widget.stale_buffers.extend(buffers);
+        let quads = widget
+            .thick_line_buffers
+            .drain()
+            .filter_map(|(_, quads)| quads.map(|(_, quads)| quads));
+        widget.stale_buffers.extend(quads);
+    }
+}
+
+/// Sets the [LineWidth] of all line segments in the corresponding [Widget3].
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SetLineWidth {
+    /// Name of widget.
+    pub widget_label: String,
+    /// The line width.
+    pub line_width: LineWidth,
+}
+
+impl SetLineWidth {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget3>()
+            .unwrap()
+            .line_width = self.line_width.clamped();
+    }
+}
+
+/// Sets the [RotationMode] of the corresponding [Widget3].
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SetRotationMode {
+    /// Name of widget.
+    pub widget_label: String,
+    /// The rotation mode.
+    pub rotation_mode: RotationMode,
+}
+
+impl SetRotationMode {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget3>()
+            .unwrap()
+            .orbit_controller
+            .set_rotation_mode(self.rotation_mode);
+    }
+}
+
+/// Sets the aspect ratio (width / height) of the corresponding [Widget3].
+///
+/// It determines both the projection and the size of the widget on screen. The default is 4:3.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SetAspectRatio {
+    /// Name of widget.
+    pub widget_label: String,
+    /// The aspect ratio; must be positive.
+    pub aspect_ratio: f32,
+}
+
+impl SetAspectRatio {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget3>()
+            .unwrap()
+            .aspect_ratio = self.aspect_ratio;
+    }
+}
+
+/// Enables/disables the measure mode of the corresponding [Widget3].
+///
+/// In measure mode, two clicks on entities measure the distance between the two clicked scene
+/// points, which is shown as line with a label and reported as [UpdateMeasurement3]. Clicking on
+/// the background cancels the measurement. Disabling the mode removes the measurement.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SetMeasureMode {
+    /// Name of widget.
+    pub widget_label: String,
+    /// Whether measure mode is enabled.
+    pub enabled: bool,
+}
+
+impl SetMeasureMode {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        let widget = data
+            .widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget3>()
+            .unwrap();
+        widget.measure_mode = self.enabled;
+        if !self.enabled {
+            widget.measure_start = None;
+            widget.measurement = None;
+        }
+    }
+}
+
+/// Enables/disables the split view of the corresponding [Widget3].
+///
+/// In split view, the scene is rendered twice side by side: on the left as seen from the primary
+/// camera, and on the right as seen from a second camera with pose `split_camera_pose_scene`.
+/// Mouse interaction only moves the primary camera.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SetSplitView {
+    /// Name of widget.
+    pub widget_label: String,
+    /// Pose of the second camera - i.e. the transformation from scene to camera; None disables
+    /// the split view.
+    pub split_camera_pose_scene: Option<nalgebra::Isometry3<f32>>,
+}
+
+impl SetSplitView {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget3>()
+            .unwrap()
+            .split_camera_pose_scene = self.split_camera_pose_scene;
+    }
+}
+
+/// Sets the vertical field of view of the corresponding [Widget3]. The default is 60 degrees.
+///
+/// The horizontal field of view follows from the aspect ratio, see [SetAspectRatio]. This switches
+/// to [ProjectionMode::Perspective].
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SetFieldOfView {
+    /// Name of widget.
+    pub widget_label: String,
+    /// The vertical field of view in degrees; must be in (0, 180).
+    pub fov_y_degrees: f32,
+}
+
+impl SetFieldOfView {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget3>()
+            .unwrap()
+            .projection_mode = ProjectionMode::Perspective {
+            fov_deg: self.fov_y_degrees,
+        };
+    }
+}
+
+/// Sets the [ProjectionMode] of the corresponding [Widget3]. The default is a perspective
+/// projection with a vertical field of view of 60 degrees.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SetProjectionMode {
+    /// Name of widget.
+    pub widget_label: String,
+    /// The projection mode.
+    pub projection_mode: ProjectionMode,
+}
+
+impl SetProjectionMode {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget3>()
+            .unwrap()
+            .projection_mode = self.projection_mode;
+    }
+}
+
+/// Sets the near and far clipping planes of the corresponding [Widget3]. The default is
+/// (0.01, 100.0).
+///
+/// Geometry closer than `near` or farther than `far` from the camera is not rendered.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SetClipping {
+    /// Name of widget.
+    pub widget_label: String,
+    /// Distance of near plane; must be positive.
+    pub near: f32,
+    /// Distance of far plane; must be larger than `near`.
+    pub far: f32,
+}
+
+impl SetClipping {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget3>()
+            .unwrap()
+            .clipping = (self.near, self.far);
+    }
+}
+
+/// Shows or hides the [WorldGrid] of the corresponding [Widget3].
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SetWorldGrid {
+    /// Name of widget.
+    pub widget_label: String,
+    /// The grid; None to hide it.
+    pub grid: Option<WorldGrid>,
+}
+
+impl SetWorldGrid {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget3>()
+            .unwrap()
+            .world_grid = self.grid.map(|grid| grid.clamped().line_segments());
+    }
+}
+
+/// Shows or hides a coordinate axis at the origin of the corresponding [Widget3].
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SetShowWorldAxes {
+    /// Name of widget.
+    pub widget_label: String,
+    /// Whether the axes are shown.
+    pub enabled: bool,
+}
+
+impl SetShowWorldAxes {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget3>()
+            .unwrap()
+            .world_axes = if self.enabled {
+            entities::Entity3::from(entities::Axis3::default())
+                .into_line_segments()
+                .ok()
+        } else {
+            None
+        };
+    }
+}
+
+/// Sets the camera pose of the corresponding [Widget3], e.g. to fly along a scripted path.
+///
+/// Mouse interaction continues from the new pose, and the projection is not changed.
+///
+/// Example
+/// ```
+/// use vviz::common::*;
+///
+/// let mut data = vviz::gui::GuiData::default();
+/// let w3d = "w3d".to_string();
+/// ToGuiLoopMessage::AddWidget3(AddWidget3 { label: w3d.clone(), camera_pose_scene: None })
+///     .update_gui(&mut data);
+/// let scene_pose_camera = nalgebra::Isometry3::translation(1.0, 2.0, 10.0);
+/// ToGuiLoopMessage::SetCameraPose(SetCameraPose { widget_label: w3d.clone(), scene_pose_camera })
+///     .update_gui(&mut data);
+/// let widget = data.widgets[&w3d].downcast_ref::<Widget3>().unwrap();
+/// assert_eq!(widget.camera_pose_scene(), scene_pose_camera.inverse());
+/// ```
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SetCameraPose {
+    /// Name of widget.
+    pub widget_label: String,
+    /// Pose of the camera in the scene - i.e. the transformation from camera to scene, which is
+    /// the inverse of [Widget3::camera_pose_scene].
+    pub scene_pose_camera: nalgebra::Isometry3<f32>,
+}
+
+impl SetCameraPose {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget3>()
+            .unwrap()
+            .orbit_controller
+            .set_camera_pose_scene(self.scene_pose_camera.inverse());
+    }
+}
+
+/// Rotates the scene of the corresponding [Widget3], as dragging with the primary mouse button
+/// does.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct OrbitCamera {
+    /// Name of widget.
+    pub widget_label: String,
+    /// Rotation about the vertical axis in radians.
+    pub yaw: f32,
+    /// Rotation about the horizontal axis in radians.
+    pub pitch: f32,
+}
+
+impl OrbitCamera {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget3>()
+            .unwrap()
+            .orbit_controller
+            .rotate(nalgebra::Vector2::new(self.yaw, self.pitch));
+    }
+}
+
+/// Moves the camera of the corresponding [Widget3] towards or away from the rotation pivot, see
+/// [SetRotationPivot]; or, with an orthographic [ProjectionMode], shrinks or grows the view.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ZoomCamera {
+    /// Name of widget.
+    pub widget_label: String,
+    /// Zooms in if greater than one, and out if less than one.
+    pub factor: f32,
+}
+
+impl ZoomCamera {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget3>()
+            .unwrap()
+            .zoom(self.factor);
+    }
+}
+
+/// Moves the camera of the corresponding [Widget3] in its image plane, as dragging with the
+/// secondary mouse button does.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PanCamera {
+    /// Name of widget.
+    pub widget_label: String,
+    /// Horizontal offset in scene units.
+    pub dx: f32,
+    /// Vertical offset in scene units.
+    pub dy: f32,
+}
+
+impl PanCamera {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget3>()
+            .unwrap()
+            .orbit_controller
+            .pan(nalgebra::Vector2::new(self.dx, self.dy));
+    }
+}
+
+/// Moves the camera of the corresponding [Widget3] back to its initial pose, as double-clicking
+/// the widget does; see [camera::OrbitController::reset].
+///
+/// Example
+/// ```
+/// use vviz::common::*;
+///
+/// let mut data = vviz::gui::GuiData::default();
+/// let w3d = "w3d".to_string();
+/// ToGuiLoopMessage::AddWidget3(AddWidget3 { label: w3d.clone(), camera_pose_scene: None })
+///     .update_gui(&mut data);
+/// let initial_pose = data.widgets[&w3d].downcast_ref::<Widget3>().unwrap().camera_pose_scene();
+/// ToGuiLoopMessage::OrbitCamera(OrbitCamera { widget_label: w3d.clone(), yaw: 1.0, pitch: 0.5 })
+///     .update_gui(&mut data);
+/// ToGuiLoopMessage::PanCamera(PanCamera { widget_label: w3d.clone(), dx: 2.0, dy: 0.0 })
+///     .update_gui(&mut data);
+/// ToGuiLoopMessage::ResetCamera(ResetCamera { widget_label: w3d.clone() }).update_gui(&mut data);
+/// let widget = data.widgets[&w3d].downcast_ref::<Widget3>().unwrap();
+/// assert_eq!(widget.camera_pose_scene(), initial_pose);
+/// assert_eq!(initial_pose.translation.vector, nalgebra::Vector3::new(0.0, 0.0, -4.0));
+/// ```
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ResetCamera {
+    /// Name of widget.
+    pub widget_label: String,
+}
+
+impl ResetCamera {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget3>()
+            .unwrap()
+            .orbit_controller
+            .reset();
+    }
+}
+
+/// Sets the point the camera of the corresponding [Widget3] rotates about and zooms towards, e.g.
+/// to inspect an object away from the scene origin; see
+/// [camera::OrbitController::set_scene_point_orbit_center].
+///
+/// Example
+/// ```
+/// use vviz::common::*;
+///
+/// let mut data = vviz::gui::GuiData::default();
+/// let w3d = "w3d".to_string();
+/// ToGuiLoopMessage::AddWidget3(AddWidget3 { label: w3d.clone(), camera_pose_scene: None })
+///     .update_gui(&mut data);
+/// let pivot = nalgebra::Vector3::new(1.0, 2.0, 0.0);
+/// ToGuiLoopMessage::SetRotationPivot(SetRotationPivot { widget_label: w3d.clone(), pivot })
+///     .update_gui(&mut data);
+/// let camera_point_pivot = |data: &vviz::gui::GuiData| {
+///     let widget = data.widgets[&w3d].downcast_ref::<Widget3>().unwrap();
+///     widget.camera_pose_scene() * nalgebra::Point3::from(pivot)
+/// };
+/// let before = camera_point_pivot(&data);
+/// ToGuiLoopMessage::OrbitCamera(OrbitCamera { widget_label: w3d.clone(), yaw: 1.0, pitch: 0.5 })
+///     .update_gui(&mut data);
+/// // The pivot stays in place on screen.
+/// assert!((camera_point_pivot(&data) - before).norm() < 1e-5);
+/// ```
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SetRotationPivot {
+    /// Name of widget.
+    pub widget_label: String,
+    /// The pivot in scene coordinates.
+    pub pivot: nalgebra::Vector3<f32>,
+}
+
+impl SetRotationPivot {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget3>()
+            .unwrap()
+            .orbit_controller
+            .set_scene_point_orbit_center(nalgebra::Point3::from(self.pivot));
+    }
+}
+
+/// Sets the [CullMode] of [super::entities::Entity3] in corresponding [Widget3].
+///
+/// If `cull_mode` is None, the entity falls back to the cull mode of the widget. It is no-op, if
+/// an entity with that name `entity_label` does not exist.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SetEntityCullMode {
+    /// Name of widget.
+    pub widget_label: String,
+    /// Name of entity.
+    pub entity_label: String,
+    /// The cull mode.
+    pub cull_mode: Option<CullMode>,
+}
+
+impl SetEntityCullMode {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        let maybe_entity = data
+            .widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget3>()
+            .unwrap()
+            .entities
+            .get_mut(&self.entity_label);
+        if maybe_entity.is_none() {
+            // No-op.
+            return;
+        }
+        maybe_entity.unwrap().cull_mode = self.cull_mode;
+    }
+}
+
+/// Sets the depth bias of [super::entities::Entity3] in corresponding [Widget3].
+///
+/// The bias is an offset in normalized device depth (range [-1..1]); positive values move the
+/// entity towards the camera. A small bias such as `1e-4` lets overlays, e.g. a wireframe on a
+/// mesh face, render on top of coplanar geometry without z-fighting. It is no-op, if an entity with
+/// that name `entity_label` does not exist.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SetEntityDepthBias {
+    /// Name of widget.
+    pub widget_label: String,
+    /// Name of entity.
+    pub entity_label: String,
+    /// The depth bias.
+    pub depth_bias: f32,
+}
+
+impl SetEntityDepthBias {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        let maybe_entity = data
+            .widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget3>()
+            .unwrap()
+            .entities
+            .get_mut(&self.entity_label);
+        if maybe_entity.is_none() {
+            // No-op.
+            return;
+        }
+        maybe_entity.unwrap().depth_bias = self.depth_bias;
+    }
+}
+
+/// Shows or hides [super::entities::Entity3] in corresponding [Widget3].
+///
+/// The visibility is kept by the widget, also if an entity with name `entity_label` does not
+/// exist (yet) or is placed again.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SetEntityVisible {
+    /// Name of widget.
+    pub widget_label: String,
+    /// Name of entity.
+    pub entity_label: String,
+    /// Whether the entity is shown.
+    pub visible: bool,
+}
+
+impl SetEntityVisible {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        let hidden_entities = &mut data
+            .widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget3>()
+            .unwrap()
+            .hidden_entities;
+        if self.visible {
+            hidden_entities.remove(&self.entity_label);
+        } else {
+            hidden_entities.insert(self.entity_label);
+        }
+    }
+}
+
+/// Draws [super::entities::Entity3] in corresponding [Widget3] on top of all other entities, e.g.
+/// for gizmos or the current robot pose.
+///
+/// Like the visibility, the flag is kept by the widget, also if the entity does not exist (yet).
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SetEntityOnTop {
+    /// Name of widget.
+    pub widget_label: String,
+    /// Name of entity.
+    pub entity_label: String,
+    /// Whether the entity is drawn on top.
+    pub on_top: bool,
+}
+
+impl SetEntityOnTop {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        let on_top_entities = &mut data
+            .widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget3>()
+            .unwrap()
+            .on_top_entities;
+        if self.on_top {
+            on_top_entities.insert(self.entity_label);
+        } else {
+            on_top_entities.remove(&self.entity_label);
+        }
+    }
+}
+
+/// Sets the opacity of [super::entities::Entity3] in corresponding [Widget3], which multiplies the
+/// alpha of its vertex colors.
+///
+/// Entities are blended in placement order and without sorting, and also transparent entities
+/// occlude what is drawn after them. Hence, place transparent entities last. As for
+/// [SetEntityVisible], the opacity is kept if an entity is placed again.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SetEntityOpacity {
+    /// Name of widget.
+    pub widget_label: String,
+    /// Name of entity.
+    pub entity_label: String,
+    /// The opacity in [0..1]; 1 is opaque.
+    pub opacity: f32,
+}
+
+impl SetEntityOpacity {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget3>()
+            .unwrap()
+            .entity_opacities
+            .insert(self.entity_label, self.opacity.clamp(0.0, 1.0));
     }
 }
 
-/// To add a button to side panel.
+/// Creates an [EntityGroup] in corresponding [Widget3], replacing an existing group of the same
+/// name.
 #[derive(Serialize, Deserialize, Debug)]
-pub struct AddButton {
-    /// The name of button.
-    pub label: String,
+pub struct CreateEntityGroup {
+    /// Name of widget.
+    pub widget_label: String,
+    /// Name of group.
+    pub group_label: String,
+    /// The group; None for an empty, visible group at the origin.
+    pub group: Option<EntityGroup>,
 }
 
-impl AddButton {
-    fn update_gui(self, data: &mut gui::GuiData, _ctx: &mut miniquad::Context) {
-        data.components
-            .insert(self.label, Box::new(Button { pressed: false }));
+impl CreateEntityGroup {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget3>()
+            .unwrap()
+            .groups
+            .insert(self.group_label, self.group.unwrap_or_default());
     }
 }
 
-/// Add bool (as checkbox) or numeric value (as read-only text box) to side panel.
+/// Adds an entity to an [EntityGroup] of corresponding [Widget3], removing it from any other
+/// group.
 ///
-/// Also see [Var].
+/// The entity does not need to exist (yet). This is a no-op if there is no group with name
+/// `group_label`.
 #[derive(Serialize, Deserialize, Debug)]
-pub struct AddVar<T> {
-    /// The name of variable.
-    pub label: String,
-    /// The initial value.
-    pub value: T,
+pub struct AddToEntityGroup {
+    /// Name of widget.
+    pub widget_label: String,
+    /// Name of group.
+    pub group_label: String,
+    /// Name of entity.
+    pub entity_label: String,
 }
 
-impl AddVar<bool> {
-    fn update_gui(self, data: &mut gui::GuiData, _ctx: &mut miniquad::Context) {
-        data.components
-            .insert(self.label, Box::new(Var::<bool> { value: self.value }));
+impl AddToEntityGroup {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        let groups = &mut data
+            .widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget3>()
+            .unwrap()
+            .groups;
+        if !groups.contains_key(&self.group_label) {
+            // No-op.
+            return;
+        }
+        for group in groups.values_mut() {
+            group.members.remove(&self.entity_label);
+        }
+        groups
+            .get_mut(&self.group_label)
+            .unwrap()
+            .members
+            .insert(self.entity_label);
     }
 }
 
-impl<T: Number> AddVar<T> {
-    fn update_gui(self, data: &mut gui::GuiData, _ctx: &mut miniquad::Context) {
-        data.components
-            .insert(self.label, Box::new(Var::<T> { value: self.value }));
+/// Shows or hides all members of an [EntityGroup] of corresponding [Widget3].
+///
+/// A member is shown if both the group and the entity itself, see [SetEntityVisible], are
+/// visible. This is a no-op if there is no group with name `group_label`.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SetEntityGroupVisible {
+    /// Name of widget.
+    pub widget_label: String,
+    /// Name of group.
+    pub group_label: String,
+    /// Whether the members are shown.
+    pub visible: bool,
+}
+
+impl SetEntityGroupVisible {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        if let Some(group) = data
+            .widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget3>()
+            .unwrap()
+            .groups
+            .get_mut(&self.group_label)
+        {
+            group.visible = self.visible;
+        }
     }
 }
 
-/// Add a numeric value as a slider to side panel.
+/// Sets the pose of an [EntityGroup] of corresponding [Widget3], i.e. the parent transform of its
+/// members.
 ///
-/// Also see [RangedVar].
+/// This is a no-op if there is no group with name `group_label`.
 #[derive(Serialize, Deserialize, Debug)]
-pub struct AddRangedVar<T> {
-    /// Name of variable.
-    pub label: String,
-    /// Initial value.
-    pub value: T,
-    /// Min, max bounds
-    pub min_max: (T, T),
+pub struct SetEntityGroupPose {
+    /// Name of widget.
+    pub widget_label: String,
+    /// Name of group.
+    pub group_label: String,
+    /// Pose of the group in the scene.
+    pub scene_pose_group: nalgebra::Isometry3<f32>,
 }
 
-impl<T: Number> AddRangedVar<T> {
-    fn update_gui(self, data: &mut gui::GuiData, _ctx: &mut miniquad::Context) {
-        data.components.insert(
-            self.label,
-            Box::new(RangedVar::<T> {
-                value: self.value,
-                min_max: self.min_max,
-            }),
-        );
+impl SetEntityGroupPose {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        if let Some(group) = data
+            .widgets
+            .get_mut(&self.widget_label)
+            .unwrap()
+            .downcast_mut::<Widget3>()
+            .unwrap()
+            .groups
+            .get_mut(&self.group_label)
+        {
+            group.scene_pose_group = self.scene_pose_group;
+        }
     }
 }
 
-/// u8 RGBA image
+/// Enables/disables the list of entities of all [Widget3]s in the side panel, which lets the user
+/// toggle their visibility. It is enabled by default.
 #[derive(Serialize, Deserialize, Debug)]
-pub struct ImageRgba8 {
-    /// raw bytes
-    pub bytes: Vec<u8>,
-    /// image width
-    pub width: u32,
-    /// image height
-    pub height: u32,
+pub struct SetShowEntityList {
+    /// Whether the list is shown.
+    pub enabled: bool,
 }
 
-/// Adds [Widget2] to main panel.
+impl SetShowEntityList {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.show_entity_list = self.enabled;
+    }
+}
+
+/// Shows or hides the title bars, i.e. the labels, above all widgets in the main panel; e.g. to
+/// take clean screenshots. They are shown by default.
 #[derive(Serialize, Deserialize, Debug)]
-pub struct AddWidget2 {
-    /// Name of widget
-    pub label: String,
-    /// Image to show in (background of) widget
-    pub image: ImageRgba8,
+pub struct SetShowWidgetTitles {
+    /// Whether the titles are shown.
+    pub enabled: bool,
 }
 
-impl AddWidget2 {
-    fn update_gui(self, data: &mut gui::GuiData, ctx: &mut miniquad::Context) {
-        data.widgets
-            .insert(self.label, Box::new(Widget2::from_image(ctx, self.image)));
+impl SetShowWidgetTitles {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.show_widget_titles = self.enabled;
     }
 }
 
-/// Adds [Widget3] to main panel.
+/// Limits the number of frames the gui draws per second, e.g. to save power on a laptop. If None,
+/// frames are only paced by vsync, which is the default; see
+/// [super::capabilities::Capabilities::vsync].
 #[derive(Serialize, Deserialize, Debug)]
-pub struct AddWidget3 {
-    /// Name of widget
-    pub label: String,
+pub struct SetMaxFrameRate {
+    /// Maximal number of frames per second.
+    pub max_frame_rate: Option<f32>,
 }
 
-impl AddWidget3 {
-    fn update_gui(self, data: &mut gui::GuiData, ctx: &mut miniquad::Context) {
-        data.widgets.insert(self.label, Box::new(Widget3::new(ctx)));
+impl SetMaxFrameRate {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.max_frame_rate = self.max_frame_rate;
     }
 }
 
-/// Place [super::entities::Entity3] in corresponding [Widget3].
+/// Fixes the number of columns of the widget grid in the main panel, see
+/// [gui::compute_widget_layout_with_columns]. If None, the number of columns is chosen such that
+/// the widgets are as large as possible, which is the default.
 #[derive(Serialize, Deserialize, Debug)]
-pub struct PlaceEntity3 {
+pub struct SetGridColumns {
+    /// Number of columns.
+    pub num_cols: Option<usize>,
+}
+
+impl SetGridColumns {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.grid_columns = self.num_cols;
+    }
+}
+
+/// Animation of an entity: returns `scene_pose_entity` given the elapsed time in seconds.
+pub type Animation = Box<dyn FnMut(f64) -> nalgebra::Isometry3<f32> + Send>;
+
+/// Animates [super::entities::Entity3] in corresponding [Widget3].
+///
+/// The `animation` is evaluated by the gui once per frame, before rendering, and overrides the
+/// pose of the entity with name `entity_label` - if such an entity exists. If `animation` is None,
+/// an existing animation is stopped and the entity keeps its last pose.
+///
+/// Since it contains a closure, this message cannot be serialized. Hence, it is not supported in
+/// remote mode and not part of [super::gui::GuiSnapshot]s.
+pub struct AnimateEntity3 {
     /// Name of widget.
     pub widget_label: String,
-    /// The 3d entity
-    pub named_entity: entities::NamedEntity3,
+    /// Name of entity.
+    pub entity_label: String,
+    /// The animation.
+    pub animation: Option<Animation>,
 }
 
-impl PlaceEntity3 {
-    fn update_gui(self, data: &mut gui::GuiData, _ctx: &mut miniquad::Context) {
-        data.widgets
+impl std::fmt::Debug for AnimateEntity3 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AnimateEntity3")
+            .field("widget_label", &self.widget_label)
+            .field("entity_label", &self.entity_label)
+            .field("animation", &self.animation.as_ref().map(|_| "FnMut(f64)"))
+            .finish()
+    }
+}
+
+impl AnimateEntity3 {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        let animations = &mut data
+            .widgets
             .get_mut(&self.widget_label)
             .unwrap()
             .downcast_mut::<Widget3>()
             .unwrap()
-            .entities
-            .insert(self.named_entity.label.clone(), self.named_entity);
+            .animations;
+        match self.animation {
+            Some(animation) => {
+                let start = std::time::Instant::now();
+                animations.insert(self.entity_label, (start, std::sync::Mutex::new(animation)));
+            }
+            None => {
+                animations.remove(&self.entity_label);
+            }
+        }
     }
 }
 
-/// Updates pose of [super::entities::Entity3] in corresponding [Widget3].
+/// Sets the background color of the corresponding [Widget3], overriding the one of the [Theme].
+/// The default is white, or the background color of the theme if set.
 ///
-/// It is no-op, if an entity with that name `entity_label` does not exist.
+/// The color is written to the render target unchanged, i.e. it is not encoded as sRGB.
 #[derive(Serialize, Deserialize, Debug)]
-pub struct UpdateScenePoseEntity3 {
+pub struct SetBackgroundColor {
     /// Name of widget.
     pub widget_label: String,
-    /// Name of entity.
-    pub entity_label: String,
-    /// Pose of the entity in the scene.
-    pub scene_pose_entity: nalgebra::Isometry3<f32>,
+    /// The background color; None restores the background color of the theme.
+    pub color: Option<entities::Color>,
 }
 
-impl UpdateScenePoseEntity3 {
-    fn update_gui(self, data: &mut gui::GuiData, _ctx: &mut miniquad::Context) {
-        let maybe_entity = data
+impl SetBackgroundColor {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        let widget = data
             .widgets
             .get_mut(&self.widget_label)
             .unwrap()
             .downcast_mut::<Widget3>()
+            .unwrap();
+        widget.custom_background_color = self.color;
+        widget.background_color = match self.color {
+            Some(c) => (c.r, c.g, c.b, c.alpha),
+            None => Theme::background_color(data.theme),
+        };
+    }
+}
+
+/// Enables/disables shading the meshes of the corresponding [Widget3] by a directional light,
+/// and/or sets the direction of the light. Lighting is disabled by default, i.e. meshes are drawn
+/// with their plain vertex colors.
+///
+/// Shading uses the vertex normals of the meshes, see [super::entities::Mesh3::vertex_normals],
+/// i.e. it requires counter-clockwise winding seen from the front. Line segments and text labels
+/// are not shaded.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SetLighting {
+    /// Name of widget.
+    pub widget_label: String,
+    /// Whether meshes are shaded. If None, this is not changed.
+    pub enabled: Option<bool>,
+    /// Direction the light travels in scene coordinates, e.g. `(0, -1, 0)` for light from above;
+    /// must not be zero. If None, the direction is not changed.
+    pub light_direction: Option<nalgebra::Vector3<f32>>,
+}
+
+impl SetLighting {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        let widget = data
+            .widgets
+            .get_mut(&self.widget_label)
             .unwrap()
-            .entities
-            .get_mut(&self.entity_label);
-        if maybe_entity.is_none() {
-            // No-op.
-            return;
+            .downcast_mut::<Widget3>()
+            .unwrap();
+        if let Some(enabled) = self.enabled {
+            widget.lighting = enabled;
         }
-        maybe_entity.unwrap().scene_pose_entity = self.scene_pose_entity;
+        if let Some(direction) = self
+            .light_direction
+            .and_then(|d| d.try_normalize(f32::EPSILON))
+        {
+            widget.light_direction = direction;
+        }
+    }
+}
+
+/// Sets the [Theme] of the gui, including the background of all [Widget3]s.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SetTheme {
+    /// The theme.
+    pub theme: Theme,
+}
+
+impl SetTheme {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.theme = Some(self.theme);
+        for (_, widget) in &mut data.widgets {
+            if let Some(widget3) = widget
+                .downcast_mut::<Widget3>()
+                .filter(|w| w.custom_background_color.is_none())
+            {
+                widget3.background_color = Theme::background_color(data.theme);
+            }
+        }
+    }
+}
+
+/// Saves a [gui::GuiSnapshot] of all components and 3d widgets to a json file at `path`.
+///
+/// The file is written by the gui, hence in remote mode it is written on the client side. Success
+/// or failure is reported as a notification.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SaveSnapshot {
+    /// The file path.
+    pub path: std::path::PathBuf,
+}
+
+impl SaveSnapshot {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        let result = serde_json::to_string(&data.to_snapshot())
+            .map_err(std::io::Error::from)
+            .and_then(|json| std::fs::write(&self.path, json));
+        let (text, level) = match result {
+            Ok(()) => (
+                format!("Saved snapshot to {}", self.path.display()),
+                Level::Info,
+            ),
+            Err(e) => (
+                format!("Failed to save snapshot to {}: {}", self.path.display(), e),
+                Level::Error,
+            ),
+        };
+        Notify { text, level }.update_gui(data);
+    }
+}
+
+/// Saves a screenshot of the whole window - side panel and all widgets - as png file at `path`.
+///
+/// The window is captured after the next frame was drawn, at the full resolution of the
+/// framebuffer, i.e. including the high-DPI scale factor. As for [SaveSnapshot], the file is
+/// written by the gui and success or failure is reported as a notification.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SaveWindowPng {
+    /// The file path.
+    pub path: std::path::PathBuf,
+}
+
+impl SaveWindowPng {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.window_capture_path = Some(self.path);
+    }
+}
+
+/// Reports that the application thread panicked; the gui shows the message in an error window.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AppThreadPanic {
+    /// The panic message.
+    pub message: String,
+}
+
+impl AppThreadPanic {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.app_thread_panic = Some(self.message);
+    }
+}
+
+/// Severity of a notification; determines its color.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    /// Informational, e.g. success of an operation.
+    Info,
+    /// Warning.
+    Warn,
+    /// Error.
+    Error,
+}
+
+/// Shows a transient notification (toast), which fades out after a few seconds.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Notify {
+    /// The text to show.
+    pub text: String,
+    /// The severity.
+    pub level: Level,
+}
+
+impl Notify {
+    fn update_gui(self, data: &mut gui::GuiData) {
+        data.notifications.push(gui::Notification {
+            text: self.text,
+            level: self.level,
+            created: std::time::Instant::now(),
+        });
     }
 }
 
@@ -896,13 +5156,13 @@ pub struct DeleteComponent {
 }
 
 impl DeleteComponent {
-    fn update_gui(self, data: &mut gui::GuiData, _ctx: &mut miniquad::Context) {
+    fn update_gui(self, data: &mut gui::GuiData) {
         data.components.remove(&self.label);
     }
 }
 
 /// Message from [super::gui::GuiLoop] to [super::manager::Manager].
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum FromGuiLoopMessage {
     /// enum combobox update
     UpdateEnumStringRepr(UpdateEnumStringRepr),
@@ -920,6 +5180,22 @@ pub enum FromGuiLoopMessage {
     UpdateRangedValueF64(UpdateRangedValue<f64>),
     /// button update
     UpdateButton(UpdateButton),
+    /// checkbox list update
+    UpdateCheckboxList(UpdateCheckboxList),
+    /// raw keyboard or mouse event
+    InputEvent(InputEvent),
+    /// hovered scene point of 3d widget
+    UpdateHoveredPoint3(UpdateHoveredPoint3),
+    /// hovered entity of 3d widget
+    UpdateHoveredEntity3(UpdateHoveredEntity3),
+    /// completed measurement in 3d widget
+    UpdateMeasurement3(UpdateMeasurement3),
+    /// DPI scale of the gui window
+    UpdateDpiScale(UpdateDpiScale),
+    /// camera pose of 3d widget
+    UpdateCameraPose(UpdateCameraPose),
+    /// button of preset controls pressed
+    UpdatePresetControls(UpdatePresetControls),
 }
 
 impl FromGuiLoopMessage {
@@ -939,14 +5215,70 @@ impl FromGuiLoopMessage {
             UpdateRangedValueF32(e) => e.update(components),
             UpdateRangedValueF64(e) => e.update(components),
             UpdateButton(e) => e.update(components),
+            UpdateCheckboxList(e) => e.update(components),
+            // Not component updates; queued by the manager directly.
+            InputEvent(_)
+            | UpdateHoveredPoint3(_)
+            | UpdateHoveredEntity3(_)
+            | UpdateMeasurement3(_)
+            | UpdateDpiScale(_)
+            | UpdateCameraPose(_)
+            | UpdatePresetControls(_) => {}
         }
     }
 }
 
+/// The scene point under the mouse cursor in a [Widget3] changed, see [Widget3::hovered_point].
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UpdateHoveredPoint3 {
+    /// Name of widget.
+    pub widget_label: String,
+    /// The hovered point in scene coordinates; None if not hovering over an entity.
+    pub point: Option<nalgebra::Vector3<f32>>,
+}
+
+/// The entity under the mouse cursor in a [Widget3] changed, see [Widget3::hovered_entity].
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UpdateHoveredEntity3 {
+    /// Name of widget.
+    pub widget_label: String,
+    /// Name of the hovered entity; None if not hovering over an entity.
+    pub entity_label: Option<String>,
+}
+
+/// A measurement was completed in a [Widget3], see [SetMeasureMode].
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UpdateMeasurement3 {
+    /// Name of widget.
+    pub widget_label: String,
+    /// The Euclidean distance between the two clicked scene points.
+    pub distance: f32,
+}
+
+/// The DPI scale of the gui window changed, e.g. since the window was moved to another screen.
+///
+/// It is the number of physical pixels per logical point, which is also egui's
+/// `pixels_per_point`, i.e. the single source of truth for converting between both.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UpdateDpiScale {
+    /// Physical pixels per logical point.
+    pub dpi_scale: f32,
+}
+
+/// The camera pose of a [Widget3] changed, e.g. by mouse interaction.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UpdateCameraPose {
+    /// Name of widget.
+    pub widget_label: String,
+    /// Pose of the camera in the scene - i.e. the transformation from camera to scene, which is
+    /// the inverse of [Widget3::camera_pose_scene].
+    pub scene_pose_camera: nalgebra::Isometry3<f32>,
+}
+
 /// [super::manager::UiEnum]  (i.e. slider) update.
 ///
 /// See also [EnumStringRepr].
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct UpdateEnumStringRepr {
     /// The name.
     pub label: String,
@@ -968,7 +5300,7 @@ impl UpdateEnumStringRepr {
 /// [Var] update.
 ///
 /// See also [super::manager::UiVar].
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct UpdateValue<T> {
     /// The name.
     pub label: String,
@@ -987,10 +5319,36 @@ impl UpdateValue<bool> {
     }
 }
 
+/// [CheckboxList] update of a single item.
+///
+/// See also [super::manager::UiCheckboxes].
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UpdateCheckboxList {
+    /// The name of the list.
+    pub label: String,
+    /// The name of the toggled item.
+    pub item: String,
+    /// Whether the item is checked now.
+    pub value: bool,
+}
+
+impl UpdateCheckboxList {
+    fn update(&self, components: &mut linked_hash_map::LinkedHashMap<String, Box<dyn Component>>) {
+        let list = components
+            .get_mut(&self.label)
+            .unwrap()
+            .downcast_mut::<CheckboxList>()
+            .unwrap();
+        if let Some((_, checked)) = list.items.iter_mut().find(|(item, _)| *item == self.item) {
+            *checked = self.value;
+        }
+    }
+}
+
 /// [RangedVar] (slider) update.
 ///
 /// See also [super::manager::UiRangedVar].
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct UpdateRangedValue<T> {
     /// The name.
     pub label: String,
@@ -1000,19 +5358,17 @@ pub struct UpdateRangedValue<T> {
 
 impl<T: Number> UpdateRangedValue<T> {
     fn update(&self, components: &mut linked_hash_map::LinkedHashMap<String, Box<dyn Component>>) {
-        components
-            .get_mut(&self.label)
-            .unwrap()
-            .downcast_mut::<RangedVar<T>>()
-            .unwrap()
-            .value = self.value;
+        // The slider might have been removed meanwhile, see [RemoveComponent].
+        if let Some(component) = components.get_mut(&self.label) {
+            component.downcast_mut::<RangedVar<T>>().unwrap().value = self.value;
+        }
     }
 }
 
 /// [Button] press event.
 ///
 /// See also [super::manager::UiButton].
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct UpdateButton {
     /// The name.
     pub label: String,
@@ -1028,3 +5384,130 @@ impl UpdateButton {
             .pressed = true;
     }
 }
+
+/// Action requested with [PresetControls].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum PresetAction {
+    /// Save the current values as new preset.
+    Save,
+    /// Apply the preset with the given name.
+    Apply(String),
+    /// Reset all values to the values they were added with.
+    Reset,
+}
+
+/// A button of [PresetControls] was pressed; handled by the manager, which keeps the presets.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UpdatePresetControls {
+    /// The name of the preset controls.
+    pub label: String,
+    /// The requested action.
+    pub action: PresetAction,
+}
+
+/// State of the modifier keys.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct KeyModifiers {
+    /// Shift key.
+    pub shift: bool,
+    /// Control key.
+    pub ctrl: bool,
+    /// Alt key.
+    pub alt: bool,
+    /// Logo key, e.g. Windows or Command.
+    pub logo: bool,
+}
+
+impl From<miniquad::KeyMods> for KeyModifiers {
+    fn from(mods: miniquad::KeyMods) -> KeyModifiers {
+        KeyModifiers {
+            shift: mods.shift,
+            ctrl: mods.ctrl,
+            alt: mods.alt,
+            logo: mods.logo,
+        }
+    }
+}
+
+/// Mouse button.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MouseButton {
+    /// Left mouse button.
+    Left,
+    /// Right mouse button.
+    Right,
+    /// Middle mouse button.
+    Middle,
+    /// Any other mouse button.
+    Unknown,
+}
+
+impl From<miniquad::MouseButton> for MouseButton {
+    fn from(button: miniquad::MouseButton) -> MouseButton {
+        match button {
+            miniquad::MouseButton::Left => MouseButton::Left,
+            miniquad::MouseButton::Right => MouseButton::Right,
+            miniquad::MouseButton::Middle => MouseButton::Middle,
+            miniquad::MouseButton::Unknown => MouseButton::Unknown,
+        }
+    }
+}
+
+/// Raw keyboard or mouse event, forwarded from [super::gui::GuiLoop] to the application.
+///
+/// Keys are named as the variants of `miniquad::KeyCode`, e.g. "Space", "Left" or "A". Mouse
+/// positions are in window pixels. Events are only forwarded once enabled with
+/// [super::manager::Manager::set_input_events_enabled]. Keyboard events are not forwarded while
+/// egui has keyboard focus, e.g. while typing into a text field, and mouse events are not
+/// forwarded while the pointer is over a panel or a widget egui handles, e.g. a slider.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum InputEvent {
+    /// Key pressed.
+    KeyDown {
+        /// Name of the key.
+        key: String,
+        /// Modifier keys.
+        modifiers: KeyModifiers,
+        /// True if this is a key repeat event.
+        repeat: bool,
+    },
+    /// Key released.
+    KeyUp {
+        /// Name of the key.
+        key: String,
+        /// Modifier keys.
+        modifiers: KeyModifiers,
+    },
+    /// Mouse button pressed.
+    MouseButtonDown {
+        /// The button.
+        button: MouseButton,
+        /// x position.
+        x: f32,
+        /// y position.
+        y: f32,
+    },
+    /// Mouse button released.
+    MouseButtonUp {
+        /// The button.
+        button: MouseButton,
+        /// x position.
+        x: f32,
+        /// y position.
+        y: f32,
+    },
+    /// Mouse moved.
+    MouseMotion {
+        /// x position.
+        x: f32,
+        /// y position.
+        y: f32,
+    },
+    /// Mouse wheel scrolled.
+    MouseWheel {
+        /// Horizontal scroll delta.
+        dx: f32,
+        /// Vertical scroll delta.
+        dy: f32,
+    },
+}
@@ -4,8 +4,11 @@
 use serde::{Deserialize, Serialize};
 
 /// Color.
+///
+/// Channels are sRGB-encoded, unless the color is drawn by a 3d widget with
+/// [super::manager::UiWidget3::set_srgb_output] enabled, which treats them as linear.
 #[repr(C)]
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
 pub struct Color {
     /// red [0..1]
     pub r: f32,
@@ -17,8 +20,20 @@ pub struct Color {
     pub alpha: f32,
 }
 
+impl From<Color> for egui::Color32 {
+    fn from(color: Color) -> egui::Color32 {
+        let to_u8 = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+        egui::Color32::from_rgba_unmultiplied(
+            to_u8(color.r),
+            to_u8(color.g),
+            to_u8(color.b),
+            to_u8(color.alpha),
+        )
+    }
+}
+
 /// Colored vertices.
-#[derive(Default, Serialize, Deserialize, Debug)]
+#[derive(Default, Serialize, Deserialize, Debug, Clone)]
 pub struct PositionColorVertices {
     /// Vector of vertices of position (3 elements) and color (4 elements)
     pub vertices: std::vec::Vec<[f32; 7]>,
@@ -41,7 +56,7 @@ impl PositionColorVertices {
 
 /// Position and texture coordinate vertices.
 #[repr(C)]
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct PositionUvVertices {
     /// Vector of vertices of position (3 elements) and texture coordinate (2 elements)
     pub vertices: std::vec::Vec<[f32; 5]>,
@@ -55,11 +70,11 @@ impl PositionUvVertices {
 }
 
 /// A texture.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Texture {}
 
 /// Position/texture coordinate vertices and texture.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct PositionUvVerticesAndTexture {
     /// The vertices.
     pub vertices: PositionUvVertices,
@@ -68,7 +83,7 @@ pub struct PositionUvVerticesAndTexture {
 }
 
 /// Enumeration of possible vertex options.
-#[derive(enum_as_inner::EnumAsInner, Serialize, Deserialize, Debug)]
+#[derive(enum_as_inner::EnumAsInner, Serialize, Deserialize, Debug, Clone)]
 pub enum MeshVertices {
     /// Colored vertices.
     PositionColor(PositionColorVertices),
@@ -78,7 +93,7 @@ pub enum MeshVertices {
 
 /// Triangle faces.
 #[repr(C)]
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Faces {
     /// Vector of triangle faces (3-array). A triangle face consists of three vertex indices.
     pub indices: std::vec::Vec<[i16; 3]>,
@@ -91,7 +106,7 @@ impl Faces {
 }
 
 /// A 3d mesh
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Mesh3 {
     /// The vertices.
     pub vertices: MeshVertices,
@@ -99,7 +114,138 @@ pub struct Mesh3 {
     pub faces: Faces,
 }
 
+/// Error of [Mesh3::validate] and [LineSegments3::validate].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MeshError {
+    /// A face or line segment refers to a vertex which does not exist.
+    InvalidIndex {
+        /// Position of the face/segment in the list of faces/segments.
+        primitive: usize,
+        /// The offending vertex index.
+        index: i16,
+        /// Number of vertices.
+        num_vertices: usize,
+    },
+}
+
+impl std::fmt::Display for MeshError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MeshError::InvalidIndex {
+                primitive,
+                index,
+                num_vertices,
+            } => write!(
+                f,
+                "primitive {} refers to vertex {}, but there are only {} vertices",
+                primitive, index, num_vertices
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MeshError {}
+
+/// Checks that all `indices` are in [0, num_vertices).
+fn validate_indices<'a, I: IntoIterator<Item = &'a [i16]>>(
+    indices: I,
+    num_vertices: usize,
+) -> Result<(), MeshError> {
+    for (primitive, primitive_indices) in indices.into_iter().enumerate() {
+        for &index in primitive_indices {
+            if index < 0 || index as usize >= num_vertices {
+                return Err(MeshError::InvalidIndex {
+                    primitive,
+                    index,
+                    num_vertices,
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
 impl Mesh3 {
+    /// Checks that all face indices refer to existing vertices.
+    ///
+    /// Example
+    /// ```
+    /// let mut cube = vviz::entities::colored_cube(1.0);
+    /// let mesh = cube.as_mesh_mut().unwrap();
+    /// assert!(mesh.validate().is_ok());
+    /// mesh.faces.indices.push([0, 1, 42]);
+    /// assert!(mesh.validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<(), MeshError> {
+        let num_vertices = match &self.vertices {
+            MeshVertices::PositionColor(v) => v.vertices.len(),
+            MeshVertices::PositionUvAndTexture(v) => v.vertices.vertices.len(),
+        };
+        validate_indices(self.faces.indices.iter().map(|f| &f[..]), num_vertices)
+    }
+
+    /// Vertex positions, regardless of the vertex type.
+    pub fn positions(&self) -> std::vec::Vec<nalgebra::Vector3<f32>> {
+        match &self.vertices {
+            MeshVertices::PositionColor(v) => v
+                .vertices
+                .iter()
+                .map(|v| nalgebra::Vector3::new(v[0], v[1], v[2]))
+                .collect(),
+            MeshVertices::PositionUvAndTexture(v) => v
+                .vertices
+                .vertices
+                .iter()
+                .map(|v| nalgebra::Vector3::new(v[0], v[1], v[2]))
+                .collect(),
+        }
+    }
+
+    /// Unit normal of each face, assuming counter-clockwise winding when seen from the front.
+    ///
+    /// Degenerate faces have a zero normal.
+    pub fn face_normals(&self) -> std::vec::Vec<nalgebra::Vector3<f32>> {
+        let positions = self.positions();
+        self.faces
+            .indices
+            .iter()
+            .map(|[a, b, c]| {
+                let (a, b, c) = (
+                    positions[*a as usize],
+                    positions[*b as usize],
+                    positions[*c as usize],
+                );
+                (b - a)
+                    .cross(&(c - a))
+                    .try_normalize(f32::EPSILON)
+                    .unwrap_or_else(nalgebra::Vector3::zeros)
+            })
+            .collect()
+    }
+
+    /// Unit normal of each vertex, i.e. the area-weighted average of the normals of adjacent faces.
+    ///
+    /// Vertices which are not part of any (non-degenerate) face have a zero normal.
+    pub fn vertex_normals(&self) -> std::vec::Vec<nalgebra::Vector3<f32>> {
+        let positions = self.positions();
+        let mut normals = vec![nalgebra::Vector3::zeros(); positions.len()];
+        for face in &self.faces.indices {
+            let [a, b, c] = face.map(|i| i as usize);
+            // The length of the cross product is twice the face area, hence area-weighted.
+            let n = (positions[b] - positions[a]).cross(&(positions[c] - positions[a]));
+            for i in [a, b, c] {
+                normals[i] += n;
+            }
+        }
+        normals
+            .into_iter()
+            .map(|n| {
+                n.try_normalize(f32::EPSILON)
+                    .unwrap_or_else(nalgebra::Vector3::zeros)
+            })
+            .collect()
+    }
+
     fn from_position_color_vertices_and_faces(
         vertices: PositionColorVertices,
         faces: Faces,
@@ -126,7 +272,7 @@ impl Mesh3 {
 }
 
 /// 3d line segments
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct LineSegments3 {
     /// The vertices.
     pub vertices: PositionColorVertices,
@@ -135,19 +281,249 @@ pub struct LineSegments3 {
     pub indices: std::vec::Vec<[i16; 2]>,
 }
 
+impl LineSegments3 {
+    /// Maximal number of vertices, such that the number of vertices fits into the 16-bit indices.
+    pub const MAX_VERTICES: usize = i16::MAX as usize;
+
+    /// Treats the line segments as a path, i.e. a polyline, and appends `point`: a segment from the
+    /// previous to the new point is added.
+    ///
+    /// At most `max_points` points are retained - but never more than [Self::MAX_VERTICES]; the
+    /// oldest points are dropped first. Assumes that the line segments form a path, which is the
+    /// case if they were only ever created by this method.
+    ///
+    /// Example
+    /// ```
+    /// use vviz::entities::{Color, LineSegments3};
+    ///
+    /// let red = Color { r: 1.0, g: 0.0, b: 0.0, alpha: 1.0 };
+    /// let mut path = LineSegments3::default();
+    /// for i in 0..5 {
+    ///     path.append_to_path(nalgebra::Vector3::new(i as f32, 0.0, 0.0), red, Some(3));
+    /// }
+    /// assert_eq!(path.vertices.vertices.len(), 3);
+    /// assert_eq!(path.vertices.vertices[0][0], 2.0);
+    /// assert_eq!(path.indices, vec![[0, 1], [1, 2]]);
+    ///
+    /// // Without `max_points`, the path is capped at MAX_VERTICES.
+    /// let mut path = LineSegments3::default();
+    /// for i in 0..LineSegments3::MAX_VERTICES + 2 {
+    ///     path.append_to_path(nalgebra::Vector3::new(i as f32, 0.0, 0.0), red, None);
+    /// }
+    /// assert_eq!(path.vertices.vertices.len(), LineSegments3::MAX_VERTICES);
+    /// assert_eq!(path.indices.len(), LineSegments3::MAX_VERTICES - 1);
+    /// assert!(path.validate().is_ok());
+    /// ```
+    pub fn append_to_path(
+        &mut self,
+        point: nalgebra::Vector3<f32>,
+        color: Color,
+        max_points: Option<usize>,
+    ) {
+        let max_points = max_points
+            .unwrap_or(Self::MAX_VERTICES)
+            .clamp(1, Self::MAX_VERTICES);
+        let vertices = &mut self.vertices.vertices;
+        vertices.push(PositionColorVertices::to_array(point, color));
+        if vertices.len() > max_points {
+            vertices.drain(..vertices.len() - max_points);
+            self.indices = (1..vertices.len() as i16).map(|i| [i - 1, i]).collect();
+        } else if vertices.len() > 1 {
+            let i = (vertices.len() - 1) as i16;
+            self.indices.push([i - 1, i]);
+        }
+    }
+
+    /// Checks that all indices refer to existing vertices.
+    pub fn validate(&self) -> Result<(), MeshError> {
+        validate_indices(
+            self.indices.iter().map(|s| &s[..]),
+            self.vertices.vertices.len(),
+        )
+    }
+}
+
+/// Text label of a `Widget3`, drawn at the origin of its entity.
+///
+/// Labels are billboards: they always face the camera and are sized in screen space, i.e. stay
+/// legible independent of the zoom. They are rendered by egui, i.e. anti-aliased and DPI-aware
+/// using its font atlas. Also see [text_label].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TextLabel3 {
+    /// The text.
+    pub text: String,
+    /// Text color; sRGB as for all entities, see [Color].
+    pub color: Color,
+    /// Font size in points; snapped to the closest egui text style, i.e. small, body or heading.
+    pub size: f32,
+    /// If true, the label is drawn on top of all geometry; otherwise it is hidden if its anchor
+    /// point is occluded.
+    pub always_on_top: bool,
+}
+
 /// 3d entity to be added to a `Widget3`.
-#[derive(Serialize, Deserialize, Debug)]
+///
+/// Entities and their geometry types are serializable, e.g. for remote streaming and snapshots:
+///
+/// ```
+/// let cube = vviz::entities::colored_cube(0.5);
+/// let json = serde_json::to_string(&cube).unwrap();
+/// let restored: vviz::entities::Entity3 = serde_json::from_str(&json).unwrap();
+/// let mesh = restored.as_mesh().unwrap();
+/// assert_eq!(mesh.faces.indices, cube.as_mesh().unwrap().faces.indices);
+/// assert_eq!(
+///     mesh.vertices.as_position_color().unwrap().vertices,
+///     cube.as_mesh().unwrap().vertices.as_position_color().unwrap().vertices
+/// );
+/// ```
+#[derive(enum_as_inner::EnumAsInner, Serialize, Deserialize, Debug, Clone)]
 pub enum Entity3 {
     /// Mesh
     Mesh(Mesh3),
     /// Line segments
     LineSegments(LineSegments3),
+    /// Text label
+    Text(TextLabel3),
     // Points(Points3),
-    // Note: Miniquad does not support points (yet).
+    // Note: The pinned miniquad (0.3.0-alpha.43) only offers `PrimitiveType::Triangles` and
+    // `PrimitiveType::Lines`. Once it exposes `PrimitiveType::Points`, add this variant, a
+    // points pipeline with a point size uniform in Widget3, and set
+    // `Capabilities::gl_points` accordingly.
+}
+
+impl Entity3 {
+    /// Sets the color of all vertices. Textured meshes are left unchanged.
+    pub fn set_color(&mut self, color: Color) {
+        let vertices = match self {
+            Entity3::Mesh(mesh) => match &mut mesh.vertices {
+                MeshVertices::PositionColor(v) => &mut v.vertices,
+                MeshVertices::PositionUvAndTexture(_) => return,
+            },
+            Entity3::LineSegments(segments) => &mut segments.vertices.vertices,
+            Entity3::Text(label) => {
+                label.color = color;
+                return;
+            }
+        };
+        for v in vertices {
+            v[3..].copy_from_slice(&[color.r, color.g, color.b, color.alpha]);
+        }
+    }
+
+    /// Checks that all indices refer to existing vertices; see [Mesh3::validate].
+    pub fn validate(&self) -> Result<(), MeshError> {
+        match self {
+            Entity3::Mesh(mesh) => mesh.validate(),
+            Entity3::LineSegments(segments) => segments.validate(),
+            Entity3::Text(_) => Ok(()),
+        }
+    }
+
+    /// Axis-aligned bounding box of the entity's vertices, in entity coordinates. For a text label,
+    /// this is its anchor point, i.e. the origin.
+    ///
+    /// Returns None if the entity has no vertices.
+    pub fn aabb(&self) -> Option<Aabb3> {
+        match self {
+            Entity3::Mesh(mesh) => match &mesh.vertices {
+                MeshVertices::PositionColor(v) => {
+                    Aabb3::from_points(v.vertices.iter().map(|v| [v[0], v[1], v[2]]))
+                }
+                MeshVertices::PositionUvAndTexture(v) => {
+                    Aabb3::from_points(v.vertices.vertices.iter().map(|v| [v[0], v[1], v[2]]))
+                }
+            },
+            Entity3::LineSegments(segments) => Aabb3::from_points(
+                segments
+                    .vertices
+                    .vertices
+                    .iter()
+                    .map(|v| [v[0], v[1], v[2]]),
+            ),
+            Entity3::Text(_) => Aabb3::from_points([[0.0; 3]]),
+        }
+    }
+}
+
+/// Axis-aligned 3d bounding box.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Aabb3 {
+    /// Minimal corner.
+    pub min: nalgebra::Vector3<f32>,
+    /// Maximal corner.
+    pub max: nalgebra::Vector3<f32>,
+}
+
+impl Aabb3 {
+    /// Smallest box containing all `points`. Returns None if there are no points.
+    pub fn from_points<I: IntoIterator<Item = [f32; 3]>>(points: I) -> Option<Self> {
+        let mut iter = points.into_iter();
+        let first = nalgebra::Vector3::from(iter.next()?);
+        let mut aabb = Self {
+            min: first,
+            max: first,
+        };
+        for p in iter {
+            let p = nalgebra::Vector3::from(p);
+            aabb.min = aabb.min.inf(&p);
+            aabb.max = aabb.max.sup(&p);
+        }
+        Some(aabb)
+    }
+
+    /// The eight corners of the box.
+    pub fn corners(&self) -> [nalgebra::Vector3<f32>; 8] {
+        let (a, b) = (self.min, self.max);
+        [
+            nalgebra::Vector3::new(a.x, a.y, a.z),
+            nalgebra::Vector3::new(b.x, a.y, a.z),
+            nalgebra::Vector3::new(a.x, b.y, a.z),
+            nalgebra::Vector3::new(b.x, b.y, a.z),
+            nalgebra::Vector3::new(a.x, a.y, b.z),
+            nalgebra::Vector3::new(b.x, a.y, b.z),
+            nalgebra::Vector3::new(a.x, b.y, b.z),
+            nalgebra::Vector3::new(b.x, b.y, b.z),
+        ]
+    }
+
+    /// The twelve edges of the box as line segments, e.g. to outline an entity.
+    ///
+    /// Example
+    /// ```
+    /// let aabb = vviz::entities::Aabb3 {
+    ///     min: nalgebra::Vector3::new(-1.0, -1.0, -1.0),
+    ///     max: nalgebra::Vector3::new(1.0, 2.0, 3.0),
+    /// };
+    /// let color = vviz::entities::Color { r: 1.0, g: 1.0, b: 0.0, alpha: 1.0 };
+    /// let edges = aabb.edges(color);
+    /// assert_eq!(edges.indices.len(), 12);
+    /// let vertices = &edges.vertices.vertices;
+    /// for [a, b] in edges.indices {
+    ///     let (a, b) = (vertices[a as usize], vertices[b as usize]);
+    ///     // Each edge is parallel to one of the axes.
+    ///     assert_eq!((0..3).filter(|&i| a[i] != b[i]).count(), 1);
+    /// }
+    /// ```
+    pub fn edges(&self, color: Color) -> LineSegments3 {
+        let mut vertices = PositionColorVertices::default();
+        for corner in self.corners() {
+            vertices
+                .vertices
+                .push(PositionColorVertices::to_array(corner, color));
+        }
+        // Corner indices encode x, y and z in their bits; edges connect corners differing in one.
+        let mut indices = std::vec::Vec::new();
+        for bit in [1, 2, 4] {
+            for i in (0..8).filter(|i| i & bit == 0) {
+                indices.push([i, i | bit]);
+            }
+        }
+        LineSegments3 { vertices, indices }
+    }
 }
 
 /// A named entity has a pose, a name and - well - an [Entity3].
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct NamedEntity3 {
     /// The name.
     pub label: String,
@@ -155,6 +531,40 @@ pub struct NamedEntity3 {
     pub entity: Entity3,
     /// Pose of the entity in the scene.
     pub scene_pose_entity: nalgebra::Isometry3<f32>,
+    /// Scale along the x, y and z axis of the entity; applied before `scene_pose_entity`.
+    pub scale: nalgebra::Vector3<f32>,
+    /// Face culling mode. If None, the cull mode of the widget is used.
+    pub cull_mode: Option<super::common::CullMode>,
+    /// Depth offset in normalized device coordinates, see [super::common::SetEntityDepthBias].
+    #[serde(default)]
+    pub depth_bias: f32,
+}
+
+impl NamedEntity3 {
+    /// Transformation from entity to scene, i.e. the `scene_pose_entity` times the scale.
+    pub fn scene_transform_entity(&self) -> nalgebra::Matrix4<f32> {
+        self.scene_pose_entity.to_matrix() * nalgebra::Matrix4::new_nonuniform_scaling(&self.scale)
+    }
+}
+
+/// Creates a text label, see [TextLabel3], which is occluded by geometry in front of it.
+///
+/// Example
+/// ```
+/// use vviz::entities::{text_label, Color};
+///
+/// let white = Color { r: 1.0, g: 1.0, b: 1.0, alpha: 1.0 };
+/// let mut label = text_label("origin", white, 14.0);
+/// label.as_text_mut().unwrap().always_on_top = true;
+/// assert_eq!(label.as_text().unwrap().text, "origin");
+/// ```
+pub fn text_label(text: impl Into<String>, color: Color, size: f32) -> Entity3 {
+    Entity3::Text(TextLabel3 {
+        text: text.into(),
+        color,
+        size,
+        always_on_top: false,
+    })
 }
 
 /// Creates a colored cube with a given scale.
@@ -294,6 +704,110 @@ impl From<Axis3> for Entity3 {
     }
 }
 
+/// Creates line segments of given `length` from each vertex of `mesh` along its vertex normal.
+///
+/// This is useful to debug the geometry and orientation of a mesh; place it with the same pose as
+/// the mesh. See [Mesh3::vertex_normals]. Since each line has two vertices, only the normals of
+/// the first `LineSegments3::MAX_VERTICES / 2` vertices are included.
+///
+/// Example
+/// ```
+/// let cube = vviz::entities::colored_cube(1.0);
+/// let mesh = cube.as_mesh().unwrap();
+/// let color = vviz::entities::Color { r: 0.0, g: 0.0, b: 1.0, alpha: 1.0 };
+/// let normals = vviz::entities::normal_lines(mesh, 0.1, color);
+/// let segments = normals.as_line_segments().unwrap();
+/// assert_eq!(segments.indices.len(), mesh.positions().len());
+/// ```
+pub fn normal_lines(mesh: &Mesh3, length: f32, color: Color) -> Entity3 {
+    Entity3::LineSegments(normal_line_segments(mesh, length, color))
+}
+
+/// Same as [normal_lines], as [LineSegments3].
+pub(crate) fn normal_line_segments(mesh: &Mesh3, length: f32, color: Color) -> LineSegments3 {
+    let mut vertices = PositionColorVertices::default();
+    let mut indices = std::vec::Vec::new();
+    for (k, (position, normal)) in mesh
+        .positions()
+        .into_iter()
+        .zip(mesh.vertex_normals())
+        .take(LineSegments3::MAX_VERTICES / 2)
+        .enumerate()
+    {
+        vertices
+            .vertices
+            .push(PositionColorVertices::to_array(position, color));
+        vertices.vertices.push(PositionColorVertices::to_array(
+            position + length * normal,
+            color,
+        ));
+        // Fits, since the number of normals is capped above.
+        let idx = (2 * k) as i16;
+        indices.push([idx, idx + 1]);
+    }
+    LineSegments3 { vertices, indices }
+}
+
+/// Creates the edges of `mesh` as line segments, with the colors of the mesh vertices; e.g. to
+/// inspect its triangulation. Edges shared by several faces are included once. Textured meshes
+/// are drawn in gray.
+///
+/// Example
+/// ```
+/// let cube = vviz::entities::colored_cube(1.0);
+/// let wireframe = vviz::entities::wireframe(cube.as_mesh().unwrap());
+/// // The sides of the cube do not share vertices: 4 edges plus one diagonal per side.
+/// assert_eq!(wireframe.as_line_segments().unwrap().indices.len(), 6 * 5);
+/// ```
+pub fn wireframe(mesh: &Mesh3) -> Entity3 {
+    let vertices = match &mesh.vertices {
+        MeshVertices::PositionColor(v) => v.clone(),
+        MeshVertices::PositionUvAndTexture(_) => {
+            let gray = Color {
+                r: 0.5,
+                g: 0.5,
+                b: 0.5,
+                alpha: 1.0,
+            };
+            PositionColorVertices {
+                vertices: mesh
+                    .positions()
+                    .into_iter()
+                    .map(|p| PositionColorVertices::to_array(p, gray))
+                    .collect(),
+            }
+        }
+    };
+    let mut edges = std::collections::BTreeSet::new();
+    for [a, b, c] in &mesh.faces.indices {
+        for (i, j) in [(a, b), (b, c), (c, a)] {
+            edges.insert([*i.min(j), *i.max(j)]);
+        }
+    }
+    Entity3::LineSegments(LineSegments3 {
+        vertices,
+        indices: edges.into_iter().collect(),
+    })
+}
+
+/// Creates a copy of `mesh` whose vertex colors encode the vertex normals, i.e. `0.5 * (n + 1)`
+/// for each component of the normal `n` in entity coordinates. See [Mesh3::vertex_normals].
+pub fn normals_as_color(mesh: &Mesh3) -> Entity3 {
+    let vertices = mesh
+        .positions()
+        .into_iter()
+        .zip(mesh.vertex_normals())
+        .map(|(p, n)| {
+            let c = 0.5 * (n + nalgebra::Vector3::repeat(1.0));
+            [p.x, p.y, p.z, c.x, c.y, c.z, 1.0]
+        })
+        .collect();
+    Entity3::Mesh(Mesh3::from_position_color_vertices_and_faces(
+        PositionColorVertices { vertices },
+        Faces::new(mesh.faces.indices.clone()),
+    ))
+}
+
 /// Colored point cloud
 #[derive(Default)]
 pub struct ColoredPoints3 {
@@ -302,36 +816,54 @@ pub struct ColoredPoints3 {
 
 impl ColoredPoints3 {
     /// From vector of 3-arrays and a color.
+    ///
+    /// Example
+    /// ```
+    /// use vviz::entities::{Color, ColoredPoints3, Entity3};
+    ///
+    /// let green = Color { r: 0.1, g: 0.9, b: 0.2, alpha: 1.0 };
+    /// let points = ColoredPoints3::from_arrays_and_color(vec![[1.0, 2.0, 3.0]], green);
+    /// let entity: Entity3 = points.into();
+    /// let mesh = entity.into_mesh().unwrap();
+    /// for vertex in &mesh.vertices.as_position_color().unwrap().vertices {
+    ///     assert_eq!(vertex[3..], [0.1, 0.9, 0.2, 1.0]);
+    /// }
+    /// ```
     pub fn from_arrays_and_color(arrays: Vec<[f32; 3]>, color: Color) -> Self {
         let mut points = PositionColorVertices::default();
         for v in arrays {
             points
                 .vertices
-                .push([v[0], v[1], v[2], color.r, color.b, color.g, color.alpha]);
+                .push([v[0], v[1], v[2], color.r, color.g, color.b, color.alpha]);
         }
         Self { points }
     }
+
+    /// From Nx3 float `Mat` and a color, see [super::opencv_interop::mat_to_points].
+    #[cfg(feature = "opencv")]
+    pub fn from_mat(
+        mat: &opencv::core::Mat,
+        color: Color,
+    ) -> Result<Self, super::opencv_interop::MatError> {
+        Ok(Self::from_arrays_and_color(
+            super::opencv_interop::mat_to_points(mat)?,
+            color,
+        ))
+    }
 }
 
 /// to Entity3 implementation for ColoredPoints3
 impl From<ColoredPoints3> for Entity3 {
     fn from(colored_points: ColoredPoints3) -> Entity3 {
-        // Hack, represent point as a tiny triangle, until points are supported.
+        // Hack, represent point as a tiny triangle, until miniquad supports points; see
+        // `Capabilities::gl_points`.
         // TODO: Slightly better to use tiny pyramid.
         let mut vertices = PositionColorVertices::default();
         let mut faces: Vec<[i16; 3]> = std::vec::Vec::new();
         for i in 0..colored_points.points.vertices.len() {
-            let v = colored_points.points.vertices[i];
-            let mut v0 = v;
-            v0[0] += 0.01;
-            let mut v1 = v;
-            v1[1] += 0.01;
-            let mut v2 = v;
-            v2[2] += 0.01;
-
-            vertices.vertices.push(v0);
-            vertices.vertices.push(v1);
-            vertices.vertices.push(v2);
+            vertices
+                .vertices
+                .extend(point_triangle(colored_points.points.vertices[i]));
 
             let idx = i as i16;
             faces.push([idx * 3, idx * 3 + 1, idx * 3 + 2]);
@@ -342,3 +874,658 @@ impl From<ColoredPoints3> for Entity3 {
         ))
     }
 }
+
+/// Tiny triangle which represents a point (position and color) of a point cloud.
+fn point_triangle(v: [f32; 7]) -> [[f32; 7]; 3] {
+    let mut v0 = v;
+    v0[0] += 0.01;
+    let mut v1 = v;
+    v1[1] += 0.01;
+    let mut v2 = v;
+    v2[2] += 0.01;
+    [v0, v1, v2]
+}
+
+/// Maximal number of points of a point cloud, given the 16-bit vertex indices.
+pub const MAX_NUM_POINTS: usize = (i16::MAX as usize + 1) / 3;
+
+/// Appends `points` to a point cloud `mesh` - as created from [ColoredPoints3] - e.g. to
+/// accumulate streamed sensor data.
+///
+/// `colors` holds either one color per point, or a single color for all points. If there are
+/// more than `max_points` points - and in any case more than [MAX_NUM_POINTS] - the oldest points
+/// are dropped. Textured meshes are left unchanged.
+///
+/// Panics if the number of colors is neither one nor the number of points.
+///
+/// Example
+/// ```
+/// use vviz::entities::{append_points, Color, ColoredPoints3, Entity3};
+///
+/// let red = Color { r: 1.0, g: 0.0, b: 0.0, alpha: 1.0 };
+/// let cloud = Entity3::from(ColoredPoints3::from_arrays_and_color(vec![[0.0; 3]], red));
+/// let mut mesh = cloud.into_mesh().unwrap();
+///
+/// append_points(&mut mesh, &[[1.0, 0.0, 0.0], [2.0, 0.0, 0.0]], &[red], None);
+/// assert_eq!(mesh.faces.indices.len(), 3);
+/// assert!(mesh.validate().is_ok());
+///
+/// // Keeps the two newest points only.
+/// append_points(&mut mesh, &[[3.0, 0.0, 0.0]], &[red], Some(2));
+/// assert_eq!(mesh.faces.indices.len(), 2);
+/// let vertices = &mesh.vertices.as_position_color().unwrap().vertices;
+/// assert_eq!(vertices.len(), 6);
+/// assert_eq!(vertices[0][1], 0.0);
+/// assert!((vertices[0][0] - 2.01).abs() < 1e-6);
+/// assert!(mesh.validate().is_ok());
+/// ```
+pub fn append_points(
+    mesh: &mut Mesh3,
+    points: &[[f32; 3]],
+    colors: &[Color],
+    max_points: Option<usize>,
+) {
+    assert!(
+        colors.len() == 1 || colors.len() == points.len(),
+        "expected one color, or one per point; got {} colors for {} points",
+        colors.len(),
+        points.len()
+    );
+    let vertices = match &mut mesh.vertices {
+        MeshVertices::PositionColor(v) => &mut v.vertices,
+        MeshVertices::PositionUvAndTexture(_) => return,
+    };
+    for (i, p) in points.iter().enumerate() {
+        let color = colors[i.min(colors.len() - 1)];
+        vertices.extend(point_triangle([
+            p[0],
+            p[1],
+            p[2],
+            color.r,
+            color.g,
+            color.b,
+            color.alpha,
+        ]));
+    }
+    let max_points = max_points.unwrap_or(MAX_NUM_POINTS).min(MAX_NUM_POINTS);
+    let num_points = vertices.len() / 3;
+    if num_points > max_points {
+        vertices.drain(..3 * (num_points - max_points));
+    }
+    let num_points = vertices.len() / 3;
+    mesh.faces.indices = (0..num_points as i16)
+        .map(|i| [3 * i, 3 * i + 1, 3 * i + 2])
+        .collect();
+}
+
+/// Unit UV-sphere with `(lat_segments + 1) * (lon_segments + 1)` vertices.
+///
+/// The faces are wound counter-clockwise when seen from outside the sphere.
+fn unit_uv_sphere(
+    lat_segments: u32,
+    lon_segments: u32,
+) -> (
+    std::vec::Vec<nalgebra::Vector3<f32>>,
+    std::vec::Vec<[i16; 3]>,
+) {
+    let mut positions = std::vec::Vec::new();
+    for i in 0..=lat_segments {
+        let theta = std::f32::consts::PI * i as f32 / lat_segments as f32;
+        for j in 0..=lon_segments {
+            let phi = 2.0 * std::f32::consts::PI * j as f32 / lon_segments as f32;
+            positions.push(nalgebra::Vector3::new(
+                theta.sin() * phi.cos(),
+                theta.sin() * phi.sin(),
+                theta.cos(),
+            ));
+        }
+    }
+    let idx = |i: u32, j: u32| -> i16 { (i * (lon_segments + 1) + j).try_into().unwrap() };
+    let mut faces = std::vec::Vec::new();
+    for i in 0..lat_segments {
+        for j in 0..lon_segments {
+            faces.push([idx(i, j), idx(i + 1, j), idx(i + 1, j + 1)]);
+            faces.push([idx(i, j), idx(i + 1, j + 1), idx(i, j + 1)]);
+        }
+    }
+    (positions, faces)
+}
+
+/// Creates a UV-sphere mesh with given `radius` and `color`, centered at the origin.
+///
+/// It has `(lat_segments + 1) * (lon_segments + 1)` vertices. The faces are wound
+/// counter-clockwise when seen from outside, so that back-face culling hides the inside only.
+///
+/// Example
+/// ```
+/// use vviz::entities::{colored_sphere, Color};
+///
+/// let red = Color { r: 1.0, g: 0.0, b: 0.0, alpha: 1.0 };
+/// let (lat_segments, lon_segments) = (8, 16);
+/// let sphere = colored_sphere(0.5, red, lat_segments, lon_segments);
+/// let mesh = sphere.as_mesh().unwrap();
+/// let vertices = &mesh.vertices.as_position_color().unwrap().vertices;
+/// assert_eq!(
+///     vertices.len() as u32,
+///     (lat_segments + 1) * (lon_segments + 1)
+/// );
+/// // All face indices are in bounds.
+/// assert!(mesh.validate().is_ok());
+///
+/// // All non-degenerate faces point outwards.
+/// let position = |i: i16| nalgebra::Vector3::from_row_slice(&vertices[i as usize][0..3]);
+/// for [a, b, c] in &mesh.faces.indices {
+///     let (a, b, c) = (position(*a), position(*b), position(*c));
+///     let normal = (b - a).cross(&(c - a));
+///     if normal.norm() > 1e-6 {
+///         assert!(normal.dot(&(a + b + c)) > 0.0);
+///     }
+/// }
+/// ```
+pub fn colored_sphere(radius: f32, color: Color, lat_segments: u32, lon_segments: u32) -> Entity3 {
+    colored_ellipsoid(
+        nalgebra::Vector3::repeat(radius),
+        color,
+        lat_segments,
+        lon_segments,
+    )
+}
+
+/// Same as [colored_sphere], but an axis-aligned ellipsoid with given `radii` along the x, y and
+/// z axis. See [covariance_ellipsoid] for the uncertainty region of a 3d Gaussian.
+pub fn colored_ellipsoid(
+    radii: nalgebra::Vector3<f32>,
+    color: Color,
+    lat_segments: u32,
+    lon_segments: u32,
+) -> Entity3 {
+    let (positions, faces) = unit_uv_sphere(lat_segments, lon_segments);
+    let vertices = PositionColorVertices {
+        vertices: positions
+            .into_iter()
+            .map(|p| PositionColorVertices::to_array(radii.component_mul(&p), color))
+            .collect(),
+    };
+    Entity3::Mesh(Mesh3::from_position_color_vertices_and_faces(
+        vertices,
+        Faces::new(faces),
+    ))
+}
+
+/// Creates an ellipsoid mesh representing the `n_sigma` uncertainty region of a 3d Gaussian with
+/// given `mean` and covariance `cov`.
+///
+/// The axes of the ellipsoid are the eigenvectors of `cov`, and the radii are `n_sigma` times the
+/// square roots of the corresponding eigenvalues. Negative eigenvalues - e.g. of a covariance
+/// which is not positive semi-definite due to numerical noise - are clamped to zero.
+pub fn covariance_ellipsoid(
+    mean: nalgebra::Vector3<f32>,
+    cov: nalgebra::Matrix3<f32>,
+    n_sigma: f32,
+    color: Color,
+) -> Entity3 {
+    let eigen = nalgebra::SymmetricEigen::new(cov);
+    let mut axes = eigen.eigenvectors;
+    if axes.determinant() < 0.0 {
+        // Ensure a proper rotation, so that the face winding is preserved.
+        axes.set_column(2, &-axes.column(2));
+    }
+    let radii = eigen
+        .eigenvalues
+        .map(|lambda| n_sigma * lambda.max(0.0).sqrt());
+    let transform = axes * nalgebra::Matrix3::from_diagonal(&radii);
+
+    let (positions, faces) = unit_uv_sphere(16, 32);
+    let vertices = PositionColorVertices {
+        vertices: positions
+            .into_iter()
+            .map(|p| PositionColorVertices::to_array(mean + transform * p, color))
+            .collect(),
+    };
+    Entity3::Mesh(Mesh3::from_position_color_vertices_and_faces(
+        vertices,
+        Faces::new(faces),
+    ))
+}
+
+/// Creates a square grid of line segments in the xy-plane, centered at the origin, with side
+/// length `size` and `divisions` cells along each side; e.g. as ground plane of a z-up scene.
+///
+/// Example
+/// ```
+/// let gray = vviz::entities::Color { r: 0.5, g: 0.5, b: 0.5, alpha: 1.0 };
+/// for divisions in [1, 4, 10] {
+///     let grid = vviz::entities::grid(2.0, divisions, gray);
+///     let segments = grid.as_line_segments().unwrap();
+///     assert_eq!(segments.indices.len(), 2 * (divisions as usize + 1));
+///     assert!(segments.vertices.vertices.iter().all(|v| v[2] == 0.0));
+/// }
+/// ```
+pub fn grid(size: f32, divisions: u32, color: Color) -> Entity3 {
+    grid_with_major_lines(size, divisions, color, 0, color)
+}
+
+/// Like [grid], but every `major_every`-th line - counted from the border - is drawn in
+/// `major_color`, e.g. a darker color. If `major_every` is 0, there are no major lines.
+///
+/// Example
+/// ```
+/// use vviz::entities::{grid_with_major_lines, Color};
+///
+/// let light = Color { r: 0.8, g: 0.8, b: 0.8, alpha: 1.0 };
+/// let dark = Color { r: 0.3, g: 0.3, b: 0.3, alpha: 1.0 };
+/// let grid = grid_with_major_lines(10.0, 10, light, 5, dark);
+/// let segments = grid.as_line_segments().unwrap();
+/// // Lines 0, 5 and 10 in both directions.
+/// let num_major = segments.vertices.vertices.iter().filter(|v| v[3] == dark.r).count() / 2;
+/// assert_eq!(num_major, 2 * 3);
+/// ```
+pub fn grid_with_major_lines(
+    size: f32,
+    divisions: u32,
+    color: Color,
+    major_every: u32,
+    major_color: Color,
+) -> Entity3 {
+    let mut vertices = PositionColorVertices::default();
+    let mut indices = std::vec::Vec::with_capacity(2 * (divisions as usize + 1));
+    let half = 0.5 * size;
+    for i in 0..=divisions {
+        let t = -half + size * i as f32 / divisions.max(1) as f32;
+        let color = if major_every > 0 && i % major_every == 0 {
+            major_color
+        } else {
+            color
+        };
+        for (start, end) in [
+            (
+                nalgebra::Vector3::new(t, -half, 0.0),
+                nalgebra::Vector3::new(t, half, 0.0),
+            ),
+            (
+                nalgebra::Vector3::new(-half, t, 0.0),
+                nalgebra::Vector3::new(half, t, 0.0),
+            ),
+        ] {
+            let idx: i16 = vertices.vertices.len().try_into().unwrap();
+            vertices
+                .vertices
+                .push(PositionColorVertices::to_array(start, color));
+            vertices
+                .vertices
+                .push(PositionColorVertices::to_array(end, color));
+            indices.push([idx, idx + 1]);
+        }
+    }
+    Entity3::LineSegments(LineSegments3 { vertices, indices })
+}
+
+/// Error of [load_obj].
+#[derive(Debug)]
+pub enum ObjError {
+    /// The file could not be read.
+    Io(std::io::Error),
+    /// A line could not be parsed.
+    Parse {
+        /// Line number, starting at 1.
+        line: usize,
+        /// What is wrong with the line.
+        message: String,
+    },
+    /// The mesh has more vertices than 16-bit indices can address.
+    TooManyVertices(usize),
+}
+
+impl std::fmt::Display for ObjError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ObjError::Io(e) => write!(f, "failed to read obj file: {}", e),
+            ObjError::Parse { line, message } => write!(f, "line {}: {}", line, message),
+            ObjError::TooManyVertices(n) => write!(
+                f,
+                "{} vertices, but at most {} are supported",
+                n,
+                i16::MAX as usize + 1
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ObjError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ObjError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for ObjError {
+    fn from(e: std::io::Error) -> Self {
+        ObjError::Io(e)
+    }
+}
+
+/// Loads a mesh from a Wavefront OBJ file, see [parse_obj].
+///
+/// Example
+/// ```
+/// let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures/square_pyramid.obj");
+/// let pyramid = vviz::entities::load_obj(&path).unwrap();
+/// let mesh = pyramid.as_mesh().unwrap();
+/// assert!(mesh.validate().is_ok());
+/// assert_eq!(mesh.positions().len(), 5);
+/// // The quad base is split into two triangles.
+/// assert_eq!(mesh.faces.indices.len(), 2 + 4);
+/// ```
+pub fn load_obj(path: &std::path::Path) -> Result<Entity3, ObjError> {
+    parse_obj(std::io::BufReader::new(std::fs::File::open(path)?))
+}
+
+/// Parses a mesh in Wavefront OBJ format, i.e. its vertices (`v`) and faces (`f`).
+///
+/// Vertex colors given as `v x y z r g b` are used, other vertices are gray. Polygons are split
+/// into triangles as a fan about their first vertex. Texture coordinates, normals, groups and
+/// materials are ignored.
+pub fn parse_obj(reader: impl std::io::BufRead) -> Result<Entity3, ObjError> {
+    let gray = Color {
+        r: 0.7,
+        g: 0.7,
+        b: 0.7,
+        alpha: 1.0,
+    };
+    let mut vertices = PositionColorVertices::default();
+    let mut faces = std::vec::Vec::new();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        let parse_error = |message: String| ObjError::Parse {
+            line: i + 1,
+            message,
+        };
+        let mut tokens = line.split_whitespace();
+        match tokens.next() {
+            Some("v") => {
+                let values = tokens
+                    .map(|t| t.parse::<f32>())
+                    .collect::<Result<std::vec::Vec<_>, _>>()
+                    .map_err(|e| parse_error(format!("invalid vertex: {}", e)))?;
+                let color = match values.len() {
+                    3 | 4 => gray,
+                    6 => Color {
+                        r: values[3],
+                        g: values[4],
+                        b: values[5],
+                        alpha: 1.0,
+                    },
+                    n => return Err(parse_error(format!("vertex with {} values", n))),
+                };
+                let position = nalgebra::Vector3::new(values[0], values[1], values[2]);
+                vertices
+                    .vertices
+                    .push(PositionColorVertices::to_array(position, color));
+            }
+            Some("f") => {
+                let num_vertices = vertices.vertices.len() as i64;
+                let polygon = tokens
+                    .map(|t| {
+                        // Of `v/vt/vn`, only the vertex index is used; negative indices are
+                        // relative to the end of the current vertex list.
+                        let index: i64 = t.split('/').next().unwrap().parse().map_err(|e| {
+                            parse_error(format!("invalid face index '{}': {}", t, e))
+                        })?;
+                        let index = if index < 0 {
+                            num_vertices + index
+                        } else {
+                            index - 1
+                        };
+                        if index < 0 || index >= num_vertices {
+                            return Err(parse_error(format!("face index '{}' out of range", t)));
+                        }
+                        i16::try_from(index)
+                            .map_err(|_| ObjError::TooManyVertices(num_vertices as usize))
+                    })
+                    .collect::<Result<std::vec::Vec<_>, _>>()?;
+                if polygon.len() < 3 {
+                    return Err(parse_error(format!("face with {} vertices", polygon.len())));
+                }
+                for k in 1..polygon.len() - 1 {
+                    faces.push([polygon[0], polygon[k], polygon[k + 1]]);
+                }
+            }
+            _ => {}
+        }
+    }
+    if vertices.vertices.len() > i16::MAX as usize + 1 {
+        return Err(ObjError::TooManyVertices(vertices.vertices.len()));
+    }
+    Ok(Entity3::Mesh(
+        Mesh3::from_position_color_vertices_and_faces(vertices, Faces::new(faces)),
+    ))
+}
+
+/// Creates the entities to visualize a pose graph, ready to be passed to
+/// [super::manager::UiWidget3::place_entity_at].
+///
+/// There is one [Axis3] per node labeled `<label_prefix>_node_<i>` placed at
+/// `scene_poses_node[i]`, and one [LineSegments3] labeled `<label_prefix>_edges` connecting the
+/// node origins for each index pair in `edges`; hence several graphs with different prefixes can
+/// be shown in the same widget. If `edge_residuals` are given (one per edge), the edges are
+/// colored by the magnitude of the residual from green (zero) to red (largest), otherwise they are
+/// gray. Edges with non-finite residuals are red.
+///
+/// Panics if an edge refers to a non-existing node, or if the number of residuals does not match
+/// the number of edges.
+///
+/// Example
+/// ```
+/// let poses = vec![
+///     nalgebra::Isometry3::<f32>::identity(),
+///     nalgebra::Isometry3::<f32>::translation(1.0, 0.0, 0.0),
+///     nalgebra::Isometry3::<f32>::translation(2.0, 0.0, 0.0),
+/// ];
+/// let entities =
+///     vviz::entities::pose_graph("odometry", &poses, &[(0, 1), (1, 2)], 0.2, Some(&[-2.0, 1.0]));
+/// assert_eq!(entities.len(), poses.len() + 1);
+/// assert_eq!(entities[0].0, "odometry_node_0");
+///
+/// // The negative residual has the larger magnitude, hence its edge is red.
+/// let (label, edges, _) = &entities[3];
+/// assert_eq!(label, "odometry_edges");
+/// match edges {
+///     vviz::entities::Entity3::LineSegments(segments) => {
+///         let red_green = |v: usize| {
+///             let vertex = segments.vertices.vertices[v];
+///             (vertex[3], vertex[4])
+///         };
+///         assert_eq!(red_green(0), (1.0, 0.0));
+///         assert_eq!(red_green(2), (0.5, 0.5));
+///     }
+///     _ => unreachable!(),
+/// }
+/// ```
+pub fn pose_graph(
+    label_prefix: &str,
+    scene_poses_node: &[nalgebra::Isometry3<f32>],
+    edges: &[(usize, usize)],
+    axis_scale: f32,
+    edge_residuals: Option<&[f32]>,
+) -> std::vec::Vec<(String, Entity3, nalgebra::Isometry3<f32>)> {
+    let mut result = std::vec::Vec::with_capacity(scene_poses_node.len() + 1);
+    for (i, scene_pose_node) in scene_poses_node.iter().enumerate() {
+        result.push((
+            format!("{}_node_{}", label_prefix, i),
+            Axis3::from_scale(axis_scale).into(),
+            *scene_pose_node,
+        ));
+    }
+
+    if let Some(residuals) = edge_residuals {
+        assert_eq!(residuals.len(), edges.len());
+    }
+    let max_residual = edge_residuals
+        .map(|residuals| {
+            residuals
+                .iter()
+                .map(|residual| residual.abs())
+                .filter(|residual| residual.is_finite())
+                .fold(0.0, f32::max)
+        })
+        .unwrap_or(0.0);
+
+    let mut vertices = PositionColorVertices::default();
+    let mut indices = std::vec::Vec::with_capacity(edges.len());
+    for (k, (i, j)) in edges.iter().enumerate() {
+        let color = match edge_residuals {
+            Some(residuals) => {
+                let residual = residuals[k].abs();
+                let t = if !residual.is_finite() {
+                    1.0
+                } else if max_residual > 0.0 {
+                    residual / max_residual
+                } else {
+                    0.0
+                };
+                Color {
+                    r: t,
+                    g: 1.0 - t,
+                    b: 0.0,
+                    alpha: 1.0,
+                }
+            }
+            None => Color {
+                r: 0.5,
+                g: 0.5,
+                b: 0.5,
+                alpha: 1.0,
+            },
+        };
+        for node in [i, j] {
+            vertices.vertices.push(PositionColorVertices::to_array(
+                scene_poses_node[*node].translation.vector,
+                color,
+            ));
+        }
+        let idx: i16 = (2 * k).try_into().unwrap();
+        indices.push([idx, idx + 1]);
+    }
+    result.push((
+        format!("{}_edges", label_prefix),
+        Entity3::LineSegments(LineSegments3 { vertices, indices }),
+        nalgebra::Isometry3::identity(),
+    ));
+    result
+}
+
+/// 2d line segment in image pixel coordinates.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LineSegment2 {
+    /// Start point.
+    pub start: nalgebra::Vector2<f32>,
+    /// End point.
+    pub end: nalgebra::Vector2<f32>,
+    /// Color of the line segment.
+    pub color: Color,
+}
+
+/// 2d line segments to be overlaid on a [super::common::Widget2].
+///
+/// The coordinates are image pixel coordinates: (0, 0) is the top-left corner of the image, x
+/// points right and y points down.
+#[derive(Default, Serialize, Deserialize, Debug, Clone)]
+pub struct LineSegments2 {
+    /// The line segments.
+    pub segments: std::vec::Vec<LineSegment2>,
+}
+
+/// 2d points, drawn as filled circles.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Points2 {
+    /// The points.
+    pub points: std::vec::Vec<nalgebra::Vector2<f32>>,
+    /// Color of the points.
+    pub color: Color,
+    /// Radius in screen points, i.e. independent of the image size.
+    pub radius: f32,
+}
+
+/// 2d polyline, i.e. connected line segments.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Polyline2 {
+    /// The vertices of the polyline.
+    pub points: std::vec::Vec<nalgebra::Vector2<f32>>,
+    /// Color of the polyline.
+    pub color: Color,
+    /// Line width in screen points.
+    pub width: f32,
+    /// If true, the last point is connected to the first one.
+    pub closed: bool,
+}
+
+/// Axis-aligned 2d rectangle, e.g. a bounding box.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Rectangle2 {
+    /// Top-left corner.
+    pub min: nalgebra::Vector2<f32>,
+    /// Bottom-right corner.
+    pub max: nalgebra::Vector2<f32>,
+    /// Color of the rectangle.
+    pub color: Color,
+    /// Line width in screen points; the rectangle is filled if None.
+    pub width: Option<f32>,
+}
+
+/// 2d text, anchored at its top-left corner.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Text2 {
+    /// Top-left corner of the text.
+    pub position: nalgebra::Vector2<f32>,
+    /// The text.
+    pub text: String,
+    /// Text color.
+    pub color: Color,
+    /// Font size in points; snapped to the closest egui text style, i.e. small, body or heading.
+    pub size: f32,
+}
+
+/// 2d entity to be placed on a [super::common::Widget2].
+///
+/// As for [LineSegments2], all coordinates are image pixel coordinates: (0, 0) is the top-left
+/// corner of the image, x points right and y points down. Sizes such as line widths and radii
+/// are in screen points instead, so that entities stay legible for any image size.
+///
+/// Example
+/// ```
+/// let bounding_box = vviz::entities::Entity2::Rectangle(vviz::entities::Rectangle2 {
+///     min: nalgebra::Vector2::new(10.0, 20.0),
+///     max: nalgebra::Vector2::new(110.0, 70.0),
+///     color: vviz::entities::Color { r: 0.0, g: 1.0, b: 0.0, alpha: 1.0 },
+///     width: Some(2.0),
+/// });
+/// assert_eq!(bounding_box.as_rectangle().unwrap().max.x, 110.0);
+/// ```
+#[derive(enum_as_inner::EnumAsInner, Serialize, Deserialize, Debug, Clone)]
+pub enum Entity2 {
+    /// Points
+    Points(Points2),
+    /// Polyline
+    Polyline(Polyline2),
+    /// Line segments
+    LineSegments(LineSegments2),
+    /// Rectangle
+    Rectangle(Rectangle2),
+    /// Text
+    Text(Text2),
+}
+
+/// Keypoint correspondence between a left and a right image, in image pixel coordinates.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Correspondence {
+    /// Keypoint in left image.
+    pub left: nalgebra::Vector2<f32>,
+    /// Keypoint in right image.
+    pub right: nalgebra::Vector2<f32>,
+    /// Match quality in [0..1]; 0 is drawn red, 1 is drawn green.
+    pub quality: f32,
+    /// Whether the correspondence is an inlier.
+    pub inlier: bool,
+}
@@ -0,0 +1,40 @@
+//! Crate-wide error type.
+
+/// Error of [super::app::spawn] and friends.
+#[derive(Debug)]
+pub enum VvizError {
+    /// The gui window could not be created, or the gui loop panicked. Contains the panic message.
+    Gui(String),
+    /// The websocket server of a remote [super::manager::Manager] could not bind its address, or
+    /// could not accept a client.
+    RemoteBind(std::io::Error),
+    /// A message could not be serialized or deserialized, e.g. when exchanging messages with a
+    /// remote client.
+    Serialization(serde_json::Error),
+}
+
+impl std::fmt::Display for VvizError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VvizError::Gui(message) => write!(f, "gui failed: {}", message),
+            VvizError::RemoteBind(e) => write!(f, "failed to start websocket server: {}", e),
+            VvizError::Serialization(e) => write!(f, "failed to (de)serialize message: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for VvizError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            VvizError::Gui(_) => None,
+            VvizError::RemoteBind(e) => Some(e),
+            VvizError::Serialization(e) => Some(e),
+        }
+    }
+}
+
+impl From<serde_json::Error> for VvizError {
+    fn from(e: serde_json::Error) -> Self {
+        VvizError::Serialization(e)
+    }
+}
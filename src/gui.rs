@@ -5,11 +5,105 @@ use std::sync::mpsc;
 use super::common;
 
 /// [super::common::Component]s of the side-panel and [super::common::Widget]s of the main panel.
+///
+/// It can be driven by [common::ToGuiLoopMessage]s without a graphics context:
+///
+/// ```
+/// use vviz::common::*;
+///
+/// let mut data = vviz::gui::GuiData::default();
+/// ToGuiLoopMessage::AddButton(AddButton { label: "button".to_string() }).update_gui(&mut data);
+/// ToGuiLoopMessage::AddWidget3(AddWidget3 {
+///     label: "w3d".to_string(),
+///     camera_pose_scene: None,
+/// })
+/// .update_gui(&mut data);
+/// ToGuiLoopMessage::PlaceEntity3(PlaceEntity3 {
+///     widget_label: "w3d".to_string(),
+///     named_entity: vviz::entities::NamedEntity3 {
+///         label: "cube".to_string(),
+///         entity: vviz::entities::colored_cube(1.0),
+///         scene_pose_entity: nalgebra::Isometry3::identity(),
+///         scale: nalgebra::Vector3::repeat(1.0),
+///         cull_mode: None,
+///         depth_bias: 0.0,
+///     },
+/// })
+/// .update_gui(&mut data);
+/// let scene_pose_entity = nalgebra::Isometry3::translation(1.0, 2.0, 3.0);
+/// ToGuiLoopMessage::UpdateScenePoseEntity3(UpdateScenePoseEntity3 {
+///     widget_label: "w3d".to_string(),
+///     entity_label: "cube".to_string(),
+///     scene_pose_entity,
+/// })
+/// .update_gui(&mut data);
+/// ToGuiLoopMessage::DeleteComponent(DeleteComponent { label: "button".to_string() })
+///     .update_gui(&mut data);
+///
+/// assert!(data.components.is_empty());
+/// let w3d = data.widgets["w3d"].downcast_ref::<Widget3>().unwrap();
+/// assert_eq!(w3d.entities()["cube"].scene_pose_entity, scene_pose_entity);
+/// ```
 pub struct GuiData {
     /// List of components such as buttons, sliders etc.
     pub components: linked_hash_map::LinkedHashMap<String, Box<dyn common::Component>>,
     /// List of widgets such as 3d widgets.
     pub widgets: linked_hash_map::LinkedHashMap<String, Box<dyn common::Widget>>,
+    /// Panic message, if the application thread panicked.
+    pub app_thread_panic: Option<String>,
+    /// Currently visible notifications, oldest first.
+    pub notifications: std::vec::Vec<Notification>,
+    /// Color theme; if None, egui's default visuals are used.
+    pub theme: Option<common::Theme>,
+    /// Whether the entities of all 3d widgets are listed in the side panel.
+    pub show_entity_list: bool,
+    /// Whether the label of each widget is shown above it.
+    pub show_widget_titles: bool,
+    /// Number of columns of the widget grid; if None, it is chosen to maximize the widget size.
+    pub grid_columns: Option<usize>,
+    /// Maximal number of frames per second; if None, frames are only paced by vsync.
+    pub max_frame_rate: Option<f32>,
+    /// If set, the window is saved as png to this path after the next frame was drawn.
+    pub window_capture_path: Option<std::path::PathBuf>,
+    /// Whether keyboard and mouse events are forwarded to the application.
+    pub forward_input_events: bool,
+    /// Scale of text and spacing on top of the DPI scale of the window.
+    pub ui_scale: f32,
+}
+
+/// A transient notification, see [common::Notify].
+pub struct Notification {
+    /// The text to show.
+    pub text: String,
+    /// The severity.
+    pub level: common::Level,
+    /// When the notification was received.
+    pub created: std::time::Instant,
+}
+
+impl Notification {
+    /// How long a notification is shown in total, including fade-out.
+    const LIFETIME: std::time::Duration = std::time::Duration::from_secs(4);
+    /// Duration of the fade-out at the end of its lifetime.
+    const FADE_OUT: std::time::Duration = std::time::Duration::from_secs(1);
+
+    fn is_expired(&self) -> bool {
+        self.created.elapsed() >= Self::LIFETIME
+    }
+
+    /// Opacity in [0..1]; 1.0 until the fade-out starts.
+    fn opacity(&self) -> f32 {
+        let remaining = Self::LIFETIME.saturating_sub(self.created.elapsed());
+        (remaining.as_secs_f32() / Self::FADE_OUT.as_secs_f32()).min(1.0)
+    }
+
+    fn color(&self) -> egui::Color32 {
+        match self.level {
+            common::Level::Info => egui::Color32::WHITE,
+            common::Level::Warn => egui::Color32::YELLOW,
+            common::Level::Error => egui::Color32::RED,
+        }
+    }
 }
 
 impl Default for GuiData {
@@ -17,32 +111,330 @@ impl Default for GuiData {
         Self {
             components: linked_hash_map::LinkedHashMap::new(),
             widgets: linked_hash_map::LinkedHashMap::new(),
+            app_thread_panic: None,
+            notifications: std::vec::Vec::new(),
+            theme: None,
+            show_entity_list: true,
+            show_widget_titles: true,
+            grid_columns: None,
+            max_frame_rate: None,
+            window_capture_path: None,
+            forward_input_events: false,
+            ui_scale: 1.0,
+        }
+    }
+}
+
+/// Static capture of [GuiData], i.e. all components and the entities of all 3d widgets.
+///
+/// It is represented as the list of messages which recreate the captured state, and hence is
+/// serializable.
+///
+/// ```
+/// use vviz::common::*;
+///
+/// let mut data = vviz::gui::GuiData::default();
+/// ToGuiLoopMessage::AddVarBool(AddVar { label: "flag".to_string(), value: true })
+///     .update_gui(&mut data);
+/// ToGuiLoopMessage::AddWidget3(AddWidget3 {
+///     label: "w3d".to_string(),
+///     camera_pose_scene: None,
+/// })
+/// .update_gui(&mut data);
+///
+/// let json = serde_json::to_string(&data.to_snapshot()).unwrap();
+/// let restored = vviz::gui::GuiData::from_snapshot(serde_json::from_str(&json).unwrap());
+/// assert!(restored.components["flag"].downcast_ref::<Var<bool>>().unwrap().value);
+/// assert!(restored.widgets.contains_key("w3d"));
+/// ```
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+pub struct GuiSnapshot {
+    /// Messages to recreate the state, in order.
+    pub messages: std::vec::Vec<common::ToGuiLoopMessage>,
+}
+
+impl GuiData {
+    /// Captures all components and the entities of all 3d widgets.
+    ///
+    /// 2d widgets are not captured.
+    pub fn to_snapshot(&self) -> GuiSnapshot {
+        let mut messages = std::vec::Vec::new();
+        for (label, component) in &self.components {
+            messages.extend(component.snapshot(label));
+        }
+        for (label, widget) in &self.widgets {
+            messages.extend(widget.snapshot(label));
+        }
+        GuiSnapshot { messages }
+    }
+
+    /// Recreates gui data from `snapshot`.
+    pub fn from_snapshot(snapshot: GuiSnapshot) -> Self {
+        let mut data = Self::default();
+        for m in snapshot.messages {
+            m.update_gui(&mut data);
         }
+        data
     }
 }
 
+/// Largest rect with `aspect_ratio` (width / height) which fits into `rect`, centered; i.e. where
+/// an image is drawn when letterboxed into `rect`.
+///
+/// Example
+/// ```
+/// use vviz::gui::letterbox;
+///
+/// let rect = egui::Rect::from_min_size(egui::pos2(10.0, 20.0), egui::vec2(400.0, 100.0));
+/// // Wider than the image: bars left and right.
+/// assert_eq!(
+///     letterbox(rect, 2.0),
+///     egui::Rect::from_min_size(egui::pos2(110.0, 20.0), egui::vec2(200.0, 100.0))
+/// );
+/// // Taller than the image: bars at the top and the bottom.
+/// assert_eq!(
+///     letterbox(rect, 8.0),
+///     egui::Rect::from_min_size(egui::pos2(10.0, 45.0), egui::vec2(400.0, 50.0))
+/// );
+/// assert_eq!(letterbox(rect, 4.0), rect);
+/// ```
+pub fn letterbox(rect: egui::Rect, aspect_ratio: f32) -> egui::Rect {
+    let width = rect.width().min(aspect_ratio * rect.height());
+    let size = egui::Vec2::new(width, width / aspect_ratio);
+    egui::Rect::from_center_size(rect.center(), size)
+}
+
+/// Screen rect of the image within the widget `response`, given the image's `aspect_ratio`; see
+/// [letterbox].
+///
+/// All mapping between screen and image coordinates of a widget - clicks, hovering, overlays -
+/// goes through this rect.
+pub fn image_rect(response: &egui::Response, aspect_ratio: f32) -> egui::Rect {
+    letterbox(response.rect, aspect_ratio)
+}
+
+/// Computes the grid layout of widgets with given `aspect_ratios` (width / height) within an
+/// area of size `available_width` x `available_height`.
+///
+/// All cells share the same size, with the median aspect ratio of all widgets. The number of
+/// columns is chosen such that the cells are as large as possible. Returns the number of columns
+/// and the cell width and height; or `(0, 0.0, 0.0)` if there are no widgets.
+///
+/// Example
+/// ```
+/// use vviz::gui::compute_widget_layout;
+///
+/// assert_eq!(compute_widget_layout(&[], 400.0, 300.0), (0, 0.0, 0.0));
+/// assert_eq!(compute_widget_layout(&[4.0 / 3.0], 400.0, 300.0), (1, 400.0, 300.0));
+/// // Area too wide: height is the limiting factor.
+/// assert_eq!(compute_widget_layout(&[1.0], 400.0, 100.0), (1, 100.0, 100.0));
+///
+/// // Two widgets side by side in a wide area, stacked in a tall one.
+/// assert_eq!(compute_widget_layout(&[1.0, 1.0], 200.0, 100.0), (2, 100.0, 100.0));
+/// assert_eq!(compute_widget_layout(&[1.0, 1.0], 100.0, 200.0), (1, 100.0, 100.0));
+///
+/// // Mixed aspect ratios use the median; for an even count the mean of the middle two.
+/// assert_eq!(compute_widget_layout(&[2.0, 1.0], 300.0, 100.0), (2, 150.0, 100.0));
+/// assert_eq!(compute_widget_layout(&[0.5, 4.0, 1.0], 300.0, 100.0), (3, 100.0, 100.0));
+/// assert_eq!(compute_widget_layout(&[1.0, 2.0, 1.0, 1.0], 200.0, 200.0), (2, 100.0, 100.0));
+/// let (_, w, h) = compute_widget_layout(&[2.0, 1.0, 2.0, 1.0, 2.0, 1.0], 600.0, 600.0);
+/// assert!((w / h - 1.5).abs() < 1e-6);
+///
+/// // Square widgets in a square area.
+/// for (n, expected_cols) in [(1, 1), (2, 1), (3, 2), (4, 2), (5, 2), (6, 2), (7, 3), (8, 3)] {
+///     let (cols, w, h) = compute_widget_layout(&vec![1.0; n], 600.0, 600.0);
+///     assert_eq!(cols, expected_cols, "n = {}", n);
+///     let rows = (n + cols - 1) / cols;
+///     assert!((w - h).abs() < 1e-3);
+///     assert!(cols as f32 * w <= 600.0 + 1e-3 && rows as f32 * h <= 600.0 + 1e-3);
+/// }
+/// ```
+pub fn compute_widget_layout(
+    aspect_ratios: &[f32],
+    available_width: f32,
+    available_height: f32,
+) -> (usize, f32, f32) {
+    let mut best = (0, 0.0, 0.0);
+    for num_cols in 1..=aspect_ratios.len() {
+        let layout = compute_widget_layout_with_columns(
+            aspect_ratios,
+            num_cols,
+            available_width,
+            available_height,
+        );
+        if layout.1 > best.1 {
+            best = layout;
+        }
+    }
+    best
+}
+
+/// Same as [compute_widget_layout], but with a fixed number of columns `num_cols`, e.g. for a
+/// grid of small multiples. It is clamped to the number of widgets.
+///
+/// Example
+/// ```
+/// use vviz::gui::compute_widget_layout_with_columns;
+///
+/// // Three columns, even though a single row of four would be larger.
+/// assert_eq!(
+///     compute_widget_layout_with_columns(&[1.0; 4], 3, 600.0, 100.0),
+///     (3, 50.0, 50.0)
+/// );
+/// assert_eq!(
+///     compute_widget_layout_with_columns(&[1.0; 2], 3, 600.0, 100.0),
+///     (2, 100.0, 100.0)
+/// );
+/// assert_eq!(compute_widget_layout_with_columns(&[], 3, 600.0, 100.0), (0, 0.0, 0.0));
+/// ```
+pub fn compute_widget_layout_with_columns(
+    aspect_ratios: &[f32],
+    num_cols: usize,
+    available_width: f32,
+    available_height: f32,
+) -> (usize, f32, f32) {
+    let n = aspect_ratios.len();
+    let num_cols = num_cols.min(n);
+    if num_cols == 0 {
+        return (0, 0.0, 0.0);
+    }
+    let mut sorted = aspect_ratios.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let median_aspect_ratio = if n % 2 == 1 {
+        sorted[n / 2]
+    } else {
+        0.5 * sorted[n / 2 - 1] + 0.5 * sorted[n / 2]
+    };
+
+    let num_rows = ((n as f32) / (num_cols as f32)).ceil();
+    let w = available_width / (num_cols as f32);
+    let h = (w / median_aspect_ratio).min(available_height / num_rows);
+    (num_cols, median_aspect_ratio * h, h)
+}
+
+/// Reads the default framebuffer, i.e. the content of the window, at its physical resolution.
+fn read_framebuffer(ctx: &miniquad::Context) -> image::RgbaImage {
+    let (width, height) = ctx.screen_size();
+    let (width, height) = (width as u32, height as u32);
+    let mut bytes = vec![0_u8; 4 * width as usize * height as usize];
+    unsafe {
+        miniquad::gl::glReadPixels(
+            0,
+            0,
+            width as _,
+            height as _,
+            miniquad::gl::GL_RGBA,
+            miniquad::gl::GL_UNSIGNED_BYTE,
+            bytes.as_mut_ptr() as _,
+        );
+    }
+    let mut rgba = image::RgbaImage::from_raw(width, height, bytes).unwrap();
+    // GL rows are bottom to top, and the window itself is opaque.
+    image::imageops::flip_vertical_in_place(&mut rgba);
+    for pixel in rgba.pixels_mut() {
+        pixel.0[3] = 255;
+    }
+    rgba
+}
+
 /// Structure which holds data for main gui loop.
 pub struct GuiLoop {
     egui_mq: egui_miniquad::EguiMq,
     to_gui_loop_receiver: mpsc::Receiver<common::ToGuiLoopMessage>,
     from_gui_loop_sender: mpsc::Sender<common::FromGuiLoopMessage>,
     data: GuiData,
+    /// Style to be applied in the first frame.
+    initial_style: Option<egui::Style>,
+    /// Theme which was applied to the egui context last.
+    applied_theme: Option<common::Theme>,
+    /// When the last frame was committed, to pace frames according to the max frame rate.
+    last_frame: std::time::Instant,
+    /// DPI scale which was last reported to the manager.
+    reported_dpi_scale: Option<f32>,
+    /// UI scale which was applied to the egui context last.
+    applied_ui_scale: f32,
 }
 
 impl GuiLoop {
+    /// Lower bound of the maximal frame rate, see [GuiLoop::with_max_frame_rate].
+    const MIN_FRAME_RATE: f32 = 1.0;
+    /// Range of the UI scale, see [super::manager::Manager::set_ui_scale].
+    const UI_SCALE_RANGE: std::ops::RangeInclusive<f32> = 0.25..=4.0;
+
     /// Creates `GuiLoop` given `miniquad::Context` and sender/receiver structs.
     pub fn new(
         ctx: &mut miniquad::Context,
         to_gui_loop_receiver: mpsc::Receiver<common::ToGuiLoopMessage>,
         from_gui_loop_sender: mpsc::Sender<common::FromGuiLoopMessage>,
     ) -> GuiLoop {
+        super::capabilities::query_gpu_limits();
         GuiLoop {
             egui_mq: egui_miniquad::EguiMq::new(ctx),
             to_gui_loop_receiver,
             from_gui_loop_sender,
             data: GuiData::default(),
+            initial_style: None,
+            applied_theme: None,
+            last_frame: std::time::Instant::now(),
+            reported_dpi_scale: None,
+            applied_ui_scale: 1.0,
         }
     }
+
+    /// Limits the number of frames per second from the first frame on, e.g. to save power. See
+    /// [super::manager::Manager::set_max_frame_rate]. Rates below one frame per second are
+    /// treated as one.
+    pub fn with_max_frame_rate(mut self, max_frame_rate: f32) -> Self {
+        self.data.max_frame_rate = Some(max_frame_rate);
+        self
+    }
+
+    /// Sets the egui style - e.g. font sizes, spacing and visuals - used from the first frame on.
+    ///
+    /// A theme set later on via [super::manager::Manager::set_theme] replaces the visuals.
+    pub fn with_style(mut self, style: egui::Style) -> Self {
+        self.initial_style = Some(style);
+        self
+    }
+
+    // TODO: Forward files dropped onto the window, once miniquad reports drop events.
+    fn forward_input_event(&self, event: common::InputEvent) {
+        if !self.data.forward_input_events {
+            return;
+        }
+        // The application thread might have finished already, in which case nobody listens.
+        let _ = self
+            .from_gui_loop_sender
+            .send(common::FromGuiLoopMessage::InputEvent(event));
+    }
+
+    fn forward_key_event(&self, event: common::InputEvent) {
+        if !self.egui_mq.egui_ctx().wants_keyboard_input() {
+            self.forward_input_event(event);
+        }
+    }
+
+    fn forward_mouse_event(&self, event: common::InputEvent) {
+        if !self.egui_mq.egui_ctx().wants_pointer_input() {
+            self.forward_input_event(event);
+        }
+    }
+}
+
+/// Scales all sizes of `spacing` by `factor`.
+fn scale_spacing(spacing: &mut egui::style::Spacing, factor: f32) {
+    spacing.item_spacing *= factor;
+    spacing.window_padding *= factor;
+    spacing.button_padding *= factor;
+    spacing.indent *= factor;
+    spacing.interact_size *= factor;
+    spacing.slider_width *= factor;
+    spacing.text_edit_width *= factor;
+    spacing.icon_width *= factor;
+    spacing.icon_spacing *= factor;
+    spacing.tooltip_width *= factor;
+    spacing.combo_height *= factor;
+    spacing.scroll_bar_width *= factor;
 }
 
 impl miniquad::EventHandler for GuiLoop {
@@ -50,18 +442,156 @@ impl miniquad::EventHandler for GuiLoop {
 
     fn draw(&mut self, ctx: &mut miniquad::Context) {
         for m in self.to_gui_loop_receiver.try_iter() {
-            m.update_gui(&mut self.data, ctx);
+            m.update_gui(&mut self.data);
         }
 
-        for (_, w) in &mut self.data.widgets {
+        // egui-miniquad sets egui's pixels per point to this scale in every frame, and paints with
+        // it; hence it cannot be overridden and the UI scale is applied to text and spacing instead.
+        let dpi_scale = ctx.dpi_scale();
+        if self.reported_dpi_scale != Some(dpi_scale) {
+            self.reported_dpi_scale = Some(dpi_scale);
+            let _ = self
+                .from_gui_loop_sender
+                .send(common::FromGuiLoopMessage::UpdateDpiScale(
+                    common::UpdateDpiScale { dpi_scale },
+                ));
+        }
+
+        for (label, w) in &mut self.data.widgets {
             w.render(ctx);
+            if let Some(point) = w
+                .downcast_mut::<common::Widget3>()
+                .and_then(|w| w.take_hovered_point_update())
+            {
+                let _ = self.from_gui_loop_sender.send(
+                    common::FromGuiLoopMessage::UpdateHoveredPoint3(common::UpdateHoveredPoint3 {
+                        widget_label: label.clone(),
+                        point,
+                    }),
+                );
+            }
+            if let Some(entity_label) = w
+                .downcast_mut::<common::Widget3>()
+                .and_then(|w| w.take_hovered_entity_update())
+            {
+                let _ = self.from_gui_loop_sender.send(
+                    common::FromGuiLoopMessage::UpdateHoveredEntity3(
+                        common::UpdateHoveredEntity3 {
+                            widget_label: label.clone(),
+                            entity_label,
+                        },
+                    ),
+                );
+            }
+            if let Some(camera_pose_scene) = w
+                .downcast_mut::<common::Widget3>()
+                .and_then(|w| w.take_camera_pose_update())
+            {
+                let _ =
+                    self.from_gui_loop_sender
+                        .send(common::FromGuiLoopMessage::UpdateCameraPose(
+                            common::UpdateCameraPose {
+                                widget_label: label.clone(),
+                                scene_pose_camera: camera_pose_scene.inverse(),
+                            },
+                        ));
+            }
+            if let Some(distance) = w
+                .downcast_mut::<common::Widget3>()
+                .and_then(|w| w.take_measurement_update())
+            {
+                let _ =
+                    self.from_gui_loop_sender
+                        .send(common::FromGuiLoopMessage::UpdateMeasurement3(
+                            common::UpdateMeasurement3 {
+                                widget_label: label.clone(),
+                                distance,
+                            },
+                        ));
+            }
         }
 
+        let mut quit_requested = false;
         self.egui_mq.run(ctx, |egui_ctx| {
+            if let Some(style) = self.initial_style.take() {
+                egui_ctx.set_style(style);
+            }
+            if self.data.theme != self.applied_theme {
+                if let Some(theme) = self.data.theme {
+                    egui_ctx.set_visuals(theme.visuals());
+                }
+                self.applied_theme = self.data.theme;
+            }
+            let ui_scale = if self.data.ui_scale.is_finite() {
+                self.data
+                    .ui_scale
+                    .clamp(*Self::UI_SCALE_RANGE.start(), *Self::UI_SCALE_RANGE.end())
+            } else {
+                1.0
+            };
+            if ui_scale != self.applied_ui_scale {
+                let mut fonts = egui::FontDefinitions::default();
+                for (_family, size) in fonts.family_and_size.values_mut() {
+                    *size *= ui_scale;
+                }
+                egui_ctx.set_fonts(fonts);
+                let mut style = (*egui_ctx.style()).clone();
+                scale_spacing(&mut style.spacing, ui_scale / self.applied_ui_scale);
+                egui_ctx.set_style(style);
+                self.applied_ui_scale = ui_scale;
+            }
+            if let Some(message) = &self.data.app_thread_panic {
+                egui::Window::new("Application thread panicked")
+                    .collapsible(false)
+                    .resizable(false)
+                    .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+                    .show(egui_ctx, |ui| {
+                        ui.colored_label(egui::Color32::RED, message);
+                        ui.label("The gui is not updated anymore. See stderr for the backtrace.");
+                        if ui.button("Quit").clicked() {
+                            quit_requested = true;
+                        }
+                    });
+            }
+
+            self.data.notifications.retain(|n| !n.is_expired());
+            if !self.data.notifications.is_empty() {
+                egui::Area::new("notifications")
+                    .anchor(egui::Align2::RIGHT_BOTTOM, egui::Vec2::new(-10.0, -10.0))
+                    .interactable(false)
+                    .show(egui_ctx, |ui| {
+                        for notification in &self.data.notifications {
+                            let opacity = notification.opacity();
+                            egui::Frame::popup(ui.style())
+                                .fill(egui::Color32::from_black_alpha((200.0 * opacity) as u8))
+                                .show(ui, |ui| {
+                                    ui.colored_label(
+                                        notification.color().linear_multiply(opacity),
+                                        &notification.text,
+                                    );
+                                });
+                        }
+                    });
+            }
+
             egui::SidePanel::left("ver").show(egui_ctx, |ui| {
                 for (label, var) in &mut self.data.components {
                     var.show(label, ui, &mut self.from_gui_loop_sender);
                 }
+                if !self.data.show_entity_list {
+                    return;
+                }
+                for (label, widget) in &mut self.data.widgets {
+                    let widget3 = match widget.downcast_mut::<common::Widget3>() {
+                        Some(widget3) if !widget3.entities().is_empty() => widget3,
+                        _ => continue,
+                    };
+                    ui.separator();
+                    egui::CollapsingHeader::new(label)
+                        .id_source(("entity_list", label))
+                        .default_open(true)
+                        .show(ui, |ui| widget3.show_entity_list(ui));
+                }
             });
 
             egui::CentralPanel::default().show(egui_ctx, |ui0| {
@@ -73,36 +603,42 @@ impl miniquad::EventHandler for GuiLoop {
                 let available_width: f32 = 0.95 * ui0.available_width();
                 let available_height: f32 = 0.95 * ui0.available_height();
 
-                let mut aspect_ratios = std::vec::Vec::with_capacity(self.data.widgets.len());
-                for (_, widget) in &mut self.data.widgets {
-                    aspect_ratios.push(widget.aspect_ratio());
-                }
-                aspect_ratios.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
-                let n = aspect_ratios.len();
-                let median_aspect_ratio = if n % 2 == 1 {
-                    aspect_ratios[n / 2]
+                let aspect_ratios: std::vec::Vec<f32> = self
+                    .data
+                    .widgets
+                    .values()
+                    .map(|widget| widget.aspect_ratio())
+                    .collect();
+                let (_, max_width, max_height) = match self.data.grid_columns {
+                    Some(num_cols) => compute_widget_layout_with_columns(
+                        &aspect_ratios,
+                        num_cols,
+                        available_width,
+                        available_height,
+                    ),
+                    None => {
+                        compute_widget_layout(&aspect_ratios, available_width, available_height)
+                    }
+                };
+                let show_titles = self.data.show_widget_titles;
+                // The title bar takes space from the widget, which keeps its aspect ratio.
+                let title_height = if show_titles {
+                    ui0.fonts().row_height(egui::TextStyle::Body) + ui0.spacing().item_spacing.y
                 } else {
-                    0.5 * aspect_ratios[n / 2] + 0.5 * aspect_ratios[n / 2 + 1]
+                    0.0
                 };
 
-                let mut max_width = 0.0;
-                let mut max_height = 0.0;
-
-                for num_cols in 1..=n {
-                    let num_rows: f32 = ((n as f32) / (num_cols as f32)).ceil();
-
-                    let w: f32 = available_width / (num_cols as f32);
-                    let h = (w / median_aspect_ratio).min(available_height / num_rows);
-                    let w = median_aspect_ratio * h;
-                    if w > max_width {
-                        max_width = w;
-                        max_height = h;
-                    }
-                }
-
                 ui0.horizontal_wrapped(|ui| {
-                    for (_, widget) in &mut self.data.widgets {
-                        let opt = widget.show(ui, max_width, max_height);
+                    for (label, widget) in &mut self.data.widgets {
+                        let opt = if show_titles {
+                            ui.vertical(|ui| {
+                                ui.label(label);
+                                widget.show(ui, max_width, max_height - title_height)
+                            })
+                            .inner
+                        } else {
+                            widget.show(ui, max_width, max_height)
+                        };
                         let r = opt.unwrap();
                         // println!(
                         //     "{} {} {} {}",
@@ -122,15 +658,46 @@ impl miniquad::EventHandler for GuiLoop {
 
         self.egui_mq.draw(ctx);
 
+        if let Some(path) = self.data.window_capture_path.take() {
+            let (text, level) = match read_framebuffer(ctx).save(&path) {
+                Ok(()) => (
+                    format!("Saved window to {}", path.display()),
+                    common::Level::Info,
+                ),
+                Err(e) => (
+                    format!("Failed to save window to {}: {}", path.display(), e),
+                    common::Level::Error,
+                ),
+            };
+            common::ToGuiLoopMessage::Notify(common::Notify { text, level })
+                .update_gui(&mut self.data);
+        }
+
         ctx.commit_frame();
+
+        if let Some(max_frame_rate) = self.data.max_frame_rate.filter(|fps| *fps > 0.0) {
+            // Tiny rates would stall the gui, or overflow the duration.
+            let max_frame_rate = max_frame_rate.max(Self::MIN_FRAME_RATE);
+            let frame_duration = std::time::Duration::from_secs_f32(1.0 / max_frame_rate);
+            if let Some(remaining) = frame_duration.checked_sub(self.last_frame.elapsed()) {
+                std::thread::sleep(remaining);
+            }
+        }
+        self.last_frame = std::time::Instant::now();
+
+        if quit_requested {
+            ctx.request_quit();
+        }
     }
 
     fn mouse_motion_event(&mut self, ctx: &mut miniquad::Context, x: f32, y: f32) {
         self.egui_mq.mouse_motion_event(ctx, x, y);
+        self.forward_mouse_event(common::InputEvent::MouseMotion { x, y });
     }
 
     fn mouse_wheel_event(&mut self, ctx: &mut miniquad::Context, dx: f32, dy: f32) {
         self.egui_mq.mouse_wheel_event(ctx, dx, dy);
+        self.forward_mouse_event(common::InputEvent::MouseWheel { dx, dy });
     }
 
     fn mouse_button_down_event(
@@ -141,6 +708,11 @@ impl miniquad::EventHandler for GuiLoop {
         y: f32,
     ) {
         self.egui_mq.mouse_button_down_event(ctx, mb, x, y);
+        self.forward_mouse_event(common::InputEvent::MouseButtonDown {
+            button: mb.into(),
+            x,
+            y,
+        });
     }
 
     fn mouse_button_up_event(
@@ -151,6 +723,11 @@ impl miniquad::EventHandler for GuiLoop {
         y: f32,
     ) {
         self.egui_mq.mouse_button_up_event(ctx, mb, x, y);
+        self.forward_mouse_event(common::InputEvent::MouseButtonUp {
+            button: mb.into(),
+            x,
+            y,
+        });
     }
 
     fn char_event(
@@ -168,8 +745,13 @@ impl miniquad::EventHandler for GuiLoop {
         ctx: &mut miniquad::Context,
         keycode: miniquad::KeyCode,
         keymods: miniquad::KeyMods,
-        _repeat: bool,
+        repeat: bool,
     ) {
+        self.forward_key_event(common::InputEvent::KeyDown {
+            key: format!("{:?}", keycode),
+            modifiers: keymods.into(),
+            repeat,
+        });
         self.egui_mq.key_down_event(ctx, keycode, keymods);
     }
 
@@ -179,6 +761,10 @@ impl miniquad::EventHandler for GuiLoop {
         keycode: miniquad::KeyCode,
         keymods: miniquad::KeyMods,
     ) {
+        self.forward_key_event(common::InputEvent::KeyUp {
+            key: format!("{:?}", keycode),
+            modifiers: keymods.into(),
+        });
         self.egui_mq.key_up_event(keycode, keymods);
     }
 }
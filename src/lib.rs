@@ -3,13 +3,22 @@
 #![warn(missing_docs)]
 
 pub mod app;
+pub mod camera;
+pub mod capabilities;
+pub mod colormap;
 pub mod common;
 pub mod entities;
+pub mod error;
 pub mod gui;
 pub mod manager;
 pub mod math;
+#[cfg(feature = "opencv")]
+pub mod opencv_interop;
 pub mod utilities;
 
+pub use capabilities::capabilities;
+pub use error::VvizError;
+
 // Makes sure that example code in the readme compiles.
 #[doc = include_str!("../README.md")]
 #[cfg(doctest)]
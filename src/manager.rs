@@ -11,11 +11,188 @@ use crate::common::ToGuiLoopMessage;
 
 use super::common;
 use super::entities;
+use super::error::VvizError;
+use super::gui;
 
 /// Shared data between the varies ui structs such [UiButton], [UiWidget3] and [UiVar<T>].
 pub struct Shared {
     components: LinkedHashMap<String, Box<dyn common::Component>>,
     message_queue: std::collections::VecDeque<common::ToGuiLoopMessage>,
+    input_events: std::vec::Vec<common::InputEvent>,
+    /// DPI scale of the gui window, once reported.
+    dpi_scale: Option<f32>,
+    /// Labels of the entities of each 3d widget, in order of placement.
+    entity_labels: std::collections::HashMap<String, LinkedHashMap<String, ()>>,
+    /// Hovered scene point of each 3d widget, if any.
+    hovered_points: std::collections::HashMap<String, nalgebra::Vector3<f32>>,
+    /// Label of the hovered entity of each 3d widget, if any.
+    hovered_entities: std::collections::HashMap<String, String>,
+    /// Camera pose of each 3d widget - the transformation from camera to scene - once reported.
+    camera_poses: std::collections::HashMap<String, nalgebra::Isometry3<f32>>,
+    /// Last completed measurement of each 3d widget, if any.
+    measurements: std::collections::HashMap<String, f32>,
+    /// Opacity sliders by slider label, with the widget and entity label they control.
+    opacity_controls: std::collections::HashMap<String, (String, String)>,
+    /// Values of the components when they were first synced, see [Manager::reset_values].
+    initial_values: ComponentValues,
+    presets: LinkedHashMap<String, ComponentValues>,
+    /// Labels of all preset controls, see [Manager::add_preset_controls].
+    preset_controls: std::vec::Vec<String>,
+    /// Number of entities drawn with [UiWidget3::draw_once] of each 3d widget, since and before
+    /// the last sync.
+    draw_once_counts: std::collections::HashMap<String, (usize, usize)>,
+}
+
+/// Values of components by component label, see [common::Component::values].
+type ComponentValues = LinkedHashMap<String, std::vec::Vec<FromGuiLoopMessage>>;
+
+impl Shared {
+    /// Applies a message received from the gui.
+    fn handle(&mut self, m: FromGuiLoopMessage) {
+        match m {
+            FromGuiLoopMessage::InputEvent(event) => self.input_events.push(event),
+            FromGuiLoopMessage::UpdateDpiScale(e) => self.dpi_scale = Some(e.dpi_scale),
+            FromGuiLoopMessage::UpdateHoveredPoint3(e) => match e.point {
+                Some(point) => {
+                    self.hovered_points.insert(e.widget_label, point);
+                }
+                None => {
+                    self.hovered_points.remove(&e.widget_label);
+                }
+            },
+            FromGuiLoopMessage::UpdateHoveredEntity3(e) => match e.entity_label {
+                Some(entity_label) => {
+                    self.hovered_entities.insert(e.widget_label, entity_label);
+                }
+                None => {
+                    self.hovered_entities.remove(&e.widget_label);
+                }
+            },
+            FromGuiLoopMessage::UpdateCameraPose(e) => {
+                self.camera_poses
+                    .insert(e.widget_label, e.scene_pose_camera);
+            }
+            FromGuiLoopMessage::UpdateMeasurement3(e) => {
+                self.measurements.insert(e.widget_label, e.distance);
+            }
+            FromGuiLoopMessage::UpdateRangedValueF32(e)
+                if self.opacity_controls.contains_key(&e.label) =>
+            {
+                let (widget_label, entity_label) = self.opacity_controls[&e.label].clone();
+                self.message_queue
+                    .push_back(ToGuiLoopMessage::SetEntityOpacity(
+                        common::SetEntityOpacity {
+                            widget_label,
+                            entity_label,
+                            opacity: e.value,
+                        },
+                    ));
+                FromGuiLoopMessage::UpdateRangedValueF32(e).update(&mut self.components);
+            }
+            FromGuiLoopMessage::UpdatePresetControls(e) => match e.action {
+                common::PresetAction::Save => {
+                    let name = (self.presets.len() + 1..)
+                        .map(|i| format!("preset {}", i))
+                        .find(|name| !self.presets.contains_key(name))
+                        .unwrap();
+                    self.save_preset(name);
+                }
+                common::PresetAction::Apply(name) => {
+                    if let Some(values) = self.presets.get(&name).cloned() {
+                        self.apply_values(&values);
+                    }
+                }
+                common::PresetAction::Reset => {
+                    let values = self.initial_values.clone();
+                    self.apply_values(&values);
+                }
+            },
+            m => m.update(&mut self.components),
+        }
+    }
+
+    /// Saves the current values as preset `name`, and updates all preset controls.
+    fn save_preset(&mut self, name: String) {
+        let values = self.values();
+        self.presets.insert(name, values);
+        let presets: std::vec::Vec<String> = self.presets.keys().cloned().collect();
+        for label in &self.preset_controls {
+            self.message_queue
+                .push_back(ToGuiLoopMessage::AddPresetControls(
+                    common::AddPresetControls {
+                        label: label.clone(),
+                        presets: presets.clone(),
+                    },
+                ));
+        }
+    }
+
+    /// Removes the entities drawn with [UiWidget3::draw_once] before the last sync, which were
+    /// not drawn again since; and starts a new frame.
+    fn clear_stale_draw_once_entities(&mut self) {
+        for (widget_label, (count, previous_count)) in &mut self.draw_once_counts {
+            for i in *count..*previous_count {
+                let entity_label = UiWidget3::draw_once_label(i);
+                if let Some(labels) = self.entity_labels.get_mut(widget_label) {
+                    labels.remove(&entity_label);
+                }
+                self.message_queue
+                    .push_back(ToGuiLoopMessage::RemoveEntity3(common::RemoveEntity3 {
+                        widget_label: widget_label.clone(),
+                        entity_label,
+                    }));
+            }
+            *previous_count = *count;
+            *count = 0;
+        }
+    }
+
+    /// Current values of all components.
+    fn values(&self) -> ComponentValues {
+        self.components
+            .iter()
+            .map(|(label, component)| (label.clone(), component.values(label)))
+            .collect()
+    }
+
+    /// Sets the `values` of all components which still exist, both here and in the gui.
+    fn apply_values(&mut self, values: &ComponentValues) {
+        for (label, messages) in values {
+            if !self.components.contains_key(label) {
+                continue;
+            }
+            for m in messages {
+                self.set_value(m.clone());
+            }
+        }
+    }
+
+    /// Removes the opacity controls of the entity `entity_label` of widget `widget_label` - or of
+    /// all its entities if None - both here and in the gui; see [UiWidget3::add_opacity_control].
+    fn remove_opacity_controls(&mut self, widget_label: &str, entity_label: Option<&str>) {
+        let slider_labels: std::vec::Vec<String> = self
+            .opacity_controls
+            .iter()
+            .filter(|(_, (w, e))| w == widget_label && entity_label.is_none_or(|l| l == e))
+            .map(|(slider_label, _)| slider_label.clone())
+            .collect();
+        for slider_label in slider_labels {
+            self.opacity_controls.remove(&slider_label);
+            self.components.remove(&slider_label);
+            self.initial_values.remove(&slider_label);
+            self.message_queue
+                .push_back(ToGuiLoopMessage::RemoveComponent(common::RemoveComponent {
+                    label: slider_label,
+                }));
+        }
+    }
+
+    /// Sets a component value from the application side, both here and in the gui.
+    fn set_value(&mut self, m: FromGuiLoopMessage) {
+        self.message_queue
+            .push_back(ToGuiLoopMessage::SetComponentValue(m.clone()));
+        self.handle(m);
+    }
 }
 
 impl Default for Shared {
@@ -23,19 +200,60 @@ impl Default for Shared {
         Self {
             components: LinkedHashMap::new(),
             message_queue: std::collections::VecDeque::new(),
+            input_events: std::vec::Vec::new(),
+            dpi_scale: None,
+            entity_labels: std::collections::HashMap::new(),
+            hovered_points: std::collections::HashMap::new(),
+            hovered_entities: std::collections::HashMap::new(),
+            camera_poses: std::collections::HashMap::new(),
+            measurements: std::collections::HashMap::new(),
+            opacity_controls: std::collections::HashMap::new(),
+            initial_values: LinkedHashMap::new(),
+            preset_controls: std::vec::Vec::new(),
+            presets: LinkedHashMap::new(),
+            draw_once_counts: std::collections::HashMap::new(),
         }
     }
 }
 
-struct LocalConnection {}
+/// Configuration of the websocket server of a remote [Manager].
+#[derive(Clone, Debug)]
+pub struct RemoteConfig {
+    /// Address the server binds to, e.g. `0.0.0.0:9001` to accept clients from other machines or
+    /// from outside a container. Defaults to [RemoteConfig::DEFAULT_ADDRESS].
+    pub address: String,
+    /// Optional shared secret. If set, a remote client must send it as bearer token
+    /// (`Authorization: Bearer <token>`) during the websocket handshake, otherwise the connection
+    /// is rejected.
+    pub token: Option<String>,
+}
 
-struct WebsocketServerConnection {
-    _thread_join_handle: std::thread::JoinHandle<()>,
+impl Default for RemoteConfig {
+    fn default() -> Self {
+        Self {
+            address: Self::DEFAULT_ADDRESS.to_string(),
+            token: None,
+        }
+    }
 }
 
-enum ManagerConnection {
-    Local(LocalConnection),
-    WebsocketServer(WebsocketServerConnection),
+impl RemoteConfig {
+    /// Default address of the websocket server, i.e. local clients only.
+    pub const DEFAULT_ADDRESS: &'static str = "127.0.0.1:9001";
+
+    fn is_authorized(&self, request: &tungstenite::handshake::server::Request) -> bool {
+        match &self.token {
+            None => true,
+            Some(token) => {
+                request
+                    .headers()
+                    .get(tungstenite::http::header::AUTHORIZATION)
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(|value| value.strip_prefix("Bearer "))
+                    == Some(token.as_str())
+            }
+        }
+    }
 }
 
 /// The users employ the [Manager] to add [super::common::Component]s and [super::common::Widget]s
@@ -45,7 +263,6 @@ enum ManagerConnection {
 pub struct Manager {
     to_gui_loop_sender: mpsc::Sender<common::ToGuiLoopMessage>,
     from_gui_loop_receiver: mpsc::Receiver<common::FromGuiLoopMessage>,
-    _connection: ManagerConnection,
     shared: Rc<RefCell<Shared>>,
 }
 
@@ -134,6 +351,21 @@ impl<
         }
         None
     }
+
+    /// Sets the selected value, e.g. to reflect a value chosen by the application in the gui.
+    ///
+    /// The change is not reported by [UiEnum::get_new_value].
+    pub fn set_value(&mut self, value: T) {
+        self.shared
+            .borrow_mut()
+            .set_value(FromGuiLoopMessage::UpdateEnumStringRepr(
+                common::UpdateEnumStringRepr {
+                    label: self.label.clone(),
+                    value: value.to_string(),
+                },
+            ));
+        self.cache = value;
+    }
 }
 
 /// Represents a button in the side-panel.
@@ -159,26 +391,52 @@ impl UiButton {
 
     /// Returns true if button was pressed.
     pub fn was_pressed(&mut self) -> bool {
-        let pressed = self
-            .shared
-            .borrow()
+        take_button_press(&self.shared, &self.label)
+    }
+}
+
+/// Returns whether the [common::Button] with name `label` was pressed, and resets it.
+fn take_button_press(shared: &Rc<RefCell<Shared>>, label: &str) -> bool {
+    let mut shared = shared.borrow_mut();
+    let button = shared
+        .components
+        .get_mut(label)
+        .unwrap()
+        .downcast_mut::<common::Button>()
+        .unwrap();
+    std::mem::replace(&mut button.pressed, false)
+}
+
+/// Ui element for a button which asks for confirmation before it counts as pressed.
+///
+/// Clicking the button opens a modal dialog with the `prompt`, blocking the rest of the ui, and
+/// the press is only reported if the user confirms.
+pub struct UiConfirmButton {
+    shared: Rc<RefCell<Shared>>,
+    label: String,
+}
+
+impl UiConfirmButton {
+    fn new(shared: Rc<RefCell<Shared>>, label: String, prompt: String) -> Self {
+        shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::AddConfirmButton(
+                common::AddConfirmButton {
+                    label: label.clone(),
+                    prompt,
+                },
+            ));
+        shared
+            .borrow_mut()
             .components
-            .get(&self.label)
-            .unwrap()
-            .downcast_ref::<common::Button>()
-            .unwrap()
-            .pressed;
-        if pressed {
-            self.shared
-                .borrow_mut()
-                .components
-                .get_mut(&self.label)
-                .unwrap()
-                .downcast_mut::<common::Button>()
-                .unwrap()
-                .pressed = false;
-        }
-        pressed
+            .insert(label.clone(), Box::new(common::Button { pressed: false }));
+        Self { shared, label }
+    }
+
+    /// Returns true if button was pressed and the press was confirmed.
+    pub fn was_pressed(&mut self) -> bool {
+        take_button_press(&self.shared, &self.label)
     }
 }
 
@@ -244,6 +502,19 @@ impl UiVar<bool> {
         }
         None
     }
+
+    /// Sets the checkbox, e.g. to reflect a state chosen by the application in the gui.
+    ///
+    /// The change is not reported by [UiVar::get_new_value].
+    pub fn set_value(&mut self, value: bool) {
+        self.shared
+            .borrow_mut()
+            .set_value(FromGuiLoopMessage::UpdateValueBool(common::UpdateValue {
+                label: self.label.clone(),
+                value,
+            }));
+        self.cache = value;
+    }
 }
 
 impl<T: common::Number> UiVar<T> {
@@ -295,6 +566,156 @@ impl<T: common::Number> UiVar<T> {
         }
         None
     }
+
+    /// Sets the displayed value, e.g. to use it as a live numeric readout.
+    pub fn set_value(&mut self, value: T) {
+        let mut shared = self.shared.borrow_mut();
+        shared
+            .message_queue
+            .push_back(value.update_var_message(self.label.clone()));
+        shared
+            .components
+            .get_mut(&self.label)
+            .unwrap()
+            .downcast_mut::<common::Var<T>>()
+            .unwrap()
+            .value = value;
+        self.cache = value;
+    }
+}
+
+/// Read-only pose display in the side panel, with a button to copy it to the clipboard.
+pub struct UiPoseReadout {
+    shared: Rc<RefCell<Shared>>,
+    label: String,
+}
+
+impl UiPoseReadout {
+    fn new(shared: Rc<RefCell<Shared>>, label: String) -> Self {
+        let pose = nalgebra::Isometry3::identity();
+        shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::AddPoseReadout(common::AddPoseReadout {
+                label: label.clone(),
+                pose,
+            }));
+        shared
+            .borrow_mut()
+            .components
+            .insert(label.clone(), Box::new(common::PoseReadout { pose }));
+        Self { shared, label }
+    }
+
+    /// Sets the displayed pose.
+    pub fn set_pose(&self, pose: nalgebra::Isometry3<f32>) {
+        let mut shared = self.shared.borrow_mut();
+        shared
+            .message_queue
+            .push_back(ToGuiLoopMessage::UpdatePoseReadout(
+                common::AddPoseReadout {
+                    label: self.label.clone(),
+                    pose,
+                },
+            ));
+        shared
+            .components
+            .get_mut(&self.label)
+            .unwrap()
+            .downcast_mut::<common::PoseReadout>()
+            .unwrap()
+            .pose = pose;
+    }
+}
+
+/// Handle to a computation running on a background thread, see [Manager::spawn_task].
+pub struct TaskHandle<T> {
+    receiver: mpsc::Receiver<std::thread::Result<T>>,
+    result: Option<std::thread::Result<T>>,
+}
+
+impl<T> TaskHandle<T> {
+    fn try_receive(&mut self) {
+        if self.result.is_none() {
+            self.result = self.receiver.try_recv().ok();
+        }
+    }
+
+    /// Returns true if the task has finished (or panicked).
+    pub fn is_finished(&mut self) -> bool {
+        self.try_receive();
+        self.result.is_some()
+    }
+
+    /// Returns the result if the task has finished, without blocking. Afterwards, the handle is
+    /// empty and `poll` returns None again.
+    ///
+    /// Panics if the task panicked.
+    pub fn poll(&mut self) -> Option<T> {
+        self.try_receive();
+        self.result.take().map(|result| match result {
+            Ok(value) => value,
+            Err(panic) => std::panic::resume_unwind(panic),
+        })
+    }
+}
+
+/// Ui element for a list of checkboxes, e.g. to toggle the visibility of layers.
+pub struct UiCheckboxes {
+    shared: Rc<RefCell<Shared>>,
+    label: String,
+    cache: std::collections::HashSet<String>,
+}
+
+impl UiCheckboxes {
+    fn new(shared: Rc<RefCell<Shared>>, label: String, items: Vec<(String, bool)>) -> Self {
+        shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::AddCheckboxList(common::AddCheckboxList {
+                label: label.clone(),
+                items: items.clone(),
+            }));
+        let list = common::CheckboxList { items };
+        let cache = list.checked();
+        shared
+            .borrow_mut()
+            .components
+            .insert(label.clone(), Box::new(list));
+        Self {
+            shared,
+            label,
+            cache,
+        }
+    }
+
+    fn checked(&self) -> std::collections::HashSet<String> {
+        self.shared
+            .borrow()
+            .components
+            .get(&self.label)
+            .unwrap()
+            .downcast_ref::<common::CheckboxList>()
+            .unwrap()
+            .checked()
+    }
+
+    /// Returns the names of all checked items.
+    pub fn get_value(&mut self) -> std::collections::HashSet<String> {
+        let value = self.checked();
+        self.cache = value.clone();
+        value
+    }
+
+    /// Only returns the names of all checked items if any item was toggled.
+    pub fn get_new_value(&mut self) -> Option<std::collections::HashSet<String>> {
+        let value = self.checked();
+        if value != self.cache {
+            self.cache = value.clone();
+            return Some(value);
+        }
+        None
+    }
 }
 
 /// Ui element for a [super::common::Number] ([i32], [i64], [f32], [f64]) with a given range
@@ -327,58 +748,116 @@ impl<T: common::Number> UiRangedVar<T> {
         }
     }
 
-    /// Returns the current numeric value; it is guaranteed to be within its bounds `[min, max]`
-    pub fn get_value(&mut self) -> T {
-        let value = self
-            .shared
+    /// Value and bounds of the slider; None if it was removed, e.g. as opacity control of a removed
+    /// entity, see [UiWidget3::add_opacity_control].
+    fn current(&self) -> Option<(T, (T, T))> {
+        self.shared
             .borrow()
             .components
             .get(&self.label)
-            .unwrap()
-            .downcast_ref::<common::RangedVar<T>>()
-            .unwrap()
-            .value;
-        self.cache = value;
-        value
+            .map(|component| {
+                let var = component.downcast_ref::<common::RangedVar<T>>().unwrap();
+                (var.value, var.min_max)
+            })
+    }
+
+    /// Returns the current numeric value; it is guaranteed to be within its bounds `[min, max]`.
+    /// If the slider was removed, its last value is returned.
+    pub fn get_value(&mut self) -> T {
+        if let Some((value, _)) = self.current() {
+            self.cache = value;
+        }
+        self.cache
     }
 
     /// Only returns the current numeric value if it was updated.
     /// In this case, is guaranteed to be within its bounds `[min, max]`
     pub fn get_new_value(&mut self) -> Option<T> {
-        let value = self
-            .shared
-            .borrow()
-            .components
-            .get(&self.label)
-            .unwrap()
-            .downcast_ref::<common::RangedVar<T>>()
-            .unwrap()
-            .value;
+        let (value, _) = self.current()?;
         if value != self.cache {
             self.cache = value;
             return Some(value);
         }
         None
     }
+
+    /// Sets the value, clamped to its bounds `[min, max]`, e.g. to reflect an auto-tuned
+    /// parameter in the gui; the slider handle moves accordingly.
+    ///
+    /// The change is not reported by [UiRangedVar::get_new_value]. It is no-op, if the slider was
+    /// removed.
+    pub fn set_value(&mut self, value: T) {
+        let (min, max) = match self.current() {
+            Some((_, min_max)) => min_max,
+            None => return,
+        };
+        let mut shared = self.shared.borrow_mut();
+        let value = if value < min {
+            min
+        } else if value > max {
+            max
+        } else {
+            value
+        };
+        shared.set_value(value.update_range_value_message(self.label.clone()));
+        self.cache = value;
+    }
 }
 
 /// 2d widget.
 pub struct UiWidget2 {
-    // label: String,
-// hared: Rc<RefCell<Shared>>,
+    label: String,
+    shared: Rc<RefCell<Shared>>,
+    image_size: (u32, u32),
 }
 
 impl UiWidget2 {
+    /// Replaces the image of [UiWidget2], e.g. to stream camera frames. The overlays are kept.
+    ///
+    /// Only images of the same size as the original image are supported. Returns false, and the
+    /// image is not updated, if the size differs.
+    ///
+    /// Example
+    /// ```
+    /// let (to_gui_loop_sender, _) = std::sync::mpsc::channel();
+    /// let (_, from_gui_loop_receiver) = std::sync::mpsc::channel();
+    /// let manager = vviz::manager::Manager::new_local(to_gui_loop_sender, from_gui_loop_receiver);
+    /// let w2d = manager.add_widget2("w2d", image::RgbaImage::new(4, 3));
+    /// for i in 0..3u8 {
+    ///     let frame = image::RgbaImage::from_pixel(4, 3, image::Rgba([i, i, i, 255]));
+    ///     assert!(w2d.update_image(frame));
+    /// }
+    /// assert!(!w2d.update_image(image::RgbaImage::new(3, 4)));
+    /// ```
+    pub fn update_image(&self, rgba8: image::ImageBuffer<image::Rgba<u8>, Vec<u8>>) -> bool {
+        if rgba8.dimensions() != self.image_size {
+            return false;
+        }
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::TryUpdateImage(common::TryUpdateImage {
+                widget_label: self.label.clone(),
+                image: common::ImageRgba8 {
+                    width: rgba8.width(),
+                    height: rgba8.height(),
+                    bytes: rgba8.into_raw(),
+                },
+            }));
+        true
+    }
+
     fn new(
         shared: Rc<RefCell<Shared>>,
         label: String,
         rgba8: image::ImageBuffer<image::Rgba<u8>, Vec<u8>>,
     ) -> Self {
+        let image_size = (rgba8.width(), rgba8.height());
         shared
             .borrow_mut()
             .message_queue
             .push_back(ToGuiLoopMessage::AddWidget2(common::AddWidget2 {
-                label,
+                label: label.clone(),
                 image: common::ImageRgba8 {
                     width: rgba8.width(),
                     height: rgba8.height(),
@@ -386,57 +865,325 @@ impl UiWidget2 {
                 },
             }));
 
-        Self {}
+        Self {
+            label,
+            shared,
+            image_size,
+        }
     }
-}
-
-/// 3d widget.
-pub struct UiWidget3 {
-    label: String,
-    shared: Rc<RefCell<Shared>>,
-}
 
-impl UiWidget3 {
-    fn new(shared: Rc<RefCell<Shared>>, label: String) -> Self {
-        shared
+    /// Overlays 2d line segments, given in image pixel coordinates, over the image. If an overlay
+    /// with such `label` already exists it will be replaced.
+    pub fn place_overlay(&self, label: impl Into<String>, segments: entities::LineSegments2) {
+        self.shared
             .borrow_mut()
             .message_queue
-            .push_back(ToGuiLoopMessage::AddWidget3(common::AddWidget3 {
-                label: label.clone(),
+            .push_back(ToGuiLoopMessage::PlaceOverlay2(common::PlaceOverlay2 {
+                widget_label: self.label.clone(),
+                label: label.into(),
+                segments,
             }));
-
-        Self { label, shared }
     }
 
-    /// Adds new [entities::Entity3] to [UiWidget3]. If an entity with such `label` already exists
-    /// it will be replaced.
-    pub fn place_entity(&self, label: String, entity: entities::Entity3) {
+    /// Shows [UiWidget2] at a fixed size in physical screen pixels - instead of fitting it into
+    /// the main panel - with scroll bars if it does not fit. The image keeps its aspect ratio
+    /// within that size. If None, the widget is fitted again.
+    pub fn set_fixed_size(&self, size: Option<(u32, u32)>) {
         self.shared
             .borrow_mut()
             .message_queue
-            .push_back(ToGuiLoopMessage::PlaceEntity3(common::PlaceEntity3 {
+            .push_back(ToGuiLoopMessage::SetFixedSize2(common::SetFixedSize2 {
                 widget_label: self.label.clone(),
-                named_entity: entities::NamedEntity3 {
-                    label,
-                    entity,
-                    scene_pose_entity: nalgebra::Isometry3::<f32>::identity(),
-                },
+                size,
             }));
     }
 
-    /// Adds new [entities::Entity3] to [UiWidget3] at specified pose. If an entity with such
-    /// `label` already exists it will be replaced.
+    /// Shows the image 1:1, i.e. one image pixel per screen pixel, e.g. to inspect fine details.
+    /// Same as [UiWidget2::set_fixed_size] with the image size.
+    pub fn set_one_to_one(&self, enabled: bool) {
+        self.set_fixed_size(enabled.then_some(self.image_size));
+    }
+
+    /// Places 2d entity, given in image pixel coordinates, over the image. If an entity with such
+    /// `label` already exists it will be replaced - in place, i.e. keeping its drawing order.
     ///
-    /// Here `scene`_pose_entity` is the pose (3d position and orientation) of the entity in the
-    /// scene reference frame.
-    pub fn place_entity_at(
-        &self,
-        label: String,
-        entity: entities::Entity3,
-        scene_pose_entity: nalgebra::Isometry3<f32>,
-    ) {
+    /// Entities and overlays share the same labels; see [UiWidget2::place_overlay].
+    pub fn place_entity2(&self, label: impl Into<String>, entity: entities::Entity2) {
+        self.push_entity2(label.into(), entity, false);
+    }
+
+    /// Replaces 2d entity with name `label`, e.g. to move a tracked bounding box.
+    ///
+    /// If no such entity exists, this is no-op.
+    pub fn update_entity2(&self, label: impl Into<String>, entity: entities::Entity2) {
+        self.push_entity2(label.into(), entity, true);
+    }
+
+    /// Removes the 2d entity with name `label`; same as [UiWidget2::remove_overlay].
+    ///
+    /// If no such entity exists, this is no-op.
+    pub fn remove_entity2(&self, label: impl Into<String>) {
+        self.remove_overlay(label);
+    }
+
+    fn push_entity2(&self, label: String, entity: entities::Entity2, update_only: bool) {
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::PlaceEntity2(common::PlaceEntity2 {
+                widget_label: self.label.clone(),
+                label,
+                entity,
+                update_only,
+            }));
+    }
+
+    /// Overlays the epipolar line of `point` (given in the other image) using the fundamental
+    /// matrix `f`; see [super::utilities::epipolar_line].
+    ///
+    /// The line is clipped to the image. If it does not intersect the image, any existing overlay
+    /// with such `label` is removed instead.
+    pub fn place_epipolar_line(
+        &self,
+        label: impl Into<String>,
+        f: &nalgebra::Matrix3<f32>,
+        point: nalgebra::Vector2<f32>,
+        color: entities::Color,
+    ) {
+        let line = super::utilities::epipolar_line(f, point);
+        match super::utilities::clip_line_to_image(&line, self.image_size.0, self.image_size.1) {
+            Some((start, end)) => self.place_overlay(
+                label,
+                entities::LineSegments2 {
+                    segments: vec![entities::LineSegment2 { start, end, color }],
+                },
+            ),
+            None => self.remove_overlay(label),
+        }
+    }
+
+    /// Removes the overlay with name `label`.
+    ///
+    /// If no such overlay exists, this is no-op.
+    pub fn remove_overlay(&self, label: impl Into<String>) {
         self.shared
             .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::RemoveOverlay2(common::RemoveOverlay2 {
+                widget_label: self.label.clone(),
+                label: label.into(),
+            }));
+    }
+}
+
+/// Two images side by side with lines connecting corresponding keypoints.
+///
+/// Inliers and outliers can be toggled using two checkboxes in the side panel. Call
+/// [UiCorrespondences::update] repeatably, e.g. next to [Manager::sync_with_gui], to apply the
+/// checkbox state.
+pub struct UiCorrespondences {
+    widget: UiWidget2,
+    ui_show_inliers: UiVar<bool>,
+    ui_show_outliers: UiVar<bool>,
+    inliers: entities::LineSegments2,
+    outliers: entities::LineSegments2,
+}
+
+impl UiCorrespondences {
+    /// Gap in pixels between the left and the right image.
+    const GAP: u32 = 16;
+
+    fn new(
+        shared: Rc<RefCell<Shared>>,
+        label: String,
+        left: image::RgbaImage,
+        right: image::RgbaImage,
+        correspondences: &[entities::Correspondence],
+    ) -> Self {
+        let right_offset = nalgebra::Vector2::new((left.width() + Self::GAP) as f32, 0.0);
+        let mut inliers = entities::LineSegments2::default();
+        let mut outliers = entities::LineSegments2::default();
+        for c in correspondences {
+            let quality = c.quality.clamp(0.0, 1.0);
+            let segment = entities::LineSegment2 {
+                start: c.left,
+                end: c.right + right_offset,
+                color: entities::Color {
+                    r: 1.0 - quality,
+                    g: quality,
+                    b: 0.0,
+                    alpha: 1.0,
+                },
+            };
+            if c.inlier {
+                inliers.segments.push(segment);
+            } else {
+                outliers.segments.push(segment);
+            }
+        }
+
+        let image = super::utilities::concat_images_horizontally(&left, &right, Self::GAP);
+        let widget = UiWidget2::new(shared.clone(), label.clone(), image);
+        let ui_show_inliers =
+            UiVar::<bool>::new(shared.clone(), format!("{} inliers", label), true);
+        let ui_show_outliers = UiVar::<bool>::new(shared, format!("{} outliers", label), true);
+        widget.place_overlay("inliers", inliers.clone());
+        widget.place_overlay("outliers", outliers.clone());
+        Self {
+            widget,
+            ui_show_inliers,
+            ui_show_outliers,
+            inliers,
+            outliers,
+        }
+    }
+
+    /// Shows or hides inliers/outliers according to the checkboxes in the side panel.
+    pub fn update(&mut self) {
+        if let Some(show) = self.ui_show_inliers.get_new_value() {
+            if show {
+                self.widget.place_overlay("inliers", self.inliers.clone());
+            } else {
+                self.widget.remove_overlay("inliers");
+            }
+        }
+        if let Some(show) = self.ui_show_outliers.get_new_value() {
+            if show {
+                self.widget.place_overlay("outliers", self.outliers.clone());
+            } else {
+                self.widget.remove_overlay("outliers");
+            }
+        }
+    }
+}
+
+/// 3d widget.
+#[derive(Clone)]
+pub struct UiWidget3 {
+    label: String,
+    shared: Rc<RefCell<Shared>>,
+}
+
+impl UiWidget3 {
+    fn new(
+        shared: Rc<RefCell<Shared>>,
+        label: String,
+        camera_pose_scene: Option<nalgebra::Isometry3<f32>>,
+    ) -> Self {
+        shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::AddWidget3(common::AddWidget3 {
+                label: label.clone(),
+                camera_pose_scene,
+            }));
+
+        Self { label, shared }
+    }
+
+    /// Adds new [entities::Entity3] to [UiWidget3]. If an entity with such `label` already exists
+    /// it will be replaced.
+    pub fn place_entity(&self, label: impl Into<String>, entity: entities::Entity3) {
+        self.place_entity_at(label, entity, nalgebra::Isometry3::<f32>::identity());
+    }
+
+    /// Shows `entity` at `scene_pose_entity` for the current iteration of the application loop
+    /// only - like a "printf" for geometry.
+    ///
+    /// There is no need to manage labels: the entities drawn once between two calls of
+    /// [Manager::sync_with_gui] replace the ones drawn in the previous iteration. They are labeled
+    /// `draw_once <i>`, where `i` counts the calls since the last sync.
+    ///
+    /// ```no_run
+    /// vviz::app::spawn(vviz::app::VVizMode::Local, |mut manager: vviz::manager::Manager| {
+    ///     let w3d = manager.add_widget3("w3d");
+    ///     let mut t = 0.0_f32;
+    ///     while manager.sync_with_gui() {
+    ///         t += 0.01;
+    ///         let pose = nalgebra::Isometry3::translation(t.sin(), 0.0, 0.0);
+    ///         w3d.draw_once(vviz::entities::colored_cube(0.2), pose);
+    ///     }
+    /// });
+    /// ```
+    pub fn draw_once(
+        &self,
+        entity: entities::Entity3,
+        scene_pose_entity: nalgebra::Isometry3<f32>,
+    ) {
+        let i = {
+            let mut shared = self.shared.borrow_mut();
+            let (count, _) = shared
+                .draw_once_counts
+                .entry(self.label.clone())
+                .or_default();
+            *count += 1;
+            *count - 1
+        };
+        self.place_entity_at(Self::draw_once_label(i), entity, scene_pose_entity);
+    }
+
+    fn draw_once_label(i: usize) -> String {
+        format!("draw_once {}", i)
+    }
+
+    /// Label of the slider of [UiWidget3::add_opacity_control].
+    fn opacity_slider_label(widget_label: &str, entity_label: &str) -> String {
+        format!("{}: {} opacity", widget_label, entity_label)
+    }
+
+    /// Like [UiWidget3::place_entity], but first checks that the entity is well-formed.
+    ///
+    /// Returns an error, and does not place the entity, if it refers to non-existing vertices. See
+    /// [entities::Entity3::validate].
+    pub fn try_place_entity(
+        &self,
+        label: impl Into<String>,
+        entity: entities::Entity3,
+    ) -> Result<(), entities::MeshError> {
+        entity.validate()?;
+        self.place_entity(label, entity);
+        Ok(())
+    }
+
+    /// Adds new [entities::Entity3] to [UiWidget3] at specified pose. If an entity with such
+    /// `label` already exists it will be replaced.
+    ///
+    /// Here `scene`_pose_entity` is the pose (3d position and orientation) of the entity in the
+    /// scene reference frame.
+    pub fn place_entity_at(
+        &self,
+        label: impl Into<String>,
+        entity: entities::Entity3,
+        scene_pose_entity: nalgebra::Isometry3<f32>,
+    ) {
+        self.place_entity_scaled(
+            label,
+            entity,
+            scene_pose_entity,
+            nalgebra::Vector3::<f32>::repeat(1.0),
+        );
+    }
+
+    /// Adds new [entities::Entity3] to [UiWidget3] at specified pose and (non-uniform) scale. If
+    /// an entity with such `label` already exists it will be replaced.
+    ///
+    /// The entity is first scaled along its own x, y and z axis, and then transformed by
+    /// `scene_pose_entity`. For uniform scaling, see [UiWidget3::place_entity_scaled_uniformly].
+    pub fn place_entity_scaled(
+        &self,
+        label: impl Into<String>,
+        entity: entities::Entity3,
+        scene_pose_entity: nalgebra::Isometry3<f32>,
+        scale: nalgebra::Vector3<f32>,
+    ) {
+        let label = label.into();
+        let mut shared = self.shared.borrow_mut();
+        shared
+            .entity_labels
+            .entry(self.label.clone())
+            .or_default()
+            .insert(label.clone(), ());
+        shared
             .message_queue
             .push_back(ToGuiLoopMessage::PlaceEntity3(common::PlaceEntity3 {
                 widget_label: self.label.clone(),
@@ -444,31 +1191,1147 @@ impl UiWidget3 {
                     label,
                     entity,
                     scene_pose_entity,
+                    scale,
+                    cull_mode: None,
+                    depth_bias: 0.0,
+                },
+            }));
+    }
+
+    /// Adds new [entities::Entity3] to [UiWidget3] at specified pose and uniform `scale`, e.g. to
+    /// show a unit-sized model at its real size. If an entity with such `label` already exists it
+    /// will be replaced.
+    ///
+    /// Same as [UiWidget3::place_entity_scaled] with the same scale along all axes.
+    pub fn place_entity_scaled_uniformly(
+        &self,
+        label: impl Into<String>,
+        entity: entities::Entity3,
+        scene_pose_entity: nalgebra::Isometry3<f32>,
+        scale: f32,
+    ) {
+        self.place_entity_scaled(
+            label,
+            entity,
+            scene_pose_entity,
+            nalgebra::Vector3::<f32>::repeat(scale),
+        );
+    }
+
+    /// Appends `point` to the path - a growing polyline entity - with name `label`, drawing a
+    /// segment from the previous point to the new one.
+    ///
+    /// The path is maintained by the gui, which makes this much cheaper than placing the whole
+    /// polyline again in each iteration, e.g. to show the trajectory of a tracked camera. If no
+    /// such path exists, it is created at the scene origin.
+    pub fn append_to_path(
+        &self,
+        label: impl Into<String>,
+        point: nalgebra::Vector3<f32>,
+        color: entities::Color,
+    ) {
+        self.append_to_path_capped(label, point, color, None);
+    }
+
+    /// Same as [UiWidget3::append_to_path], but retains at most the last `max_points` points of
+    /// the path, if given.
+    pub fn append_to_path_capped(
+        &self,
+        label: impl Into<String>,
+        point: nalgebra::Vector3<f32>,
+        color: entities::Color,
+        max_points: Option<usize>,
+    ) {
+        let label = label.into();
+        let mut shared = self.shared.borrow_mut();
+        shared
+            .entity_labels
+            .entry(self.label.clone())
+            .or_default()
+            .insert(label.clone(), ());
+        shared
+            .message_queue
+            .push_back(ToGuiLoopMessage::AppendPathPoint(common::AppendPathPoint {
+                widget_label: self.label.clone(),
+                entity_label: label,
+                point,
+                color,
+                max_points,
+            }));
+    }
+
+    /// The scene point under the mouse cursor, or None if the cursor is not over an entity of
+    /// [UiWidget3].
+    ///
+    /// The gui hits meshes exactly and line segments within a few pixels of the cursor, see
+    /// [crate::common::Widget3::hovered_point]. It is received in [Manager::sync_with_gui].
+    pub fn hovered_point(&self) -> Option<nalgebra::Vector3<f32>> {
+        self.shared
+            .borrow()
+            .hovered_points
+            .get(&self.label)
+            .copied()
+    }
+
+    /// Label of the entity under the mouse cursor, or None if the cursor is not over an entity of
+    /// [UiWidget3].
+    ///
+    /// This is the nearest entity hit at [UiWidget3::hovered_point]. It is received in
+    /// [Manager::sync_with_gui].
+    pub fn hovered_entity(&self) -> Option<String> {
+        self.shared
+            .borrow()
+            .hovered_entities
+            .get(&self.label)
+            .cloned()
+    }
+
+    /// Pose of the camera in the scene, i.e. `scene_pose_camera` - the transformation from camera
+    /// to scene coordinates - as of the last rendered frame; e.g. to record the camera trajectory
+    /// while the user moves the camera with the mouse.
+    ///
+    /// Note that this is the inverse of the `camera_pose_scene` passed to
+    /// [Manager::add_widget3_with_camera]. The camera looks along its negative z axis, with x to
+    /// the right and y up. The pose is received in [Manager::sync_with_gui], hence it is None until
+    /// the widget was rendered for the first time.
+    pub fn get_camera_pose(&self) -> Option<nalgebra::Isometry3<f32>> {
+        self.shared.borrow().camera_poses.get(&self.label).copied()
+    }
+
+    /// Sets the pose of the camera in the scene, i.e. `scene_pose_camera` - the transformation from
+    /// camera to scene coordinates, see [UiWidget3::get_camera_pose]; e.g. to fly through the scene
+    /// on a scripted path.
+    ///
+    /// The user can still move the camera with the mouse, starting from the new pose. Field of view
+    /// and clipping planes are not changed.
+    pub fn set_camera_pose(&self, scene_pose_camera: nalgebra::Isometry3<f32>) {
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::SetCameraPose(common::SetCameraPose {
+                widget_label: self.label.clone(),
+                scene_pose_camera,
+            }));
+    }
+
+    /// Enables/disables outlining the bounding box of the entity under the mouse cursor, see
+    /// [UiWidget3::hovered_entity]. It is enabled by default.
+    pub fn set_hover_highlight(&self, enabled: bool) {
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::SetHoverHighlight(
+                common::SetHoverHighlight {
+                    widget_label: self.label.clone(),
+                    enabled,
                 },
+            ));
+    }
+
+    /// Enables/disables the measure mode of [UiWidget3]: two clicks on entities measure the
+    /// distance between the clicked scene points; clicking on the background cancels. See
+    /// [common::SetMeasureMode].
+    pub fn set_measure_mode(&self, enabled: bool) {
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::SetMeasureMode(common::SetMeasureMode {
+                widget_label: self.label.clone(),
+                enabled,
+            }));
+    }
+
+    /// Distance of the last completed measurement in measure mode, received in
+    /// [Manager::sync_with_gui]; None if there was no measurement yet.
+    pub fn last_measurement(&self) -> Option<f32> {
+        self.shared.borrow().measurements.get(&self.label).copied()
+    }
+
+    /// Removes the [entities::Entity3] with name `label`, e.g. an object which went out of view.
+    ///
+    /// If no such entity exists, this is no-op. Its visibility, opacity and animation are kept,
+    /// and apply again if an entity with that name is placed later.
+    ///
+    /// Example
+    /// ```
+    /// let (to_gui_loop_sender, _) = std::sync::mpsc::channel();
+    /// let (_, from_gui_loop_receiver) = std::sync::mpsc::channel();
+    /// let manager = vviz::manager::Manager::new_local(to_gui_loop_sender, from_gui_loop_receiver);
+    /// let w3d = manager.add_widget3("w3d");
+    /// w3d.place_entity("cube", vviz::entities::colored_cube(1.0));
+    /// w3d.place_entity("detection", vviz::entities::colored_cube(0.1));
+    /// w3d.remove_entity("detection");
+    /// w3d.remove_entity("does not exist");
+    /// assert_eq!(w3d.entity_labels(), vec!["cube".to_string()]);
+    /// ```
+    pub fn remove_entity(&self, label: impl Into<String>) {
+        let label = label.into();
+        let mut shared = self.shared.borrow_mut();
+        if let Some(labels) = shared.entity_labels.get_mut(&self.label) {
+            labels.remove(&label);
+        }
+        shared.remove_opacity_controls(&self.label, Some(&label));
+        shared
+            .message_queue
+            .push_back(ToGuiLoopMessage::RemoveEntity3(common::RemoveEntity3 {
+                widget_label: self.label.clone(),
+                entity_label: label,
+            }));
+    }
+
+    /// Removes all entities of [UiWidget3], e.g. to draw the whole scene anew in each iteration
+    /// without keeping track of the labels. See [UiWidget3::remove_entity].
+    pub fn clear_entities(&self) {
+        let mut shared = self.shared.borrow_mut();
+        shared.entity_labels.remove(&self.label);
+        shared.remove_opacity_controls(&self.label, None);
+        shared
+            .message_queue
+            .push_back(ToGuiLoopMessage::ClearWidget3(common::ClearWidget3 {
+                widget_label: self.label.clone(),
+            }));
+    }
+
+    /// Labels of all entities placed in [UiWidget3], in order of placement.
+    ///
+    /// This is tracked on the application side, hence does not require a round-trip to the gui.
+    pub fn entity_labels(&self) -> Vec<String> {
+        self.shared
+            .borrow()
+            .entity_labels
+            .get(&self.label)
+            .map(|labels| labels.keys().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Appends `points` to the point cloud with name `label`, e.g. for streaming sensor data; it
+    /// is created at the scene origin if it does not exist yet. See [entities::append_points]
+    /// for `colors` and `max_points`, which drops the oldest points.
+    ///
+    /// Panics if the number of colors is neither one nor the number of points.
+    pub fn append_points(
+        &self,
+        label: impl Into<String>,
+        points: std::vec::Vec<[f32; 3]>,
+        colors: std::vec::Vec<entities::Color>,
+        max_points: Option<usize>,
+    ) {
+        // Checked here as well, so that it panics in the application thread instead of the gui.
+        assert!(colors.len() == 1 || colors.len() == points.len());
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::AppendPoints(common::AppendPoints {
+                widget_label: self.label.clone(),
+                entity_label: label.into(),
+                points,
+                colors,
+                max_points,
             }));
     }
 
-    /// Updates `scene`_pose_entity` of the [entities::Entity3] with name `label`.
-    ///
-    /// If no such entity exists, this is no-op.
-    ///
-    /// Here `scene`_pose_entity` is the pose (3d position and orientation) of the entity in the
-    /// scene reference frame.
-    pub fn update_scene_pose_entity(
-        &self,
-        label: String,
-        scene_pose_entity: nalgebra::Isometry3<f32>,
-    ) {
-        self.shared
-            .borrow_mut()
-            .message_queue
-            .push_back(ToGuiLoopMessage::UpdateScenePoseEntity3(
-                common::UpdateScenePoseEntity3 {
-                    widget_label: self.label.clone(),
-                    entity_label: label,
-                    scene_pose_entity,
-                },
-            ));
+    /// Updates `scene`_pose_entity` of the [entities::Entity3] with name `label`.
+    ///
+    /// If no such entity exists, this is no-op.
+    ///
+    /// Here `scene`_pose_entity` is the pose (3d position and orientation) of the entity in the
+    /// scene reference frame.
+    pub fn update_scene_pose_entity(
+        &self,
+        label: impl Into<String>,
+        scene_pose_entity: nalgebra::Isometry3<f32>,
+    ) {
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::UpdateScenePoseEntity3(
+                common::UpdateScenePoseEntity3 {
+                    widget_label: self.label.clone(),
+                    entity_label: label.into(),
+                    scene_pose_entity,
+                },
+            ));
+    }
+
+    /// Updates the (non-uniform) scale of the [entities::Entity3] with name `label`.
+    ///
+    /// If no such entity exists, this is no-op.
+    pub fn update_scene_scale_entity(
+        &self,
+        label: impl Into<String>,
+        scale: nalgebra::Vector3<f32>,
+    ) {
+        self.shared.borrow_mut().message_queue.push_back(
+            ToGuiLoopMessage::UpdateSceneScaleEntity3(common::UpdateSceneScaleEntity3 {
+                widget_label: self.label.clone(),
+                entity_label: label.into(),
+                scale,
+            }),
+        );
+    }
+
+    /// Sets the face culling mode of [UiWidget3]. The default is [common::CullMode::Nothing],
+    /// i.e. double-sided rendering, which is robust to meshes with inconsistent winding.
+    pub fn set_cull_mode(&self, cull_mode: common::CullMode) {
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::SetCullMode(common::SetCullMode {
+                widget_label: self.label.clone(),
+                cull_mode,
+            }));
+    }
+
+    /// Enables or disables frustum culling of [UiWidget3], i.e. skipping entities which are fully
+    /// outside of the view. It is enabled by default; disabling it can help debugging.
+    pub fn set_frustum_culling(&self, enabled: bool) {
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::SetFrustumCulling(
+                common::SetFrustumCulling {
+                    widget_label: self.label.clone(),
+                    enabled,
+                },
+            ));
+    }
+
+    /// Enables or disables sRGB encoding of [UiWidget3]'s output colors.
+    ///
+    /// By default, entity colors are considered sRGB and are displayed unchanged, consistent with
+    /// 2d widgets and the background color. If enabled, they are considered linear instead and
+    /// are gamma-encoded to sRGB before display, e.g. for colors computed in linear space.
+    pub fn set_srgb_output(&self, enabled: bool) {
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::SetSrgbOutput(common::SetSrgbOutput {
+                widget_label: self.label.clone(),
+                enabled,
+            }));
+    }
+
+    /// Shows the vertex normals of all meshes of [UiWidget3] as lines of given `length`; None
+    /// hides them. See [entities::normal_lines].
+    pub fn show_normals(&self, length: Option<f32>) {
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::SetShowNormals(common::SetShowNormals {
+                widget_label: self.label.clone(),
+                length,
+            }));
+    }
+
+    /// Sets the render mode of [UiWidget3], e.g. depth as grayscale to diagnose z-fighting. See
+    /// [common::DebugMode]; the default is [common::DebugMode::Normal].
+    pub fn set_debug_mode(&self, debug_mode: common::DebugMode) {
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::SetDebugMode(common::SetDebugMode {
+                widget_label: self.label.clone(),
+                debug_mode,
+            }));
+    }
+
+    /// Draws entity with given label on top of all other entities of [UiWidget3], ignoring their
+    /// depth, e.g. for gizmos or the current pose. Entities on top occlude each other as usual,
+    /// but cannot be hovered.
+    pub fn set_entity_on_top(&self, entity_label: &str, on_top: bool) {
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::SetEntityOnTop(common::SetEntityOnTop {
+                widget_label: self.label.clone(),
+                entity_label: entity_label.to_string(),
+                on_top,
+            }));
+    }
+
+    /// Sets the width of all line segments of [UiWidget3], in pixels.
+    ///
+    /// Widths above one pixel are rendered as camera-facing quads, since many backends ignore GL
+    /// line widths above one pixel. The width is clamped to at most
+    /// [common::LineWidth::MAX_PIXELS]. Use [UiWidget3::set_line_width_mode] for widths in scene
+    /// units.
+    pub fn set_line_width(&self, pixels: f32) {
+        self.set_line_width_mode(common::LineWidth::Pixels(pixels));
+    }
+
+    /// Sets the [common::LineWidth] of all line segments of [UiWidget3], see
+    /// [common::LineWidth::clamped].
+    pub fn set_line_width_mode(&self, line_width: common::LineWidth) {
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::SetLineWidth(common::SetLineWidth {
+                widget_label: self.label.clone(),
+                line_width,
+            }));
+    }
+
+    /// Sets how mouse drags rotate the scene of [UiWidget3]. The default is
+    /// [common::RotationMode::Trackball].
+    pub fn set_rotation_mode(&self, rotation_mode: common::RotationMode) {
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::SetRotationMode(common::SetRotationMode {
+                widget_label: self.label.clone(),
+                rotation_mode,
+            }));
+    }
+
+    /// Sets the aspect ratio (width / height) of [UiWidget3], e.g. to match the image of a paired
+    /// [UiWidget2]. It affects both the projection and the size on screen. The default is 4:3.
+    ///
+    /// Panics if `aspect_ratio` is not positive.
+    pub fn set_aspect_ratio(&self, aspect_ratio: f32) {
+        assert!(aspect_ratio > 0.0, "aspect ratio must be positive");
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::SetAspectRatio(common::SetAspectRatio {
+                widget_label: self.label.clone(),
+                aspect_ratio,
+            }));
+    }
+
+    /// Enables the split view of [UiWidget3], showing the scene side by side from the primary
+    /// camera (left) and from a second camera with pose `split_camera_pose_scene` (right); e.g.
+    /// for stereo or before/after comparisons. None disables the split view.
+    pub fn set_split(&self, split_camera_pose_scene: Option<nalgebra::Isometry3<f32>>) {
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::SetSplitView(common::SetSplitView {
+                widget_label: self.label.clone(),
+                split_camera_pose_scene,
+            }));
+    }
+
+    /// Sets the vertical field of view of [UiWidget3] in degrees, and switches to a perspective
+    /// projection. The default is 60 degrees.
+    ///
+    /// Panics if `degrees` is not in (0, 180).
+    pub fn set_fov(&self, degrees: f32) {
+        assert!(
+            degrees > 0.0 && degrees < 180.0,
+            "field of view must be in (0, 180) degrees"
+        );
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::SetFieldOfView(common::SetFieldOfView {
+                widget_label: self.label.clone(),
+                fov_y_degrees: degrees,
+            }));
+    }
+
+    /// Sets the projection of [UiWidget3], e.g. an orthographic projection for top-down maps; see
+    /// [common::ProjectionMode]. The default is a perspective projection with a vertical field of
+    /// view of 60 degrees.
+    ///
+    /// In orthographic mode, [UiWidget3::zoom] changes the height of the view instead of moving
+    /// the camera.
+    ///
+    /// Panics if the field of view is not in (0, 180) degrees, or the height is not positive.
+    pub fn set_projection_mode(&self, projection_mode: common::ProjectionMode) {
+        match projection_mode {
+            common::ProjectionMode::Perspective { fov_deg } => assert!(
+                fov_deg > 0.0 && fov_deg < 180.0,
+                "field of view must be in (0, 180) degrees"
+            ),
+            common::ProjectionMode::Orthographic { height } => {
+                assert!(height > 0.0, "height must be positive")
+            }
+        }
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::SetProjectionMode(
+                common::SetProjectionMode {
+                    widget_label: self.label.clone(),
+                    projection_mode,
+                },
+            ));
+    }
+
+    /// Enables/disables shading the meshes of [UiWidget3] by a directional light, see
+    /// [UiWidget3::set_light_direction]. It is disabled by default, i.e. meshes show their plain
+    /// vertex colors.
+    pub fn set_lighting(&self, enabled: bool) {
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::SetLighting(common::SetLighting {
+                widget_label: self.label.clone(),
+                enabled: Some(enabled),
+                light_direction: None,
+            }));
+    }
+
+    /// Sets the direction the light travels, in scene coordinates; e.g. `(0, -1, 0)` for light
+    /// from above. It only takes effect if lighting is enabled, see [UiWidget3::set_lighting].
+    ///
+    /// Panics if `direction` is zero.
+    pub fn set_light_direction(&self, direction: nalgebra::Vector3<f32>) {
+        assert!(direction.norm() > 0.0, "light direction must not be zero");
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::SetLighting(common::SetLighting {
+                widget_label: self.label.clone(),
+                enabled: None,
+                light_direction: Some(direction),
+            }));
+    }
+
+    /// Sets the background color of [UiWidget3], e.g. black for dark point clouds. It overrides
+    /// the background of the [common::Theme]; the default is white.
+    pub fn set_background_color(&self, color: entities::Color) {
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::SetBackgroundColor(
+                common::SetBackgroundColor {
+                    widget_label: self.label.clone(),
+                    color: Some(color),
+                },
+            ));
+    }
+
+    /// Sets the near and far clipping planes of [UiWidget3], i.e. the range of distances from the
+    /// camera which is rendered. The default is (0.01, 100.0), e.g. for large outdoor scenes.
+    ///
+    /// Panics unless `0 < near < far`.
+    pub fn set_clipping(&self, near: f32, far: f32) {
+        assert!(near > 0.0, "near clipping plane must be positive");
+        assert!(far > near, "far clipping plane must be beyond near plane");
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::SetClipping(common::SetClipping {
+                widget_label: self.label.clone(),
+                near,
+                far,
+            }));
+    }
+
+    /// Shows or hides the default [common::WorldGrid], a ground grid at the scene origin.
+    ///
+    /// It is not an entity, hence does not need to be placed in each scene.
+    pub fn set_show_world_grid(&self, enabled: bool) {
+        self.set_world_grid(enabled.then(common::WorldGrid::default));
+    }
+
+    /// Shows the given ground grid at the scene origin, or hides it if None. Its divisions are
+    /// clamped to [common::WorldGrid::MAX_DIVISIONS].
+    pub fn set_world_grid(&self, grid: Option<common::WorldGrid>) {
+        let grid = grid.map(common::WorldGrid::clamped);
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::SetWorldGrid(common::SetWorldGrid {
+                widget_label: self.label.clone(),
+                grid,
+            }));
+    }
+
+    /// Shows or hides a coordinate axis at the scene origin.
+    ///
+    /// It is not an entity, hence does not need to be placed in each scene.
+    pub fn set_show_world_axes(&self, enabled: bool) {
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::SetShowWorldAxes(
+                common::SetShowWorldAxes {
+                    widget_label: self.label.clone(),
+                    enabled,
+                },
+            ));
+    }
+
+    /// Rotates the scene by `yaw` and `pitch` (in radians) about the rotation pivot, as dragging
+    /// with the left mouse button does. Respects the [common::RotationMode].
+    pub fn orbit(&self, yaw: f32, pitch: f32) {
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::OrbitCamera(common::OrbitCamera {
+                widget_label: self.label.clone(),
+                yaw,
+                pitch,
+            }));
+    }
+
+    /// Moves the camera towards the rotation pivot - the scene origin by default, see
+    /// [UiWidget3::set_rotation_pivot] - if `factor` > 1, and away from it if `factor` < 1.
+    ///
+    /// Example: `zoom(2.0)` halves the distance to the pivot. With an orthographic
+    /// [common::ProjectionMode], it halves the height of the view instead.
+    pub fn zoom(&self, factor: f32) {
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::ZoomCamera(common::ZoomCamera {
+                widget_label: self.label.clone(),
+                factor,
+            }));
+    }
+
+    /// Moves the camera by `dx`, `dy` (in scene units) in its image plane, as dragging with the
+    /// right mouse button does.
+    pub fn pan(&self, dx: f32, dy: f32) {
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::PanCamera(common::PanCamera {
+                widget_label: self.label.clone(),
+                dx,
+                dy,
+            }));
+    }
+
+    /// Sets the point, in scene coordinates, the camera rotates about when dragging with the left
+    /// mouse button and zooms towards. The default is the scene origin.
+    pub fn set_rotation_pivot(&self, pivot: nalgebra::Vector3<f32>) {
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::SetRotationPivot(
+                common::SetRotationPivot {
+                    widget_label: self.label.clone(),
+                    pivot,
+                },
+            ));
+    }
+
+    /// Moves the camera back to its initial pose - the one passed to
+    /// [Manager::add_widget3_with_camera], or 4 units in front of the scene origin - e.g. after
+    /// the scene got lost off-screen. Double-clicking the widget does the same.
+    pub fn reset_camera(&self) {
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::ResetCamera(common::ResetCamera {
+                widget_label: self.label.clone(),
+            }));
+    }
+
+    /// Sets the color of all vertices of the [entities::Entity3] with name `label`, e.g. to
+    /// highlight it. This is cheaper than placing the entity again, since only the vertices are
+    /// uploaded to the GPU again; cached bounds and normals are kept.
+    ///
+    /// If no such entity exists, this is no-op.
+    pub fn set_entity_color(&self, label: impl Into<String>, color: entities::Color) {
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::UpdateEntityColor(
+                common::UpdateEntityColor {
+                    widget_label: self.label.clone(),
+                    entity_label: label.into(),
+                    color,
+                },
+            ));
+    }
+
+    /// Shows or hides the [entities::Entity3] with name `label`. The user can also toggle this in
+    /// the entity list of the side panel, see [Manager::set_show_entity_list].
+    pub fn set_entity_visible(&self, label: impl Into<String>, visible: bool) {
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::SetEntityVisible(
+                common::SetEntityVisible {
+                    widget_label: self.label.clone(),
+                    entity_label: label.into(),
+                    visible,
+                },
+            ));
+    }
+
+    /// Sets the opacity of the [entities::Entity3] with name `label`, in [0..1] where 1 is
+    /// opaque. See [common::SetEntityOpacity] for the limitations of transparency.
+    pub fn set_entity_opacity(&self, label: impl Into<String>, opacity: f32) {
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::SetEntityOpacity(
+                common::SetEntityOpacity {
+                    widget_label: self.label.clone(),
+                    entity_label: label.into(),
+                    opacity,
+                },
+            ));
+    }
+
+    /// Adds a slider to the side-panel which controls the opacity of the [entities::Entity3] with
+    /// name `label`, see [UiWidget3::set_entity_opacity]. The slider is labeled
+    /// `"<widget label>: <entity label> opacity"`.
+    ///
+    /// The opacity is updated by [Manager::sync_with_gui] whenever the slider is moved, without
+    /// the need to poll the returned slider. The slider is removed together with the entity, by
+    /// [UiWidget3::remove_entity] or [UiWidget3::clear_entities].
+    ///
+    /// Example
+    /// ```
+    /// let (to_gui_loop_sender, _) = std::sync::mpsc::channel();
+    /// let (_, from_gui_loop_receiver) = std::sync::mpsc::channel();
+    /// let manager = vviz::manager::Manager::new_local(to_gui_loop_sender, from_gui_loop_receiver);
+    /// let w3d = manager.add_widget3("w3d");
+    /// w3d.place_entity("cube", vviz::entities::colored_cube(1.0));
+    /// let mut opacity = w3d.add_opacity_control("cube");
+    /// opacity.set_value(0.5);
+    /// w3d.remove_entity("cube");
+    /// // The slider is gone; its last value is kept.
+    /// opacity.set_value(0.2);
+    /// assert_eq!(opacity.get_value(), 0.5);
+    /// assert_eq!(opacity.get_new_value(), None);
+    /// ```
+    pub fn add_opacity_control(&self, label: impl Into<String>) -> UiRangedVar<f32> {
+        let entity_label = label.into();
+        let slider_label = Self::opacity_slider_label(&self.label, &entity_label);
+        self.shared
+            .borrow_mut()
+            .opacity_controls
+            .insert(slider_label.clone(), (self.label.clone(), entity_label));
+        UiRangedVar::<f32>::new(self.shared.clone(), slider_label, 1.0, (0.0, 1.0))
+    }
+
+    /// Creates an empty group of entities with name `group_label`, e.g. the parts of an
+    /// articulated object, replacing an existing group of the same name.
+    ///
+    /// The members of a group are shown/hidden with [UiWidget3::set_group_visible] and moved with
+    /// [UiWidget3::set_group_pose], which is applied as parent transform of each member's
+    /// `scene_pose_entity`. Groups are one level deep, i.e. cannot be nested.
+    pub fn create_group(&self, group_label: impl Into<String>) {
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::CreateEntityGroup(
+                common::CreateEntityGroup {
+                    widget_label: self.label.clone(),
+                    group_label: group_label.into(),
+                    group: None,
+                },
+            ));
+    }
+
+    /// Adds the [entities::Entity3] with name `label` to the group `group_label`, see
+    /// [UiWidget3::create_group]. An entity belongs to at most one group, hence it is removed from
+    /// its previous group, if any.
+    pub fn add_to_group(&self, group_label: impl Into<String>, label: impl Into<String>) {
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::AddToEntityGroup(
+                common::AddToEntityGroup {
+                    widget_label: self.label.clone(),
+                    group_label: group_label.into(),
+                    entity_label: label.into(),
+                },
+            ));
+    }
+
+    /// Shows or hides all members of the group `group_label`, see [UiWidget3::create_group].
+    pub fn set_group_visible(&self, group_label: impl Into<String>, visible: bool) {
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::SetEntityGroupVisible(
+                common::SetEntityGroupVisible {
+                    widget_label: self.label.clone(),
+                    group_label: group_label.into(),
+                    visible,
+                },
+            ));
+    }
+
+    /// Sets the pose of the group `group_label` in the scene, see [UiWidget3::create_group].
+    pub fn set_group_pose(
+        &self,
+        group_label: impl Into<String>,
+        scene_pose_group: nalgebra::Isometry3<f32>,
+    ) {
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::SetEntityGroupPose(
+                common::SetEntityGroupPose {
+                    widget_label: self.label.clone(),
+                    group_label: group_label.into(),
+                    scene_pose_group,
+                },
+            ));
+    }
+
+    /// Animates the [entities::Entity3] with name `label`: `animation` returns its
+    /// `scene_pose_entity` given the seconds elapsed since this call.
+    ///
+    /// The animation is evaluated by the gui at its own frame rate, hence is smooth independent of
+    /// how often [Manager::sync_with_gui] is called. It overrides
+    /// [UiWidget3::update_scene_pose_entity] until stopped with [UiWidget3::stop_animating_entity].
+    /// Calling this again replaces the animation and restarts the clock.
+    ///
+    /// Threading: the closure is moved to and called on the gui thread, hence it must be [Send]
+    /// and should return quickly, since it blocks rendering. Share state with the application
+    /// thread through e.g. an `Arc<Mutex<_>>` or atomics. Animations are not supported in remote
+    /// mode, since closures cannot be sent to a remote client; they are ignored there.
+    ///
+    /// If no such entity exists, the animation has no effect until an entity with name `label` is
+    /// placed.
+    pub fn animate_entity(&self, label: impl Into<String>, animation: common::Animation) {
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::AnimateEntity3(common::AnimateEntity3 {
+                widget_label: self.label.clone(),
+                entity_label: label.into(),
+                animation: Some(animation),
+            }));
+    }
+
+    /// Stops the animation of the [entities::Entity3] with name `label`, see
+    /// [UiWidget3::animate_entity]. The entity keeps its last animated pose.
+    pub fn stop_animating_entity(&self, label: impl Into<String>) {
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::AnimateEntity3(common::AnimateEntity3 {
+                widget_label: self.label.clone(),
+                entity_label: label.into(),
+                animation: None,
+            }));
+    }
+
+    /// Sets the face culling mode of the [entities::Entity3] with name `label`, overriding the cull
+    /// mode of [UiWidget3]. Passing None restores the cull mode of the widget.
+    ///
+    /// If no such entity exists, this is no-op.
+    pub fn set_entity_cull_mode(
+        &self,
+        label: impl Into<String>,
+        cull_mode: Option<common::CullMode>,
+    ) {
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::SetEntityCullMode(
+                common::SetEntityCullMode {
+                    widget_label: self.label.clone(),
+                    entity_label: label.into(),
+                    cull_mode,
+                },
+            ));
+    }
+
+    /// Sets the depth bias of the [entities::Entity3] with name `label`, see
+    /// [common::SetEntityDepthBias]. Use a small positive value such as `1e-4` to draw an overlay
+    /// on top of coplanar geometry.
+    ///
+    /// If no such entity exists, this is no-op.
+    pub fn set_entity_depth_bias(&self, label: impl Into<String>, depth_bias: f32) {
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::SetEntityDepthBias(
+                common::SetEntityDepthBias {
+                    widget_label: self.label.clone(),
+                    entity_label: label.into(),
+                    depth_bias,
+                },
+            ));
+    }
+}
+
+/// Sequence of images from a folder, e.g. the frames of a dataset, with a slider in the side panel
+/// to scrub through them.
+///
+/// Frames are loaded lazily when selected. All frames must have the same size as the first one;
+/// frames of different size, or which fail to load, are skipped with a warning notification.
+/// Call [UiSequence::update] repeatably, e.g. next to [Manager::sync_with_gui].
+pub struct UiSequence {
+    shared: Rc<RefCell<Shared>>,
+    widget: UiWidget2,
+    paths: std::vec::Vec<std::path::PathBuf>,
+    ui_frame: UiRangedVar<usize>,
+    current_frame: usize,
+}
+
+impl UiSequence {
+    fn new(
+        shared: Rc<RefCell<Shared>>,
+        label: String,
+        paths: std::vec::Vec<std::path::PathBuf>,
+        first: image::RgbaImage,
+    ) -> Self {
+        let widget = UiWidget2::new(shared.clone(), label.clone(), first);
+        let ui_frame = UiRangedVar::<usize>::new(
+            shared.clone(),
+            format!("{} frame", label),
+            0,
+            (0, paths.len() - 1),
+        );
+        Self {
+            shared,
+            widget,
+            paths,
+            ui_frame,
+            current_frame: 0,
+        }
+    }
+
+    /// Index of the currently shown frame.
+    pub fn current_frame(&self) -> usize {
+        self.current_frame
+    }
+
+    /// Paths of all frames, sorted by name.
+    pub fn paths(&self) -> &[std::path::PathBuf] {
+        &self.paths
+    }
+
+    /// The 2d widget, e.g. to place overlays.
+    pub fn widget(&self) -> &UiWidget2 {
+        &self.widget
+    }
+
+    /// Loads and shows the selected frame if the slider was moved. Returns the new frame index if
+    /// it changed.
+    pub fn update(&mut self) -> Option<usize> {
+        let frame = self.ui_frame.get_new_value()?;
+        let path = &self.paths[frame];
+        let warning = match image::open(path).map(|image| image.into_rgba8()) {
+            Ok(rgba8) if rgba8.dimensions() == self.widget.image_size => {
+                self.widget.update_image(rgba8);
+                None
+            }
+            Ok(_) => Some(format!(
+                "{} differs in size from the first frame",
+                path.display()
+            )),
+            Err(e) => Some(format!("Failed to load {}: {}", path.display(), e)),
+        };
+        if let Some(text) = warning {
+            self.shared
+                .borrow_mut()
+                .message_queue
+                .push_back(ToGuiLoopMessage::Notify(common::Notify {
+                    text,
+                    level: common::Level::Warn,
+                }));
+        }
+        self.current_frame = frame;
+        Some(frame)
+    }
+}
+
+/// 2d widget showing a tone-mapped float image, with exposure and gamma sliders in the side panel.
+///
+/// Call [UiFloatWidget::update] repeatably, e.g. next to [Manager::sync_with_gui], so that slider
+/// changes are applied.
+pub struct UiFloatWidget {
+    widget: UiWidget2,
+    data: std::vec::Vec<f32>,
+    range: Option<(f32, f32)>,
+    colormap: super::colormap::Colormap,
+    ui_exposure: UiRangedVar<f32>,
+    ui_gamma: UiRangedVar<f32>,
+}
+
+impl UiFloatWidget {
+    /// Range of the exposure slider, in stops.
+    pub const EXPOSURE_RANGE: (f32, f32) = (-10.0, 10.0);
+    /// Range of the gamma slider.
+    pub const GAMMA_RANGE: (f32, f32) = (0.1, 5.0);
+
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        shared: Rc<RefCell<Shared>>,
+        label: String,
+        data: std::vec::Vec<f32>,
+        (width, height): (u32, u32),
+        range: Option<(f32, f32)>,
+        colormap: super::colormap::Colormap,
+        exposure: f32,
+        gamma: f32,
+    ) -> Self {
+        let rgba8 =
+            super::colormap::float_to_rgba8(&data, width, height, range, colormap, exposure, gamma);
+        let widget = UiWidget2::new(shared.clone(), label.clone(), rgba8);
+        let ui_exposure = UiRangedVar::<f32>::new(
+            shared.clone(),
+            format!("{} exposure", label),
+            exposure,
+            Self::EXPOSURE_RANGE,
+        );
+        let ui_gamma =
+            UiRangedVar::<f32>::new(shared, format!("{} gamma", label), gamma, Self::GAMMA_RANGE);
+        Self {
+            widget,
+            data,
+            range,
+            colormap,
+            ui_exposure,
+            ui_gamma,
+        }
+    }
+
+    /// The 2d widget, e.g. to place overlays.
+    pub fn widget(&self) -> &UiWidget2 {
+        &self.widget
+    }
+
+    /// Replaces the shown float image, e.g. the next frame. Panics if the size differs from the
+    /// initial image.
+    pub fn set_data(&mut self, data: std::vec::Vec<f32>) {
+        let (width, height) = self.widget.image_size;
+        assert_eq!(
+            data.len(),
+            width as usize * height as usize,
+            "data length does not match width * height"
+        );
+        self.data = data;
+        self.tone_map();
+    }
+
+    /// Sets the range (min, max) which is mapped to [0..1] before exposure and gamma; None for
+    /// auto-range, i.e. the min/max of all finite pixels.
+    pub fn set_range(&mut self, range: Option<(f32, f32)>) {
+        self.range = range;
+        self.tone_map();
+    }
+
+    /// Re-tone-maps the image if the exposure or gamma slider was moved.
+    pub fn update(&mut self) {
+        let exposure_changed = self.ui_exposure.get_new_value().is_some();
+        let gamma_changed = self.ui_gamma.get_new_value().is_some();
+        if exposure_changed || gamma_changed {
+            self.tone_map();
+        }
+    }
+
+    fn tone_map(&mut self) {
+        let (width, height) = self.widget.image_size;
+        let rgba8 = super::colormap::float_to_rgba8(
+            &self.data,
+            width,
+            height,
+            self.range,
+            self.colormap,
+            self.ui_exposure.get_value(),
+            self.ui_gamma.get_value(),
+        );
+        self.widget.update_image(rgba8);
+    }
+}
+
+/// Play/pause checkbox and step button in the side panel, which gate a processing loop for
+/// frame-by-frame debugging.
+///
+/// The state is received from the gui in [Manager::sync_with_gui], hence [UiPlayback::should_step]
+/// reflects the clicks up to the last sync. While paused, keep calling [Manager::sync_with_gui] so
+/// that the step button is noticed:
+///
+/// ```no_run
+/// vviz::app::spawn(vviz::app::VVizMode::Local, |mut manager: vviz::manager::Manager| {
+///     let mut ui_playback = manager.add_playback_controls();
+///     let mut frame = 0;
+///     loop {
+///         if ui_playback.should_step() {
+///             frame += 1;
+///             println!("processing frame {}", frame);
+///         }
+///         manager.sync_with_gui();
+///     }
+/// });
+/// ```
+pub struct UiPlayback {
+    ui_play: UiVar<bool>,
+    ui_step: UiButton,
+}
+
+impl UiPlayback {
+    /// Label of the play/pause checkbox.
+    pub const PLAY_LABEL: &'static str = "play";
+    /// Label of the step button.
+    pub const STEP_LABEL: &'static str = "step";
+
+    fn new(shared: Rc<RefCell<Shared>>) -> Self {
+        Self {
+            ui_play: UiVar::<bool>::new(shared.clone(), Self::PLAY_LABEL.to_string(), false),
+            ui_step: UiButton::new(shared, Self::STEP_LABEL.to_string()),
+        }
+    }
+
+    /// Returns true once per click on the step button, or on each call while playing.
+    pub fn should_step(&mut self) -> bool {
+        // Always consume the button press, so that it does not carry over to after playing.
+        let step = self.ui_step.was_pressed();
+        step || self.ui_play.get_value()
+    }
+
+    /// Returns true while playing, i.e. the play checkbox is checked.
+    pub fn is_playing(&mut self) -> bool {
+        self.ui_play.get_value()
+    }
+}
+
+/// Scrubs an entity through a recorded trajectory using a slider in the side panel.
+///
+/// Call [UiTrajectoryPlayer::update] repeatably, e.g. next to [Manager::sync_with_gui].
+pub struct UiTrajectoryPlayer {
+    widget: UiWidget3,
+    entity_label: String,
+    scene_poses_entity: std::vec::Vec<nalgebra::Isometry3<f32>>,
+    ui_time: UiRangedVar<f32>,
+}
+
+impl UiTrajectoryPlayer {
+    fn new(
+        shared: Rc<RefCell<Shared>>,
+        widget: &UiWidget3,
+        entity_label: String,
+        scene_poses_entity: std::vec::Vec<nalgebra::Isometry3<f32>>,
+    ) -> Self {
+        assert!(!scene_poses_entity.is_empty());
+        let max_time = (scene_poses_entity.len() - 1) as f32;
+        let ui_time = UiRangedVar::<f32>::new(
+            shared,
+            format!("{} frame", entity_label),
+            0.0,
+            (0.0, max_time),
+        );
+        widget.update_scene_pose_entity(entity_label.clone(), scene_poses_entity[0]);
+        Self {
+            widget: widget.clone(),
+            entity_label,
+            scene_poses_entity,
+            ui_time,
+        }
+    }
+
+    /// Pose at (fractional) frame index `time`, interpolated between the two closest recorded
+    /// poses.
+    pub fn pose_at(&self, time: f32) -> nalgebra::Isometry3<f32> {
+        let max_index = self.scene_poses_entity.len() - 1;
+        let time = time.clamp(0.0, max_index as f32);
+        let index = (time.floor() as usize).min(max_index);
+        let next_index = (index + 1).min(max_index);
+        self.scene_poses_entity[index]
+            .lerp_slerp(&self.scene_poses_entity[next_index], time - index as f32)
+    }
+
+    /// Updates the pose of the entity if the slider was moved.
+    pub fn update(&mut self) {
+        if let Some(time) = self.ui_time.get_new_value() {
+            self.widget
+                .update_scene_pose_entity(self.entity_label.clone(), self.pose_at(time));
+        }
     }
 }
 
@@ -482,73 +2345,202 @@ impl Manager {
         Self {
             to_gui_loop_sender,
             from_gui_loop_receiver,
-            _connection: ManagerConnection::Local(LocalConnection {}),
             shared: Rc::new(RefCell::new(Shared::default())),
         }
     }
 
     /// Constructs remote [Manager] from sender/receiver. This usually needs not be called by the
     /// user, since it is constructed by the [super::app].
-    pub fn new_remote() -> Self {
-        let listener = std::net::TcpListener::bind("127.0.0.1:9001").unwrap();
+    ///
+    /// Blocks until a remote client connected. Clients which fail the token check of `config` are
+    /// rejected, and the server keeps waiting for the next client. Fails if the server cannot bind
+    /// its address.
+    ///
+    /// Once the client disconnects - e.g. its window was closed - [Manager::sync_with_gui] returns
+    /// false. Reconnecting is not supported, since a new client would lack the gui state.
+    // The large error response type of the handshake callback is dictated by tungstenite.
+    #[allow(clippy::result_large_err)]
+    pub fn new_remote(config: RemoteConfig) -> Result<Self, VvizError> {
+        let listener =
+            std::net::TcpListener::bind(&config.address).map_err(VvizError::RemoteBind)?;
 
-        let mut websocket = tungstenite::accept(listener.accept().unwrap().0).unwrap();
+        let mut websocket = loop {
+            let stream = listener.accept().map_err(VvizError::RemoteBind)?.0;
+            let callback =
+                |request: &tungstenite::handshake::server::Request,
+                 response: tungstenite::handshake::server::Response| {
+                    if config.is_authorized(request) {
+                        Ok(response)
+                    } else {
+                        let mut error_response = tungstenite::handshake::server::ErrorResponse::new(
+                            Some("invalid or missing token".to_string()),
+                        );
+                        *error_response.status_mut() = tungstenite::http::StatusCode::UNAUTHORIZED;
+                        Err(error_response)
+                    }
+                };
+            match tungstenite::accept_hdr(stream, callback) {
+                Ok(websocket) => break websocket,
+                Err(e) => eprintln!("vviz: rejected remote client: {}", e),
+            }
+        };
         let (to_gui_loop_sender, to_gui_loop_receiver) = std::sync::mpsc::channel();
         let (from_gui_loop_sender, from_gui_loop_receiver) = std::sync::mpsc::channel();
 
-        let thread_join_handle = std::thread::spawn(move || loop {
-            let msg = websocket.read_message().unwrap();
+        // The thread ends when the client disconnects or the manager is dropped, which closes the
+        // channels and hence is noticed by the other side - i.e. [Manager::sync_with_gui] returns
+        // false. Malformed messages end the connection as well. Since the thread is not joined,
+        // the reason is logged.
+        std::thread::spawn(move || loop {
+            let msg = match websocket.read_message() {
+                Ok(tungstenite::Message::Text(msg)) => msg,
+                Ok(_) => continue,
+                Err(tungstenite::Error::ConnectionClosed | tungstenite::Error::AlreadyClosed) => {
+                    eprintln!("vviz: remote client disconnected");
+                    return;
+                }
+                Err(e) => {
+                    eprintln!("vviz: lost connection to remote client: {}", e);
+                    return;
+                }
+            };
 
-            let from_msg: Vec<FromGuiLoopMessage> =
-                serde_json::from_str(msg.to_text().unwrap()).unwrap();
+            let from_msg: Vec<FromGuiLoopMessage> = match serde_json::from_str(&msg) {
+                Ok(from_msg) => from_msg,
+                Err(e) => {
+                    eprintln!("vviz: malformed message from remote client: {}", e);
+                    return;
+                }
+            };
             for m in from_msg {
-                from_gui_loop_sender.send(m).unwrap();
+                if from_gui_loop_sender.send(m).is_err() {
+                    return;
+                }
             }
 
-            let collection: Vec<ToGuiLoopMessage> = to_gui_loop_receiver.try_iter().collect();
+            let collection: Vec<ToGuiLoopMessage> = to_gui_loop_receiver
+                .try_iter()
+                // Closures cannot be sent to the remote client.
+                .filter(|m| !matches!(m, ToGuiLoopMessage::AnimateEntity3(_)))
+                .collect();
 
-            websocket
-                .write_message(tungstenite::Message::Text(
-                    serde_json::to_string(&collection).unwrap(),
-                ))
-                .unwrap();
+            let json = match serde_json::to_string(&collection) {
+                Ok(json) => json,
+                Err(e) => {
+                    eprintln!(
+                        "vviz: failed to serialize messages for remote client: {}",
+                        e
+                    );
+                    return;
+                }
+            };
+            if let Err(e) = websocket.write_message(tungstenite::Message::Text(json)) {
+                eprintln!("vviz: lost connection to remote client: {}", e);
+                return;
+            }
 
             std::thread::sleep(std::time::Duration::from_millis(15));
         });
 
-        Self {
+        Ok(Self {
             to_gui_loop_sender,
             from_gui_loop_receiver,
-            _connection: ManagerConnection::WebsocketServer(WebsocketServerConnection {
-                _thread_join_handle: thread_join_handle,
-            }),
             shared: Rc::new(RefCell::new(Shared::default())),
-        }
+        })
     }
 
     /// Adding button to side-panel.
-    pub fn add_button(&self, label: String) -> UiButton {
-        UiButton::new(self.shared.clone(), label)
+    pub fn add_button(&self, label: impl Into<String>) -> UiButton {
+        UiButton::new(self.shared.clone(), label.into())
+    }
+
+    /// Adds a button to side-panel, which asks for confirmation showing `prompt` before it
+    /// counts as pressed. Useful for destructive actions.
+    pub fn add_confirm_button(
+        &self,
+        label: impl Into<String>,
+        prompt: impl Into<String>,
+    ) -> UiConfirmButton {
+        UiConfirmButton::new(self.shared.clone(), label.into(), prompt.into())
+    }
+
+    /// Adds a list of checkboxes to side-panel, given the `items` and whether they are initially
+    /// checked. The items are shown in the given order.
+    pub fn add_checkboxes(
+        &self,
+        label: impl Into<String>,
+        items: Vec<(String, bool)>,
+    ) -> UiCheckboxes {
+        UiCheckboxes::new(self.shared.clone(), label.into(), items)
+    }
+
+    /// Adds a color legend of `colormap` to side-panel, with values `range` (min, max) mapped to
+    /// the start and the end of the colormap; e.g. to explain a depth image added with
+    /// [Manager::add_depth16_widget]. Adding a colorbar with an existing label replaces it.
+    pub fn add_colorbar(
+        &self,
+        label: impl Into<String>,
+        colormap: super::colormap::Colormap,
+        range: (f32, f32),
+    ) {
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::AddColorbar(common::AddColorbar {
+                label: label.into(),
+                colormap,
+                range,
+            }));
+    }
+
+    /// Adds a read-only pose display to side-panel, showing the identity until
+    /// [UiPoseReadout::set_pose] is called. Its copy button puts the pose on the clipboard, as 4x4
+    /// matrix and as translation plus quaternion.
+    pub fn add_pose_readout(&self, label: impl Into<String>) -> UiPoseReadout {
+        UiPoseReadout::new(self.shared.clone(), label.into())
     }
 
     /// Adds boolean as a checkbox to side-panel.
-    pub fn add_bool(&self, label: String, value: bool) -> UiVar<bool> {
-        UiVar::<bool>::new(self.shared.clone(), label, value)
+    pub fn add_bool(&self, label: impl Into<String>, value: bool) -> UiVar<bool> {
+        UiVar::<bool>::new(self.shared.clone(), label.into(), value)
     }
 
     /// Adds number [i32, i64, f32, f64] as a read-only text box to side-panel.
-    pub fn add_number<T: common::Number>(&self, label: String, value: T) -> UiVar<T> {
-        UiVar::<T>::new(self.shared.clone(), label, value)
+    pub fn add_number<T: common::Number>(&self, label: impl Into<String>, value: T) -> UiVar<T> {
+        UiVar::<T>::new(self.shared.clone(), label.into(), value)
     }
 
     /// Adds number [i32, i64, f32, f64] as a slider to side-panel.
     pub fn add_ranged_value<T: common::Number>(
         &self,
-        label: String,
+        label: impl Into<String>,
         value: T,
         (min, max): (T, T),
     ) -> UiRangedVar<T> {
-        UiRangedVar::<T>::new(self.shared.clone(), label, value, (min, max))
+        UiRangedVar::<T>::new(self.shared.clone(), label.into(), value, (min, max))
+    }
+
+    /// Adds number [i32, i64, f32, f64] as a slider to side-panel; same as
+    /// [Manager::add_ranged_value], but with less boilerplate.
+    ///
+    /// ```no_run
+    /// vviz::app::spawn(vviz::app::VVizMode::Local, |mut manager: vviz::manager::Manager| {
+    ///     let mut ui_x = manager.add_slider("x", 0.5, -1.0..=1.0);
+    ///     loop {
+    ///         if let Some(x) = ui_x.get_new_value() {
+    ///             println!("x: {}", x);
+    ///         }
+    ///         manager.sync_with_gui();
+    ///     }
+    /// });
+    /// ```
+    pub fn add_slider<T: common::Number>(
+        &self,
+        label: impl Into<String>,
+        value: T,
+        range: std::ops::RangeInclusive<T>,
+    ) -> UiRangedVar<T> {
+        self.add_ranged_value(label.into(), value, range.into_inner())
     }
 
     /// Adds enum as combo box box to side-panel.
@@ -556,53 +2548,648 @@ impl Manager {
         T: Clone + std::fmt::Debug + ToString + strum::VariantNames + std::str::FromStr + PartialEq,
     >(
         &self,
-        label: String,
+        label: impl Into<String>,
         value: T,
     ) -> UiEnum<T> {
-        UiEnum::<T>::new(self.shared.clone(), label, value)
+        UiEnum::<T>::new(self.shared.clone(), label.into(), value)
+    }
+
+    /// Enables/disables the list of entities of all 3d widgets in the side panel, with checkboxes
+    /// to toggle their visibility. It is enabled by default; disable it e.g. if the application
+    /// provides its own layer management.
+    pub fn set_show_entity_list(&self, enabled: bool) {
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::SetShowEntityList(
+                common::SetShowEntityList { enabled },
+            ));
+    }
+
+    /// Shows or hides the labels of all widgets in the main panel, which are shown as title bars
+    /// above the widgets by default. Hide them e.g. for clean screenshots.
+    pub fn set_show_widget_titles(&self, enabled: bool) {
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::SetShowWidgetTitles(
+                common::SetShowWidgetTitles { enabled },
+            ));
+    }
+
+    /// Fixes the number of columns of the widget grid in the main panel. If None, the number of
+    /// columns is chosen such that the widgets are as large as possible, which is the default.
+    pub fn set_grid_columns(&self, num_cols: Option<usize>) {
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::SetGridColumns(common::SetGridColumns {
+                num_cols,
+            }));
+    }
+
+    /// Adds a grid of 3d widgets with `num_cols` columns, one per value of `values`, e.g. to
+    /// compare the results of a parameter sweep side by side.
+    ///
+    /// Each widget is titled, i.e. labeled, by its value, so the values shall be distinct. Then
+    /// `setup` is called per widget, e.g. to place the shared geometry and the entities which
+    /// depend on the value. This fixes the number of columns of the main panel, see
+    /// [Manager::set_grid_columns].
+    ///
+    /// Example
+    /// ``` no_run
+    /// vviz::app::spawn(vviz::app::VVizMode::Local, |mut manager: vviz::manager::Manager| {
+    ///     let widgets = manager.add_small_multiples(&[0.25, 0.5, 1.0, 2.0], 2, |scale, widget| {
+    ///         widget.place_entity("cube", vviz::entities::colored_cube(*scale));
+    ///     });
+    ///     assert_eq!(widgets.len(), 4);
+    ///     while manager.sync_with_gui() {}
+    /// });
+    /// ```
+    pub fn add_small_multiples<T: std::fmt::Display>(
+        &self,
+        values: &[T],
+        num_cols: usize,
+        mut setup: impl FnMut(&T, &UiWidget3),
+    ) -> std::vec::Vec<UiWidget3> {
+        self.set_grid_columns(Some(num_cols));
+        values
+            .iter()
+            .map(|value| {
+                let widget = self.add_widget3(value.to_string());
+                setup(value, &widget);
+                widget
+            })
+            .collect()
+    }
+
+    /// Limits the number of frames the gui draws per second, e.g. `Some(30.0)` to save power and
+    /// reduce heat during long debugging sessions. If None, frames are only paced by vsync, which
+    /// is the default; see [super::capabilities::Capabilities::vsync]. Rates below one frame per
+    /// second are treated as one, so that the gui stays responsive.
+    pub fn set_max_frame_rate(&self, max_frame_rate: Option<f32>) {
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::SetMaxFrameRate(common::SetMaxFrameRate {
+                max_frame_rate,
+            }));
+    }
+
+    /// Sets the color theme of the gui, i.e. of the panels and the background of 3d widgets.
+    pub fn set_theme(&self, theme: common::Theme) {
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::SetTheme(common::SetTheme { theme }));
+    }
+
+    /// Shows `text` as a transient notification in the corner of the window, which fades out
+    /// after a few seconds. Multiple notifications stack; `level` determines the color.
+    pub fn notify(&self, text: String, level: common::Level) {
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::Notify(common::Notify { text, level }));
     }
 
     /// Adds a new 2d widget to the main panel.
     pub fn add_widget2(
         &self,
-        label: String,
+        label: impl Into<String>,
         image: image::ImageBuffer<image::Rgba<u8>, Vec<u8>>,
     ) -> UiWidget2 {
-        UiWidget2::new(self.shared.clone(), label, image)
+        UiWidget2::new(self.shared.clone(), label.into(), image)
+    }
+
+    /// Adds a new 2d widget to the main panel, showing an 8-bit OpenCV image; see
+    /// [super::opencv_interop::mat_to_rgba8] for the supported types.
+    #[cfg(feature = "opencv")]
+    pub fn add_widget2_from_mat(
+        &self,
+        label: impl Into<String>,
+        mat: &opencv::core::Mat,
+    ) -> Result<UiWidget2, super::opencv_interop::MatError> {
+        Ok(self.add_widget2(label, super::opencv_interop::mat_to_rgba8(mat)?))
+    }
+
+    /// Adds a new 2d widget to the main panel, showing a single-channel 8-bit image.
+    ///
+    /// Each intensity is expanded to an opaque gray RGBA pixel. Panics if
+    /// `data.len() != width * height`.
+    pub fn add_gray_widget(
+        &self,
+        label: impl Into<String>,
+        data: Vec<u8>,
+        width: u32,
+        height: u32,
+    ) -> UiWidget2 {
+        self.add_widget2(label, super::utilities::gray_to_rgba8(data, width, height))
+    }
+
+    /// Adds a new 2d widget to the main panel, showing a 16-bit depth image (e.g. in millimeters).
+    ///
+    /// Depths within `range` are colorized using `colormap`; if `range` is `None` it is set to
+    /// the min/max of all valid pixels. Invalid pixels (zero or out of range) are transparent. For a
+    /// different invalid color, use [super::colormap::depth16_to_rgba8] with [Manager::add_widget2].
+    pub fn add_depth16_widget(
+        &self,
+        label: impl Into<String>,
+        depth: Vec<u16>,
+        width: u32,
+        height: u32,
+        range: Option<(u16, u16)>,
+        colormap: super::colormap::Colormap,
+    ) -> UiWidget2 {
+        let transparent = entities::Color {
+            r: 0.0,
+            g: 0.0,
+            b: 0.0,
+            alpha: 0.0,
+        };
+        self.add_widget2(
+            label,
+            super::colormap::depth16_to_rgba8(&depth, width, height, range, colormap, transparent),
+        )
+    }
+
+    /// Adds a new 2d widget to the main panel, showing a float image (e.g. radiance or disparity)
+    /// of size `width` x `height`, tone-mapped by [super::colormap::float_to_rgba8].
+    ///
+    /// If `range` is `None`, it is set to the min/max of all finite pixels. Sliders for `exposure`
+    /// (in stops) and `gamma` are added to the side panel; call [UiFloatWidget::update] to apply
+    /// their changes. Panics if `data.len() != width * height`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_float_widget(
+        &self,
+        label: impl Into<String>,
+        data: Vec<f32>,
+        width: u32,
+        height: u32,
+        range: Option<(f32, f32)>,
+        colormap: super::colormap::Colormap,
+        exposure: f32,
+        gamma: f32,
+    ) -> UiFloatWidget {
+        UiFloatWidget::new(
+            self.shared.clone(),
+            label.into(),
+            data,
+            (width, height),
+            range,
+            colormap,
+            exposure,
+            gamma,
+        )
+    }
+
+    /// Adds a new 2d widget to the main panel, showing `left` and `right` image side by side with
+    /// lines connecting the `correspondences`.
+    pub fn add_correspondences(
+        &self,
+        label: impl Into<String>,
+        left: image::RgbaImage,
+        right: image::RgbaImage,
+        correspondences: &[entities::Correspondence],
+    ) -> UiCorrespondences {
+        UiCorrespondences::new(
+            self.shared.clone(),
+            label.into(),
+            left,
+            right,
+            correspondences,
+        )
     }
 
     /// Adds a new 3d widget to the main panel.
-    pub fn add_widget3(&self, label: String) -> UiWidget3 {
-        UiWidget3::new(self.shared.clone(), label)
+    pub fn add_widget3(&self, label: impl Into<String>) -> UiWidget3 {
+        UiWidget3::new(self.shared.clone(), label.into(), None)
+    }
+
+    /// Adds a new 3d widget to the main panel, with given initial camera pose.
+    ///
+    /// Here `camera_pose_scene` is the transformation from scene to camera, with the camera
+    /// looking along its negative z axis and y pointing up. To look from `eye` at `target`, use
+    /// `nalgebra::Isometry3::look_at_rh(&eye, &target, &nalgebra::Vector3::y())`.
+    pub fn add_widget3_with_camera(
+        &self,
+        label: impl Into<String>,
+        camera_pose_scene: nalgebra::Isometry3<f32>,
+    ) -> UiWidget3 {
+        UiWidget3::new(self.shared.clone(), label.into(), Some(camera_pose_scene))
+    }
+
+    /// Adds a slider to the side-panel, which scrubs the entity `entity_label` of `widget` through
+    /// the recorded poses `scene_poses_entity`. Slider positions between two frames interpolate
+    /// the poses.
+    ///
+    /// The entity shall be placed beforehand. Panics if `scene_poses_entity` is empty.
+    pub fn add_trajectory_player(
+        &self,
+        widget: &UiWidget3,
+        entity_label: impl Into<String>,
+        scene_poses_entity: std::vec::Vec<nalgebra::Isometry3<f32>>,
+    ) -> UiTrajectoryPlayer {
+        UiTrajectoryPlayer::new(
+            self.shared.clone(),
+            widget,
+            entity_label.into(),
+            scene_poses_entity,
+        )
+    }
+
+    /// Adds a 2d widget showing the images in folder `dir` whose file names match `pattern`, e.g.
+    /// `"*.png"`, and a slider to scrub through them; see [UiSequence] and
+    /// [super::utilities::matches_pattern].
+    ///
+    /// The frames are sorted by file name. Only the first frame is loaded eagerly, which
+    /// determines the size of all frames. Returns an error if no file matches or the first frame
+    /// cannot be loaded.
+    pub fn add_image_sequence(
+        &self,
+        label: impl Into<String>,
+        dir: impl AsRef<std::path::Path>,
+        pattern: &str,
+    ) -> image::ImageResult<UiSequence> {
+        let paths = super::utilities::list_files_matching(dir.as_ref(), pattern)?;
+        let first = match paths.first() {
+            Some(path) => image::open(path)?.into_rgba8(),
+            None => {
+                return Err(image::ImageError::IoError(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    format!("no file in {} matches {}", dir.as_ref().display(), pattern),
+                )))
+            }
+        };
+        Ok(UiSequence::new(
+            self.shared.clone(),
+            label.into(),
+            paths,
+            first,
+        ))
+    }
+
+    /// Adds play/pause and step controls to the side-panel, see [UiPlayback]. The playback starts
+    /// paused.
+    ///
+    /// The controls use the labels [UiPlayback::PLAY_LABEL] and [UiPlayback::STEP_LABEL], hence
+    /// can only be added once.
+    pub fn add_playback_controls(&self) -> UiPlayback {
+        UiPlayback::new(self.shared.clone())
     }
 
     /// Sync call to update [Manager] with [super::gui::GuiLoop]. Should be called repeatably, e.g.
     /// in a loop.
     ///
+    /// Returns false if the gui is gone, e.g. since its window was closed or the remote client
+    /// disconnected; the session is over then, and the application may exit cleanly.
+    ///
     /// Example
     /// ``` no_run
     /// vviz::app::spawn(vviz::app::VVizMode::Local, |mut manager: vviz::manager::Manager| {
-    ///     let mut ui_a_button = manager.add_button("a button".to_string());
-    ///     loop {
+    ///     let mut ui_a_button = manager.add_button("a button");
+    ///     while manager.sync_with_gui() {
     ///        if ui_a_button.was_pressed() {
     ///           println!("a button pressed");
     ///         }
-    ///         manager.sync_with_gui();
     ///     }
+    ///     println!("gui closed");
     /// });
     /// ```
-    pub fn sync_with_gui(&mut self) {
+    pub fn sync_with_gui(&mut self) -> bool {
+        self.shared.borrow_mut().clear_stale_draw_once_entities();
         loop {
             let maybe_front = self.shared.borrow_mut().message_queue.pop_front();
-            if maybe_front.is_none() {
-                break;
+            match maybe_front {
+                None => break,
+                Some(m) => {
+                    if self.to_gui_loop_sender.send(m).is_err() {
+                        return false;
+                    }
+                }
+            }
+        }
+
+        {
+            // Before any gui updates, the components still have the values they were added with.
+            let shared = &mut *self.shared.borrow_mut();
+            for (label, component) in shared.components.iter() {
+                if !shared.initial_values.contains_key(label) {
+                    shared
+                        .initial_values
+                        .insert(label.clone(), component.values(label));
+                }
             }
-            self.to_gui_loop_sender.send(maybe_front.unwrap()).unwrap();
         }
 
-        for m in self.from_gui_loop_receiver.try_iter() {
-            m.update(&mut self.shared.borrow_mut().components);
+        loop {
+            match self.from_gui_loop_receiver.try_recv() {
+                Ok(m) => self.shared.borrow_mut().handle(m),
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => return false,
+            }
         }
         std::thread::sleep(std::time::Duration::from_millis(15));
+        true
+    }
+
+    /// Saves the current values of all sliders, checkboxes and combo boxes as preset `name`,
+    /// replacing an existing preset of the same name; e.g. for A/B comparisons of parameter sets.
+    ///
+    /// Presets are kept in memory for the current session only. Restore one with
+    /// [Manager::apply_preset].
+    pub fn save_preset(&self, name: impl Into<String>) {
+        self.shared.borrow_mut().save_preset(name.into());
+    }
+
+    /// Restores the values saved as preset `name`, both for the application - e.g.
+    /// [UiRangedVar::get_new_value] reports the change - and in the gui. Components added after
+    /// saving the preset keep their value; removed components are skipped.
+    ///
+    /// Returns false if there is no such preset.
+    pub fn apply_preset(&self, name: &str) -> bool {
+        let mut shared = self.shared.borrow_mut();
+        let values = match shared.presets.get(name) {
+            Some(values) => values.clone(),
+            None => return false,
+        };
+        shared.apply_values(&values);
+        true
+    }
+
+    /// Names of all presets, in the order they were first saved; see [Manager::save_preset].
+    pub fn preset_names(&self) -> std::vec::Vec<String> {
+        self.shared.borrow().presets.keys().cloned().collect()
+    }
+
+    /// Resets all sliders, checkboxes and combo boxes to the values they were added with, in the
+    /// same way as [Manager::apply_preset].
+    pub fn reset_values(&self) {
+        let mut shared = self.shared.borrow_mut();
+        let values = shared.initial_values.clone();
+        shared.apply_values(&values);
+    }
+
+    /// Adds a combo box of all presets and a row of buttons to side-panel, to save the current
+    /// values as new preset, to apply the selected preset and to reset all values - as
+    /// [Manager::save_preset], [Manager::apply_preset] and [Manager::reset_values] do. Presets
+    /// saved with the button are named "preset 1", "preset 2" etc.
+    ///
+    /// The buttons are handled by [Manager::sync_with_gui], without the need to poll them.
+    ///
+    /// Example
+    /// ```
+    /// use vviz::common::*;
+    ///
+    /// let (to_gui_loop_sender, _to_gui_loop_receiver) = std::sync::mpsc::channel();
+    /// let (from_gui_loop_sender, from_gui_loop_receiver) = std::sync::mpsc::channel();
+    /// let mut manager =
+    ///     vviz::manager::Manager::new_local(to_gui_loop_sender, from_gui_loop_receiver);
+    /// let mut ui_flag = manager.add_bool("flag", false);
+    /// manager.add_preset_controls("presets");
+    /// manager.sync_with_gui();
+    ///
+    /// // The user checks the flag, saves a preset, resets and applies the preset again.
+    /// let send = |m| from_gui_loop_sender.send(m).unwrap();
+    /// let flag = UpdateValue { label: "flag".to_string(), value: true };
+    /// send(FromGuiLoopMessage::UpdateValueBool(flag));
+    /// let action = |action| {
+    ///     FromGuiLoopMessage::UpdatePresetControls(UpdatePresetControls {
+    ///         label: "presets".to_string(),
+    ///         action,
+    ///     })
+    /// };
+    /// send(action(PresetAction::Save));
+    /// send(action(PresetAction::Reset));
+    /// manager.sync_with_gui();
+    /// assert_eq!(manager.preset_names(), vec!["preset 1".to_string()]);
+    /// assert!(!ui_flag.get_value());
+    ///
+    /// send(action(PresetAction::Apply("preset 1".to_string())));
+    /// manager.sync_with_gui();
+    /// assert!(ui_flag.get_value());
+    /// ```
+    pub fn add_preset_controls(&self, label: impl Into<String>) {
+        let label = label.into();
+        let mut shared = self.shared.borrow_mut();
+        let presets: std::vec::Vec<String> = shared.presets.keys().cloned().collect();
+        shared.components.insert(
+            label.clone(),
+            Box::new(common::PresetControls {
+                presets: presets.clone(),
+                selected: None,
+            }),
+        );
+        if !shared.preset_controls.contains(&label) {
+            shared.preset_controls.push(label.clone());
+        }
+        shared
+            .message_queue
+            .push_back(ToGuiLoopMessage::AddPresetControls(
+                common::AddPresetControls { label, presets },
+            ));
+    }
+
+    /// Runs `task` on a background thread, so that the gui keeps responding - i.e.
+    /// [Manager::sync_with_gui] can be called - while a long computation is running.
+    ///
+    /// The manager itself is not [Send], hence the task cannot access it. Instead, the result is
+    /// moved back into the application thread by polling the returned [TaskHandle], which is
+    /// typically done in the sync loop:
+    ///
+    /// ``` no_run
+    /// vviz::app::spawn(vviz::app::VVizMode::Local, |mut manager: vviz::manager::Manager| {
+    ///     let mut task = manager.spawn_task(|| (0..100_000_000u64).sum::<u64>());
+    ///     loop {
+    ///         if let Some(sum) = task.poll() {
+    ///             println!("result: {}", sum);
+    ///         }
+    ///         manager.sync_with_gui();
+    ///     }
+    /// });
+    /// ```
+    pub fn spawn_task<T: Send + 'static>(
+        &self,
+        task: impl FnOnce() -> T + Send + 'static,
+    ) -> TaskHandle<T> {
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(task));
+            // The handle might have been dropped already, in which case nobody is interested.
+            let _ = sender.send(result);
+        });
+        TaskHandle {
+            receiver,
+            result: None,
+        }
+    }
+
+    /// Enables or disables forwarding of keyboard and mouse events from the gui, see
+    /// [Manager::poll_events]. It is disabled by default, so that applications which never poll
+    /// do not accumulate events. Disabling it drops all events which were not polled yet.
+    pub fn set_input_events_enabled(&self, enabled: bool) {
+        let mut shared = self.shared.borrow_mut();
+        if !enabled {
+            shared.input_events.clear();
+        }
+        shared
+            .message_queue
+            .push_back(ToGuiLoopMessage::SetInputEventForwarding(
+                common::SetInputEventForwarding { enabled },
+            ));
+    }
+
+    /// Returns all keyboard and mouse events received since the last call, oldest first.
+    ///
+    /// Events are received in [Manager::sync_with_gui], once enabled with
+    /// [Manager::set_input_events_enabled]. See [common::InputEvent] for details.
+    ///
+    /// Example
+    /// ``` no_run
+    /// vviz::app::spawn(vviz::app::VVizMode::Local, |mut manager: vviz::manager::Manager| {
+    ///     manager.set_input_events_enabled(true);
+    ///     loop {
+    ///         for event in manager.poll_events() {
+    ///             if let vviz::common::InputEvent::KeyDown { key, .. } = event {
+    ///                 if key == "Space" {
+    ///                     println!("step");
+    ///                 }
+    ///             }
+    ///         }
+    ///         manager.sync_with_gui();
+    ///     }
+    /// });
+    /// ```
+    pub fn poll_events(&mut self) -> std::vec::Vec<common::InputEvent> {
+        std::mem::take(&mut self.shared.borrow_mut().input_events)
+    }
+
+    /// The DPI scale of the gui window, i.e. the number of physical pixels per logical point;
+    /// e.g. 2.0 on most high-DPI screens. None until it was received in
+    /// [Manager::sync_with_gui].
+    ///
+    /// Positions of [common::InputEvent]s are in physical pixels, while the gui - and hence widget
+    /// sizes - are laid out in points. The scale is determined by the platform; the window is
+    /// created DPI-aware, and the gui always uses this scale. To enlarge the gui beyond it, see
+    /// [Manager::set_ui_scale].
+    pub fn dpi_scale(&self) -> Option<f32> {
+        self.shared.borrow().dpi_scale
+    }
+
+    /// Scales the text and spacing of the gui by `scale` on top of the DPI scale of the window,
+    /// e.g. 1.5 for 50% larger panels on a presentation screen. The default is 1.0; scales are
+    /// clamped to [0.25, 4.0].
+    ///
+    /// 3d widgets are still rendered at the physical resolution of the window, see
+    /// [Manager::dpi_scale].
+    pub fn set_ui_scale(&self, scale: f32) {
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::SetUiScale(common::SetUiScale { scale }));
+    }
+
+    /// Saves a snapshot of all components and 3d widgets to the json file at `path`.
+    ///
+    /// The file is written asynchronously by the gui - in remote mode on the client side - and the
+    /// result is reported as notification. See [gui::GuiSnapshot].
+    pub fn save_snapshot(&self, path: std::path::PathBuf) {
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::SaveSnapshot(common::SaveSnapshot {
+                path,
+            }));
+    }
+
+    /// Saves a screenshot of the whole window - side panel and all widgets - as png file at
+    /// `path`, e.g. to share a debugging state.
+    ///
+    /// The window is captured by the gui after its next frame, at full (high-DPI) resolution, and
+    /// the result is reported as notification.
+    pub fn save_window_png(&self, path: std::path::PathBuf) {
+        self.shared
+            .borrow_mut()
+            .message_queue
+            .push_back(ToGuiLoopMessage::SaveWindowPng(common::SaveWindowPng {
+                path,
+            }));
+    }
+
+    /// Loads a snapshot saved by [Manager::save_snapshot], adding its components and 3d widgets.
+    ///
+    /// Existing components and widgets with the same labels are replaced. The loaded components
+    /// and entities behave as if they were added through this manager, e.g. their labels are
+    /// listed by [UiWidget3::entity_labels] and their values are restored by
+    /// [Manager::reset_values].
+    ///
+    /// Example
+    /// ```
+    /// let (to_gui_loop_sender, to_gui_loop_receiver) = std::sync::mpsc::channel();
+    /// let (_from_gui_loop_sender, from_gui_loop_receiver) = std::sync::mpsc::channel();
+    /// let mut manager =
+    ///     vviz::manager::Manager::new_local(to_gui_loop_sender, from_gui_loop_receiver);
+    /// let w3d = manager.add_widget3("w3d");
+    /// w3d.place_entity("cube", vviz::entities::colored_cube(1.0));
+    /// manager.sync_with_gui();
+    ///
+    /// // Save the state of the gui.
+    /// let mut data = vviz::gui::GuiData::default();
+    /// for m in to_gui_loop_receiver.try_iter() {
+    ///     m.update_gui(&mut data);
+    /// }
+    /// let path = std::env::temp_dir().join("vviz_load_snapshot_doctest.json");
+    /// std::fs::write(&path, serde_json::to_string(&data.to_snapshot()).unwrap()).unwrap();
+    ///
+    /// w3d.clear_entities();
+    /// manager.load_snapshot(&path).unwrap();
+    /// assert_eq!(w3d.entity_labels(), vec!["cube".to_string()]);
+    /// ```
+    pub fn load_snapshot(&self, path: &std::path::Path) -> std::io::Result<()> {
+        let json = std::fs::read_to_string(path)?;
+        let snapshot: gui::GuiSnapshot = serde_json::from_str(&json)?;
+        // Mirror the gui state, to rebuild the manager-side state from it; the mirror then
+        // recreates the messages for the gui.
+        let mirror = gui::GuiData::from_snapshot(snapshot);
+        let messages = mirror.to_snapshot().messages;
+
+        let mut shared = self.shared.borrow_mut();
+        for (widget_label, widget) in &mirror.widgets {
+            let widget = match widget.downcast_ref::<common::Widget3>() {
+                Some(widget) => widget,
+                None => continue,
+            };
+            shared.entity_labels.insert(
+                widget_label.clone(),
+                widget
+                    .entities()
+                    .keys()
+                    .map(|entity_label| (entity_label.clone(), ()))
+                    .collect(),
+            );
+            shared.draw_once_counts.remove(widget_label);
+            shared
+                .opacity_controls
+                .retain(|_, (w, _)| w != widget_label);
+            for entity_label in widget.entities().keys() {
+                let slider_label = UiWidget3::opacity_slider_label(widget_label, entity_label);
+                if mirror.components.contains_key(&slider_label) {
+                    shared
+                        .opacity_controls
+                        .insert(slider_label, (widget_label.clone(), entity_label.clone()));
+                }
+            }
+        }
+        for (label, component) in mirror.components {
+            if component.is::<common::PresetControls>() && !shared.preset_controls.contains(&label)
+            {
+                shared.preset_controls.push(label.clone());
+            }
+            // The loaded values are the initial values, once synced.
+            shared.initial_values.remove(&label);
+            shared.components.insert(label, component);
+        }
+        shared.message_queue.extend(messages);
+        Ok(())
     }
 }
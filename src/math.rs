@@ -44,3 +44,201 @@ pub fn rot_z<T: nalgebra::RealField>(z: T) -> nalgebra::Isometry3<T> {
         nalgebra::UnitQuaternion::from_scaled_axis(scaled_axis),
     )
 }
+
+/// Rotation which maps direction `from` onto direction `to`.
+///
+/// If `from` and `to` are antiparallel, the rotation is by 180 degrees about an arbitrary axis
+/// perpendicular to `from`. If either vector has zero length, the identity is returned.
+///
+/// Example
+/// ```
+/// let z = nalgebra::Vector3::z();
+/// let dir = nalgebra::Vector3::new(1.0, 1.0, 0.0);
+/// let rotation = vviz::math::rotation_between(z, dir);
+/// assert!((rotation * z - dir.normalize()).norm() < 1e-6);
+/// ```
+pub fn rotation_between(
+    from: nalgebra::Vector3<f32>,
+    to: nalgebra::Vector3<f32>,
+) -> nalgebra::UnitQuaternion<f32> {
+    if from.norm() <= f32::EPSILON || to.norm() <= f32::EPSILON {
+        return nalgebra::UnitQuaternion::identity();
+    }
+    match nalgebra::UnitQuaternion::rotation_between(&from, &to) {
+        Some(rotation) => rotation,
+        None => {
+            // Antiparallel: pick the canonical axis least aligned with `from` to get a
+            // well-conditioned perpendicular axis.
+            let from = from.normalize();
+            let canonical = if from.x.abs() < 0.9 {
+                nalgebra::Vector3::x()
+            } else {
+                nalgebra::Vector3::y()
+            };
+            let axis = nalgebra::Unit::new_normalize(from.cross(&canonical));
+            nalgebra::UnitQuaternion::from_axis_angle(&axis, std::f32::consts::PI)
+        }
+    }
+}
+
+/// Perspective projection from camera to clip space, for a viewport of given aspect ratio (width /
+/// height) and vertical field of view in radians.
+///
+/// Example
+/// ```
+/// // A 16:9 viewport: points at equal angles left and right of the optical axis project
+/// // symmetrically, and a point at 45 degrees horizontally and vertically is not stretched.
+/// let proj = vviz::math::perspective(90.0_f32.to_radians(), 16.0 / 9.0, 0.01, 100.0);
+/// let ndc = |x: f32, y: f32| {
+///     let p = proj * nalgebra::Vector4::new(x, y, -1.0, 1.0);
+///     nalgebra::Vector2::new(p.x / p.w, p.y / p.w)
+/// };
+/// assert!((ndc(-0.5, 0.0).x + ndc(0.5, 0.0).x).abs() < 1e-6);
+/// let diagonal = ndc(1.0, 1.0);
+/// assert!((diagonal.y - 1.0).abs() < 1e-6);
+/// assert!((diagonal.x - 9.0 / 16.0).abs() < 1e-6);
+/// ```
+pub fn perspective(
+    fov_y_radians: f32,
+    aspect_ratio: f32,
+    near: f32,
+    far: f32,
+) -> nalgebra::Matrix4<f32> {
+    nalgebra_glm::perspective_fov_rh(fov_y_radians, aspect_ratio, 1.0, near, far)
+}
+
+/// Orthographic projection from camera to clip space, for a viewport of given aspect ratio (width
+/// / height), showing `height` scene units vertically.
+///
+/// Example
+/// ```
+/// // The projected size does not depend on the depth.
+/// let proj = vviz::math::orthographic(4.0, 2.0, 0.01, 100.0);
+/// for depth in [1.0, 10.0] {
+///     let p = proj * nalgebra::Vector4::new(4.0, 2.0, -depth, 1.0);
+///     assert!((p.x / p.w - 1.0).abs() < 1e-6);
+///     assert!((p.y / p.w - 1.0).abs() < 1e-6);
+/// }
+/// ```
+pub fn orthographic(height: f32, aspect_ratio: f32, near: f32, far: f32) -> nalgebra::Matrix4<f32> {
+    let (half_width, half_height) = (0.5 * height * aspect_ratio, 0.5 * height);
+    nalgebra_glm::ortho_rh(
+        -half_width,
+        half_width,
+        -half_height,
+        half_height,
+        near,
+        far,
+    )
+}
+
+/// Returns true if the bounding box `aabb` is certainly outside of the view frustum.
+///
+/// Here, `clip_from_entity` is the model-view-projection matrix mapping entity coordinates to
+/// clip space. The test is conservative: a box is only reported outside if all its corners are
+/// beyond the same frustum plane, so some boxes near frustum corners are reported inside.
+///
+/// Example
+/// ```
+/// let proj = nalgebra_glm::perspective_fov_rh(1.0f32, 640.0, 480.0, 0.01, 10.0);
+/// let aabb = vviz::entities::Aabb3 {
+///     min: nalgebra::Vector3::new(-1.0, -1.0, -1.0),
+///     max: nalgebra::Vector3::new(1.0, 1.0, 1.0),
+/// };
+/// let in_front = nalgebra::Matrix4::new_translation(&nalgebra::Vector3::new(0.0, 0.0, -4.0));
+/// let behind = nalgebra::Matrix4::new_translation(&nalgebra::Vector3::new(0.0, 0.0, 4.0));
+/// assert!(!vviz::math::is_aabb_outside_frustum(&(proj * in_front), &aabb));
+/// assert!(vviz::math::is_aabb_outside_frustum(&(proj * behind), &aabb));
+/// ```
+pub fn is_aabb_outside_frustum(
+    clip_from_entity: &nalgebra::Matrix4<f32>,
+    aabb: &super::entities::Aabb3,
+) -> bool {
+    let corners = aabb.corners().map(|c| clip_from_entity * c.push(1.0));
+    // Planes of the clip volume: -w <= x, y, z <= w.
+    (0..3).any(|i| corners.iter().all(|c| c[i] < -c.w) || corners.iter().all(|c| c[i] > c.w))
+}
+
+/// Distance `t` along the ray `origin + t * direction`, t >= 0, at which the ray enters the
+/// bounding box `aabb`; 0 if `origin` is inside the box. Returns None if the ray misses the box.
+///
+/// Example
+/// ```
+/// let aabb = vviz::entities::Aabb3 {
+///     min: nalgebra::Vector3::new(-1.0, -1.0, -1.0),
+///     max: nalgebra::Vector3::new(1.0, 1.0, 1.0),
+/// };
+/// let origin = nalgebra::Vector3::new(0.0, 0.0, 5.0);
+/// let t = vviz::math::ray_aabb_intersection(&origin, &-nalgebra::Vector3::z(), &aabb);
+/// assert_eq!(t, Some(4.0));
+/// assert_eq!(vviz::math::ray_aabb_intersection(&origin, &nalgebra::Vector3::z(), &aabb), None);
+/// ```
+pub fn ray_aabb_intersection(
+    origin: &nalgebra::Vector3<f32>,
+    direction: &nalgebra::Vector3<f32>,
+    aabb: &super::entities::Aabb3,
+) -> Option<f32> {
+    let (mut t_enter, mut t_exit) = (0.0_f32, f32::INFINITY);
+    for i in 0..3 {
+        if direction[i] == 0.0 {
+            if origin[i] < aabb.min[i] || origin[i] > aabb.max[i] {
+                return None;
+            }
+            continue;
+        }
+        let t0 = (aabb.min[i] - origin[i]) / direction[i];
+        let t1 = (aabb.max[i] - origin[i]) / direction[i];
+        t_enter = t_enter.max(t0.min(t1));
+        t_exit = t_exit.min(t0.max(t1));
+        if t_enter > t_exit {
+            return None;
+        }
+    }
+    Some(t_enter)
+}
+
+/// Distance `t` along the ray `origin + t * direction`, t >= 0, at which the ray hits the
+/// `triangle`, from either side. Returns None if the ray misses the triangle or is parallel to it.
+///
+/// Example
+/// ```
+/// let triangle = [
+///     nalgebra::Vector3::new(0.0, 0.0, 0.0),
+///     nalgebra::Vector3::new(1.0, 0.0, 0.0),
+///     nalgebra::Vector3::new(0.0, 1.0, 0.0),
+/// ];
+/// let direction = -nalgebra::Vector3::z();
+/// let hit = |x, y| {
+///     let origin = nalgebra::Vector3::new(x, y, 2.0);
+///     vviz::math::ray_triangle_intersection(&origin, &direction, &triangle)
+/// };
+/// assert_eq!(hit(0.25, 0.25), Some(2.0));
+/// assert_eq!(hit(1.0, 1.0), None);
+/// ```
+pub fn ray_triangle_intersection(
+    origin: &nalgebra::Vector3<f32>,
+    direction: &nalgebra::Vector3<f32>,
+    triangle: &[nalgebra::Vector3<f32>; 3],
+) -> Option<f32> {
+    // Moeller-Trumbore: solves origin + t * direction = a + u * (b - a) + v * (c - a).
+    let [a, b, c] = triangle;
+    let (ab, ac) = (b - a, c - a);
+    let p = direction.cross(&ac);
+    let det = ab.dot(&p);
+    // Relative to the scale of the inputs, such that the test does not depend on units.
+    if det.abs() <= f32::EPSILON * ab.norm() * ac.norm() * direction.norm() {
+        return None;
+    }
+    let s = origin - a;
+    let u = s.dot(&p) / det;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+    let q = s.cross(&ab);
+    let v = direction.dot(&q) / det;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+    let t = ac.dot(&q) / det;
+    (t >= 0.0).then_some(t)
+}
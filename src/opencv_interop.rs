@@ -0,0 +1,108 @@
+//! Conversions from OpenCV `Mat`s; only available with the `opencv` feature.
+
+use opencv::core::{Mat, Vec3b, Vec3f, Vec4b};
+use opencv::prelude::*;
+
+/// Error of the `Mat` conversions.
+#[derive(Debug)]
+pub enum MatError {
+    /// The `Mat` has a type, or shape, which is not supported by the conversion.
+    UnsupportedType {
+        /// OpenCV type of the `Mat`, e.g. `CV_32FC1`.
+        typ: i32,
+        /// Number of columns of the `Mat`.
+        cols: i32,
+        /// Description of the supported types.
+        expected: &'static str,
+    },
+    /// Error from the opencv crate.
+    OpenCv(opencv::Error),
+}
+
+impl std::fmt::Display for MatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MatError::UnsupportedType {
+                typ,
+                cols,
+                expected,
+            } => {
+                let typ = opencv::core::type_to_string(*typ).unwrap_or_else(|_| typ.to_string());
+                write!(
+                    f,
+                    "unsupported Mat of type {} with {} columns, expected {}",
+                    typ, cols, expected
+                )
+            }
+            MatError::OpenCv(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for MatError {}
+
+impl From<opencv::Error> for MatError {
+    fn from(e: opencv::Error) -> Self {
+        MatError::OpenCv(e)
+    }
+}
+
+/// Converts 8-bit image to an RGBA image; BGR (`CV_8UC3`) and gray (`CV_8UC1`) images are opaque,
+/// and `CV_8UC4` is interpreted as BGRA - OpenCV's channel order.
+pub fn mat_to_rgba8(mat: &Mat) -> Result<image::RgbaImage, MatError> {
+    let (width, height) = (mat.cols() as u32, mat.rows() as u32);
+    let mut bytes = std::vec::Vec::with_capacity(4 * width as usize * height as usize);
+    for row in 0..mat.rows() {
+        match mat.typ() {
+            opencv::core::CV_8UC1 => {
+                for gray in mat.at_row::<u8>(row)? {
+                    bytes.extend_from_slice(&[*gray, *gray, *gray, 255]);
+                }
+            }
+            opencv::core::CV_8UC3 => {
+                for bgr in mat.at_row::<Vec3b>(row)? {
+                    bytes.extend_from_slice(&[bgr[2], bgr[1], bgr[0], 255]);
+                }
+            }
+            opencv::core::CV_8UC4 => {
+                for bgra in mat.at_row::<Vec4b>(row)? {
+                    bytes.extend_from_slice(&[bgra[2], bgra[1], bgra[0], bgra[3]]);
+                }
+            }
+            typ => {
+                return Err(MatError::UnsupportedType {
+                    typ,
+                    cols: mat.cols(),
+                    expected: "CV_8UC1, CV_8UC3 or CV_8UC4",
+                })
+            }
+        }
+    }
+    Ok(image::RgbaImage::from_raw(width, height, bytes).unwrap())
+}
+
+/// Converts Nx3 float `Mat` - i.e. `CV_32FC1` with three columns, or `CV_32FC3` with a single
+/// column - to a list of 3d points.
+pub fn mat_to_points(mat: &Mat) -> Result<std::vec::Vec<[f32; 3]>, MatError> {
+    let mut points = std::vec::Vec::with_capacity(mat.rows() as usize);
+    for row in 0..mat.rows() {
+        match (mat.typ(), mat.cols()) {
+            (opencv::core::CV_32FC1, 3) => {
+                let p = mat.at_row::<f32>(row)?;
+                points.push([p[0], p[1], p[2]]);
+            }
+            (opencv::core::CV_32FC3, 1) => {
+                let p = mat.at_row::<Vec3f>(row)?[0];
+                points.push([p[0], p[1], p[2]]);
+            }
+            (typ, cols) => {
+                return Err(MatError::UnsupportedType {
+                    typ,
+                    cols,
+                    expected: "CV_32FC1 with 3 columns, or CV_32FC3 with 1 column",
+                })
+            }
+        }
+    }
+    Ok(points)
+}
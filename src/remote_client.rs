@@ -1,6 +1,22 @@
+use clap::Parser;
+use tungstenite::client::IntoClientRequest;
 use vviz::common::FromGuiLoopMessage;
 
+/// Remote visualization client arguments
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct ClientArgs {
+    /// address of the remote app
+    #[clap(long, default_value = "localhost:9001")]
+    address: String,
+    /// shared secret token expected by the remote app
+    #[clap(long)]
+    token: Option<String>,
+}
+
 fn main() {
+    let args = ClientArgs::parse();
+
     let (to_gui_loop_sender, to_gui_loop_receiver) = std::sync::mpsc::channel();
 
     let (from_gui_loop_sender, from_gui_loop_receiver) = std::sync::mpsc::channel();
@@ -11,9 +27,17 @@ fn main() {
     };
 
     std::thread::spawn(move || {
-        let (mut socket, _response) =
-            tungstenite::connect(reqwest::Url::parse("ws://localhost:9001").unwrap())
-                .expect("Can't connect");
+        let mut request = reqwest::Url::parse(&format!("ws://{}", args.address))
+            .unwrap()
+            .into_client_request()
+            .unwrap();
+        if let Some(token) = args.token {
+            request.headers_mut().insert(
+                tungstenite::http::header::AUTHORIZATION,
+                format!("Bearer {}", token).parse().unwrap(),
+            );
+        }
+        let (mut socket, _response) = tungstenite::connect(request).expect("Can't connect");
 
         loop {
             let collection: Vec<FromGuiLoopMessage> = from_gui_loop_receiver.try_iter().collect();
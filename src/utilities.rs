@@ -32,3 +32,167 @@ pub fn load_image_from_url<T: reqwest::IntoUrl>(
             .decode()?;
     Ok(img)
 }
+
+/// Places `left` and `right` image next to each other, separated by a transparent gap of `gap`
+/// pixels. The result is as high as the higher of both images.
+pub fn concat_images_horizontally(
+    left: &image::RgbaImage,
+    right: &image::RgbaImage,
+    gap: u32,
+) -> image::RgbaImage {
+    let mut result = image::RgbaImage::new(
+        left.width() + gap + right.width(),
+        left.height().max(right.height()),
+    );
+    image::imageops::replace(&mut result, left, 0, 0);
+    image::imageops::replace(&mut result, right, (left.width() + gap) as i64, 0);
+    result
+}
+
+/// Expands a single-channel 8-bit image of size `width` x `height` to RGBA, with `r=g=b=value`
+/// and `a=255`.
+///
+/// Panics if `data.len() != width * height`.
+///
+/// Example
+/// ```
+/// let rgba = vviz::utilities::gray_to_rgba8(vec![0, 128, 255, 7], 2, 2);
+/// assert_eq!(rgba.dimensions(), (2, 2));
+/// assert_eq!(rgba.get_pixel(1, 0).0, [128, 128, 128, 255]);
+/// ```
+pub fn gray_to_rgba8(data: Vec<u8>, width: u32, height: u32) -> image::RgbaImage {
+    assert_eq!(
+        data.len(),
+        width as usize * height as usize,
+        "data length does not match width * height"
+    );
+    let gray = image::GrayImage::from_raw(width, height, data).unwrap();
+    image::DynamicImage::ImageLuma8(gray).into_rgba8()
+}
+
+/// Epipolar line `l = F * x` in the second image, corresponding to `point` x in the first image.
+///
+/// Here `f` is the fundamental matrix and the returned line `(a, b, c)` is in homogeneous form,
+/// i.e. all points `(u, v)` on the line satisfy `a * u + b * v + c = 0`.
+pub fn epipolar_line(
+    f: &nalgebra::Matrix3<f32>,
+    point: nalgebra::Vector2<f32>,
+) -> nalgebra::Vector3<f32> {
+    f * point.push(1.0)
+}
+
+/// Clips the homogeneous 2d `line` to the image rectangle `[0, width] x [0, height]`.
+///
+/// Returns the two end points of the visible part of the line, or None if the line does not
+/// intersect the image (or is degenerate).
+///
+/// Example
+/// ```
+/// // horizontal line v = 5
+/// let line = nalgebra::Vector3::new(0.0, 1.0, -5.0);
+/// let (a, b) = vviz::utilities::clip_line_to_image(&line, 10, 10).unwrap();
+/// assert_eq!((a.y, b.y), (5.0, 5.0));
+/// assert_eq!((a.x - b.x).abs(), 10.0);
+///
+/// // v = 20 is below the image.
+/// let line = nalgebra::Vector3::new(0.0, 1.0, -20.0);
+/// assert!(vviz::utilities::clip_line_to_image(&line, 10, 10).is_none());
+///
+/// // The scale of the line does not matter, e.g. for lines from an unnormalized F.
+/// let line = nalgebra::Vector3::new(0.0, 1e-9, -5e-9);
+/// let (a, b) = vviz::utilities::clip_line_to_image(&line, 10, 10).unwrap();
+/// assert_eq!((a.y, b.y), (5.0, 5.0));
+/// ```
+pub fn clip_line_to_image(
+    line: &nalgebra::Vector3<f32>,
+    width: u32,
+    height: u32,
+) -> Option<(nalgebra::Vector2<f32>, nalgebra::Vector2<f32>)> {
+    // Normalized, such that (a, b) is the unit normal of the line.
+    let norm = line.xy().norm();
+    if !(norm > 0.0 && norm.is_finite()) {
+        return None;
+    }
+    let (a, b, c) = (line.x / norm, line.y / norm, line.z / norm);
+    let (w, h) = (width as f32, height as f32);
+    let mut candidates = std::vec::Vec::with_capacity(4);
+    if b.abs() > f32::EPSILON {
+        // intersections with left and right image border
+        for u in [0.0, w] {
+            candidates.push(nalgebra::Vector2::new(u, -(a * u + c) / b));
+        }
+    }
+    if a.abs() > f32::EPSILON {
+        // intersections with top and bottom image border
+        for v in [0.0, h] {
+            candidates.push(nalgebra::Vector2::new(-(b * v + c) / a, v));
+        }
+    }
+    let eps = 1e-3 * w.max(h);
+    let inside: std::vec::Vec<_> = candidates
+        .into_iter()
+        .filter(|p| p.x >= -eps && p.x <= w + eps && p.y >= -eps && p.y <= h + eps)
+        .collect();
+
+    let mut best: Option<(nalgebra::Vector2<f32>, nalgebra::Vector2<f32>)> = None;
+    for i in 0..inside.len() {
+        for j in (i + 1)..inside.len() {
+            let longer = match best {
+                Some((p, q)) => (inside[i] - inside[j]).norm() > (p - q).norm(),
+                None => true,
+            };
+            if longer {
+                best = Some((inside[i], inside[j]));
+            }
+        }
+    }
+    best.filter(|(p, q)| (p - q).norm() > eps)
+}
+
+/// Whether file `name` matches `pattern`, where `*` matches any sequence of characters and `?`
+/// matches a single character.
+///
+/// Example
+/// ```
+/// use vviz::utilities::matches_pattern;
+///
+/// assert!(matches_pattern("frame_0001.png", "*.png"));
+/// assert!(matches_pattern("frame_0001.png", "frame_????.png"));
+/// assert!(!matches_pattern("frame_0001.jpg", "*.png"));
+/// assert!(!matches_pattern("frame_1.png", "frame_????.png"));
+/// ```
+pub fn matches_pattern(name: &str, pattern: &str) -> bool {
+    fn matches(name: &[char], pattern: &[char]) -> bool {
+        match pattern.split_first() {
+            None => name.is_empty(),
+            Some(('*', rest)) => (0..=name.len()).any(|i| matches(&name[i..], rest)),
+            Some((p, rest)) => match name.split_first() {
+                Some((n, name_rest)) if *p == '?' || p == n => matches(name_rest, rest),
+                _ => false,
+            },
+        }
+    }
+    let name: std::vec::Vec<char> = name.chars().collect();
+    let pattern: std::vec::Vec<char> = pattern.chars().collect();
+    matches(&name, &pattern)
+}
+
+/// Lists all files in `dir` whose name matches `pattern` (see [matches_pattern]), sorted by name.
+pub fn list_files_matching(
+    dir: &std::path::Path,
+    pattern: &str,
+) -> std::io::Result<std::vec::Vec<std::path::PathBuf>> {
+    let mut paths = std::vec::Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        let name_matches = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| matches_pattern(name, pattern));
+        if name_matches && path.is_file() {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}